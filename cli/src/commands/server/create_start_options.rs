@@ -1,5 +1,6 @@
 use std::{env, fs};
 use jumpdrive_auth::services::jwt_service::{RsaPrivateKey,DecodeRsaPrivateKey};
+use ledgero_api::init::blob_storage_options::{BlobStorageOptions, S3Options};
 use ledgero_api::init::start_options::StartOptions;
 
 pub fn create_start_options() -> StartOptions {
@@ -37,6 +38,21 @@ pub fn create_start_options() -> StartOptions {
         .parse()
         .expect("MAX_BLOB_UNCONFIRMED is not a u32");
 
+    let blob_storage = match env::var("BLOB_STORAGE").as_deref() {
+        Ok("s3") => BlobStorageOptions::S3(S3Options {
+            endpoint: env::var("S3_ENDPOINT").expect("S3_ENDPOINT not set"),
+            region: env::var("S3_REGION").expect("S3_REGION not set"),
+            bucket: env::var("S3_BUCKET").expect("S3_BUCKET not set"),
+            access_key_id: env::var("S3_ACCESS_KEY_ID").expect("S3_ACCESS_KEY_ID not set"),
+            secret_access_key: env::var("S3_SECRET_ACCESS_KEY")
+                .expect("S3_SECRET_ACCESS_KEY not set"),
+        }),
+        Ok("filesystem") | Err(_) => BlobStorageOptions::Filesystem {
+            root: env::var("BLOB_ROOT").ok().map(Into::into),
+        },
+        Ok(other) => panic!("BLOB_STORAGE should be 'filesystem' or 's3', not '{}'", other),
+    };
+
     StartOptions {
         database_url: db_connection_string,
         memcached_url: memcached_connection_string,
@@ -45,5 +61,6 @@ pub fn create_start_options() -> StartOptions {
         jwt_refresh_expire_seconds: access_refresh_seconds,
         jwt_signing_key: private_key,
         max_blob_unconfirmed,
+        blob_storage,
     }
 }
@@ -0,0 +1,40 @@
+CREATE TABLE rules
+(
+    id                      varchar(36) not null,
+    user_id                 varchar(36) not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    name                    varchar     not null,
+    priority                int         not null,
+
+    -- Conditions. Every condition that is set has to match for the rule to apply.
+    description_contains    varchar     null,
+    description_regex       varchar     null,
+    min_amount              bigint      null,
+    max_amount              bigint      null,
+    -- The IBAN of the other party, the bank account of the transaction itself is bank_account_id.
+    iban                    varchar     null,
+    bank_account_id         varchar(36) null,
+    weekday                 int         null,
+
+    -- Actions that are performed when the rule matches.
+    set_category_id         varchar(36) null,
+    set_subcategory_id      varchar(36) null,
+    set_description         varchar     null,
+    set_external_account_id varchar(36) null,
+
+    primary key (id, user_id),
+    foreign key (bank_account_id, user_id)
+        references bank_accounts (id, user_id)
+        on delete cascade,
+    foreign key (set_category_id, user_id)
+        references categories (id, user_id)
+        on delete set null (set_category_id),
+    foreign key (set_subcategory_id, set_category_id, user_id)
+        references subcategories (id, parent_category, user_id)
+        on delete set null (set_subcategory_id),
+    foreign key (set_external_account_id, user_id)
+        references external_accounts (id, user_id)
+        on delete set null (set_external_account_id)
+);
@@ -0,0 +1,26 @@
+CREATE TABLE budgets
+(
+    id             varchar(36)              not null,
+    user_id        varchar(36)              not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    category_id    varchar(36)              not null,
+    subcategory_id varchar(36)              null,
+
+    -- The amount in cents that is available to spend each period.
+    amount         bigint                   not null,
+    period         varchar                  not null,
+    rollover       boolean                  not null,
+    starts_at      timestamp with time zone not null,
+
+    primary key (id, user_id),
+    foreign key (category_id, user_id)
+        references categories (id, user_id)
+        on delete cascade,
+    foreign key (subcategory_id, category_id, user_id)
+        references subcategories (id, parent_category, user_id)
+        on delete cascade,
+
+    CONSTRAINT unique_budget UNIQUE NULLS NOT DISTINCT (user_id, category_id, subcategory_id)
+);
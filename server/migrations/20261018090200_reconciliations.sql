@@ -0,0 +1,18 @@
+CREATE TABLE reconciliations
+(
+    id              varchar(36)              not null,
+    user_id         varchar(36)              not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    bank_account_id varchar(36)              not null,
+    date            timestamp with time zone not null,
+
+    -- The balance in cents the bank reported on the statement at the given date.
+    balance         bigint                   not null,
+
+    primary key (id, user_id),
+    foreign key (bank_account_id, user_id)
+        references bank_accounts (id, user_id)
+        on delete cascade
+);
@@ -0,0 +1,29 @@
+CREATE TABLE csv_mapping_profiles
+(
+    id                    varchar(36) not null,
+    user_id               varchar(36) not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    name                  varchar     not null,
+
+    -- The normalized header row of the CSV files this profile belongs to, used to recognize the
+    -- profile when importing a new file.
+    header                varchar     null,
+
+    date_template         varchar     null,
+    date_format           varchar     not null,
+    amount_mapping        varchar     not null,
+    ordering              varchar     not null,
+
+    -- Column indices, starting at 0.
+    account_iban          int         not null,
+    date                  int         not null,
+    follow_number         int         not null,
+    description           int         not null,
+    amount                int         not null,
+    external_account_name int         not null,
+
+    primary key (id, user_id),
+    CONSTRAINT unique_csv_mapping_profile_name UNIQUE (user_id, name)
+);
@@ -0,0 +1,23 @@
+-- Columns can now be mapped by their index or by their header name, and there are optional columns
+-- as well, so the mapped columns are stored as JSON instead of a column per field.
+ALTER TABLE csv_mapping_profiles
+    ADD COLUMN columns varchar null;
+
+UPDATE csv_mapping_profiles
+SET columns = json_build_object(
+        'accountIban', account_iban,
+        'date', date,
+        'followNumber', follow_number,
+        'description', description,
+        'amount', amount,
+        'externalAccountName', external_account_name
+    )::varchar;
+
+ALTER TABLE csv_mapping_profiles
+    ALTER COLUMN columns SET NOT NULL,
+    DROP COLUMN account_iban,
+    DROP COLUMN date,
+    DROP COLUMN follow_number,
+    DROP COLUMN description,
+    DROP COLUMN amount,
+    DROP COLUMN external_account_name;
@@ -0,0 +1,81 @@
+-- Amounts are stored in the minor unit of their currency, so cents for EUR and USD, but whole yen
+-- for JPY. Everything that existed before is in euro.
+ALTER TABLE users
+    ADD COLUMN base_currency varchar(3) not null default 'EUR';
+
+ALTER TABLE bank_accounts
+    ADD COLUMN currency varchar(3) not null default 'EUR';
+
+ALTER TABLE transactions
+    ADD COLUMN currency varchar(3) not null default 'EUR';
+
+-- Exchange rates in the same form as the reference rates of the ECB: the amount of the currency
+-- that is worth one euro on the given date.
+CREATE TABLE exchange_rates
+(
+    user_id  varchar(36)      not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    currency varchar(3)       not null,
+    date     date             not null,
+    rate     double precision not null,
+
+    primary key (user_id, currency, date)
+);
+
+-- The number of digits after the decimal separator of the currency. Keep this in sync with
+-- `utils::currency::minor_units`.
+CREATE FUNCTION currency_minor_units(p_currency varchar) RETURNS int
+    LANGUAGE sql
+    IMMUTABLE
+AS
+$$
+SELECT CASE
+           WHEN p_currency IN ('BIF', 'CLP', 'DJF', 'GNF', 'ISK', 'JPY', 'KMF', 'KRW', 'PYG', 'RWF',
+                               'UGX', 'VND', 'VUV', 'XAF', 'XOF', 'XPF') THEN 0
+           WHEN p_currency IN ('BHD', 'IQD', 'JOD', 'KWD', 'LYD', 'OMR', 'TND') THEN 3
+           ELSE 2
+           END;
+$$;
+
+-- The rate of the currency against the euro at the given date. The latest rate on or before the
+-- date is used, and when there is none, the first rate after it. Returns null when the user has no
+-- rates for the currency at all.
+CREATE FUNCTION exchange_rate(p_user_id varchar, p_currency varchar, p_date timestamptz)
+    RETURNS double precision
+    LANGUAGE sql
+    STABLE
+AS
+$$
+SELECT CASE
+           WHEN p_currency = 'EUR' THEN 1.0
+           ELSE (SELECT rate
+                 FROM exchange_rates
+                 WHERE user_id = p_user_id
+                   AND currency = p_currency
+                 ORDER BY date > p_date::date, abs(date - p_date::date)
+                 LIMIT 1)
+           END;
+$$;
+
+-- Converts an amount in the minor unit of the currency to the minor unit of the base currency of
+-- the user. Returns null when one of the exchange rates is missing.
+CREATE FUNCTION convert_to_base(p_user_id varchar, p_amount bigint, p_currency varchar,
+                                p_date timestamptz) RETURNS bigint
+    LANGUAGE sql
+    STABLE
+AS
+$$
+SELECT CASE
+           WHEN p_currency = users.base_currency THEN p_amount
+           ELSE round(
+                   p_amount / exchange_rate(p_user_id, p_currency, p_date)
+                       * exchange_rate(p_user_id, users.base_currency, p_date)
+                       * power(10, currency_minor_units(users.base_currency) -
+                                   currency_minor_units(p_currency))
+               )::bigint
+           END
+FROM users
+WHERE users.id = p_user_id;
+$$;
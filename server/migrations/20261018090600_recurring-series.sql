@@ -0,0 +1,33 @@
+-- Series of transactions with the same external account that recur at a regular interval, like
+-- rent, subscriptions or salary. They are detected from the transactions and recomputed
+-- periodically, only the status is set by the user.
+CREATE TABLE recurring_series
+(
+    id                    varchar(36)              not null,
+    user_id               varchar(36)              not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+
+    -- The id of the external account when the transactions have one, otherwise the lowercased name
+    -- of the external account. Used to recognize the series when it is detected again.
+    series_key            varchar                  not null,
+    external_account_id   varchar(36)              null,
+    external_account_name varchar                  not null,
+
+    recurrence            varchar                  not null,
+
+    -- The typical amount of a single transaction, in the minor unit of the currency.
+    amount                bigint                   not null,
+    currency              varchar(3)               not null,
+    occurrences           int                      not null,
+    last_date             timestamp with time zone not null,
+    next_date             timestamp with time zone not null,
+
+    -- Either 'detected', 'confirmed' or 'dismissed'.
+    status                varchar                  not null default 'detected',
+
+    primary key (id, user_id),
+
+    CONSTRAINT unique_series_key UNIQUE (user_id, series_key)
+);
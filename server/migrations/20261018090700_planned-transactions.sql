@@ -0,0 +1,62 @@
+-- Transactions the user expects to happen in the future, either once or at a regular interval.
+-- They are used to forecast the balance of the bank accounts, and are linked to the imported
+-- transactions that fulfil them.
+CREATE TABLE planned_transactions
+(
+    id                  varchar(36)              not null,
+    user_id             varchar(36)              not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    bank_account_id     varchar(36)              not null,
+    category_id         varchar(36)              null,
+    subcategory_id      varchar(36)              null,
+    external_account_id varchar(36)              null,
+    description         varchar                  not null,
+
+    -- The expected amount in the minor unit of the currency of the bank account.
+    amount              bigint                   not null,
+
+    -- The date of the next occurrence that has not been matched with a transaction yet.
+    date                timestamp with time zone not null,
+
+    -- Either 'week', 'month', 'quarter' or 'year', or null for a one-off transaction.
+    recurrence          varchar                  null,
+    ends_at             timestamp with time zone null,
+
+    -- Set when a one-off transaction was matched, or when the last occurrence has passed ends_at.
+    completed           boolean                  not null default false,
+
+    primary key (id, user_id),
+    foreign key (bank_account_id, user_id)
+        references bank_accounts (id, user_id)
+        on delete cascade,
+    foreign key (category_id, user_id)
+        references categories (id, user_id)
+        on delete set null (category_id),
+    foreign key (subcategory_id, category_id, user_id)
+        references subcategories (id, parent_category, user_id)
+        on delete set null (subcategory_id),
+    foreign key (external_account_id, user_id)
+        references external_accounts (id, user_id)
+        on delete set null (external_account_id)
+);
+
+-- The imported transactions that fulfilled an occurrence of a planned transaction.
+CREATE TABLE planned_transaction_links
+(
+    planned_transaction_id varchar(36)              not null,
+    user_id                varchar(36)              not null,
+    transaction_id         varchar(36)              not null,
+
+    -- The date the matched occurrence was planned on.
+    occurrence_date        timestamp with time zone not null,
+
+    primary key (planned_transaction_id, transaction_id),
+    foreign key (planned_transaction_id, user_id)
+        references planned_transactions (id, user_id)
+        on delete cascade,
+    foreign key (transaction_id, user_id)
+        references transactions (id, user_id)
+        on delete cascade
+);
@@ -0,0 +1,12 @@
+-- Transactions are searched on parts of their texts with ILIKE, which can use trigram indexes
+-- instead of scanning every transaction of the user.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX transactions_description_trgm_idx
+    ON transactions USING gin (description gin_trgm_ops);
+
+CREATE INDEX transactions_original_description_trgm_idx
+    ON transactions USING gin (original_description gin_trgm_ops);
+
+CREATE INDEX transactions_external_account_name_trgm_idx
+    ON transactions USING gin (external_account_name gin_trgm_ops);
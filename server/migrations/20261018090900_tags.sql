@@ -0,0 +1,35 @@
+-- Free-form labels like 'vacation-2026' or 'tax-deductible'. Unlike categories, a transaction can
+-- have any number of tags.
+CREATE TABLE tags
+(
+    id        varchar(36) not null,
+    user_id   varchar(36) not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    name      varchar     not null,
+    hex_color varchar     not null,
+
+    primary key (id, user_id),
+
+    CONSTRAINT unique_tag_name UNIQUE (user_id, name)
+);
+
+-- Both transactions and splits can be tagged.
+CREATE TABLE transaction_tags
+(
+    transaction_id varchar(36) not null,
+    tag_id         varchar(36) not null,
+    user_id        varchar(36) not null,
+
+    primary key (transaction_id, tag_id),
+    foreign key (transaction_id, user_id)
+        references transactions (id, user_id)
+        on delete cascade,
+    foreign key (tag_id, user_id)
+        references tags (id, user_id)
+        on delete cascade
+);
+
+CREATE INDEX transaction_tags_tag_id_idx
+    ON transaction_tags (tag_id, user_id);
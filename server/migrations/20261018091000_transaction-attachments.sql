@@ -0,0 +1,47 @@
+-- Receipts and other documents attached to a transaction or split. The file itself is stored as a
+-- blob, so every attachment owns exactly one blob.
+CREATE TABLE transaction_attachments
+(
+    id             varchar(36) not null,
+    user_id        varchar(36) not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    transaction_id varchar(36) not null,
+    blob_token     varchar     not null,
+    filename       varchar     not null,
+    created_at     timestamptz not null,
+
+    primary key (id, user_id),
+    foreign key (transaction_id, user_id)
+        references transactions (id, user_id)
+        on delete cascade,
+    foreign key (blob_token, user_id)
+        references blobs (token, user_id)
+        on delete cascade,
+
+    CONSTRAINT unique_attachment_blob UNIQUE (blob_token, user_id)
+);
+
+CREATE INDEX transaction_attachments_transaction_id_idx
+    ON transaction_attachments (transaction_id, user_id);
+
+-- Transactions are deleted in a lot of places, and also through cascades, so the blob of an
+-- attachment is removed together with the attachment here instead of in every one of them.
+CREATE FUNCTION delete_attachment_blob() RETURNS trigger
+    LANGUAGE plpgsql
+AS
+$$
+BEGIN
+    DELETE FROM blobs
+    WHERE token = OLD.blob_token AND user_id = OLD.user_id;
+
+    RETURN OLD;
+END;
+$$;
+
+CREATE TRIGGER delete_attachment_blob
+    AFTER DELETE
+    ON transaction_attachments
+    FOR EACH ROW
+EXECUTE FUNCTION delete_attachment_blob();
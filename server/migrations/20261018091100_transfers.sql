@@ -0,0 +1,8 @@
+-- Links the two legs of a transfer between bank accounts of the same user. Unlike a move, both
+-- legs are real transactions, so deleting one of them only unlinks the other.
+ALTER TABLE transactions
+ADD COLUMN related_transfer_transaction varchar(36) null,
+ADD FOREIGN KEY (user_id, related_transfer_transaction)
+    references transactions (user_id, id)
+        on delete set null (related_transfer_transaction)
+        initially deferred;
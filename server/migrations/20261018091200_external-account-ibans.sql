@@ -0,0 +1,24 @@
+-- The IBAN of the other party of a transaction, when the bank export contains it.
+ALTER TABLE transactions
+    ADD COLUMN external_account_iban varchar null;
+
+-- Links an IBAN to an external account. The IBAN of the other party stays the same, while the
+-- names of for example card payments vary, so imported transactions are matched on these first.
+CREATE TABLE external_account_ibans
+(
+    id                      varchar(36) not null,
+    user_id                 varchar(36) not null
+        references users (id)
+            on update cascade
+            on delete cascade,
+    iban                    varchar     not null,
+    parent_external_account varchar(36) not null,
+
+    primary key (id, user_id),
+    foreign key (parent_external_account, user_id)
+        references external_accounts (id, user_id)
+        on update cascade
+        on delete cascade,
+
+    CONSTRAINT unique_external_account_iban UNIQUE (user_id, iban)
+);
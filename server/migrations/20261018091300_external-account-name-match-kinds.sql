@@ -0,0 +1,20 @@
+-- How the name of an external account name entry is matched with the names of transactions. Either
+-- 'exact', 'prefix', 'contains', 'glob' or 'regex'.
+ALTER TABLE external_account_names
+    ADD COLUMN match_kind varchar not null default 'exact';
+
+-- A pattern matches many different names, so the name of a transaction no longer has to be the
+-- same as the name of the entry it references. The importer now references the entry that matched,
+-- so deleting the entry only forgets which entry it was.
+ALTER TABLE transactions
+    DROP CONSTRAINT transactions_external_account_name_id_external_account_nam_fkey;
+
+ALTER TABLE external_account_names
+    DROP CONSTRAINT external_account_names_name_user_id_key,
+    ADD UNIQUE (name, match_kind, user_id),
+    ADD UNIQUE (id, parent_external_account, user_id);
+
+ALTER TABLE transactions
+    ADD FOREIGN KEY (external_account_name_id, external_account_id, user_id)
+        references external_account_names (id, parent_external_account, user_id)
+        on delete set null (external_account_name_id);
@@ -186,6 +186,7 @@ impl Error {
 
     fn get_body(&self) -> String {
         let error_dto = match self {
+            Error::ImportError(error) => return error.get_body(),
             Error::Sqlx(error) => error.to_error_dto(),
             Error::Csv(error) => error.to_error_dto(),
             Error::HttpError(error) => error.to_error_dto(),
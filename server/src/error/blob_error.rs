@@ -6,6 +6,11 @@ use crate::error::error_dto_trait::ToErrorDto;
 pub enum BlobError {
     NoMimeType,
     NoBlobToConfirm,
+    NotFound,
+    MimeTypeNotAllowed,
+
+    /// The storage backend failed, for example because the object store could not be reached.
+    Storage(String),
 }
 
 impl ToErrorDto for BlobError {
@@ -13,6 +18,9 @@ impl ToErrorDto for BlobError {
         match self {
             BlobError::NoMimeType => Status::BadRequest,
             BlobError::NoBlobToConfirm => Status::Conflict,
+            BlobError::NotFound => Status::NotFound,
+            BlobError::MimeTypeNotAllowed => Status::UnsupportedMediaType,
+            BlobError::Storage(_) => Status::InternalServerError,
         }
     }
 
@@ -23,6 +31,11 @@ impl ToErrorDto for BlobError {
                 "No blob was found with the given token. It's either already confirmed or expired"
                     .to_string()
             }
+            BlobError::NotFound => "The blob could not be found".to_string(),
+            BlobError::MimeTypeNotAllowed => {
+                "The type of the file is not allowed here".to_string()
+            }
+            BlobError::Storage(message) => format!("The blob storage failed: {}", message),
         }
     }
 }
@@ -10,9 +10,23 @@ pub enum ImportError {
     /// number given for the column is bigger than the number of columns that exist in the CSV.
     MissingColumn(String),
 
+    /// Indicates that a value in a row of the CSV could not be read. The row is the line number in
+    /// the CSV, so the first row after the header is row 2.
+    InvalidValue {
+        row: u64,
+        column: String,
+        reason: String,
+    },
+
     /// Indicates that there are no rows to import. This is mainly used for the dry run route as the
     /// normal import routes will iterate over the records so manual checking is not required.
     NoRows,
+
+    /// Indicates that the format of a bank statement could not be recognized.
+    UnknownFormat,
+
+    /// Indicates that a bank statement could not be read, with a description of what was wrong.
+    InvalidStatement(String),
 }
 
 impl ImportError {
@@ -20,9 +34,29 @@ impl ImportError {
         ImportError::MissingColumn(mapping.into())
     }
 
+    pub fn invalid_value(
+        row: u64,
+        column: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> ImportError {
+        ImportError::InvalidValue {
+            row,
+            column: column.into(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn invalid_statement(reason: impl Into<String>) -> ImportError {
+        ImportError::InvalidStatement(reason.into())
+    }
+
     pub fn get_status_code(&self) -> u16 {
         let status = match self {
-            ImportError::MissingColumn(_) | ImportError::NoRows => Status::BadRequest,
+            ImportError::MissingColumn(_)
+            | ImportError::InvalidValue { .. }
+            | ImportError::NoRows
+            | ImportError::UnknownFormat
+            | ImportError::InvalidStatement(_) => Status::BadRequest,
         };
 
         status.code
@@ -46,7 +80,18 @@ impl Display for ImportError {
             ImportError::MissingColumn(col) => {
                 format!("No column could be found for mapping '{}'", col)
             }
+            ImportError::InvalidValue {
+                row,
+                column,
+                reason,
+            } => {
+                format!("Row {} has an invalid value for '{}': {}", row, column, reason)
+            }
             ImportError::NoRows => "The CSV did not contain any rows".to_string(),
+            ImportError::UnknownFormat => "The format of the statement is not supported".to_string(),
+            ImportError::InvalidStatement(reason) => {
+                format!("The statement could not be read: {}", reason)
+            }
         };
 
         write!(f, "{}", string)
@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+/// Where blobs like profile images and receipts are stored.
+pub enum BlobStorageOptions {
+    /// Stores the blobs on the local filesystem. Without a root, the data and cache directories of
+    /// the platform are used.
+    Filesystem { root: Option<PathBuf> },
+
+    /// Stores the blobs in a bucket of an S3-compatible object store, like AWS S3 or MinIO.
+    S3(S3Options),
+}
+
+pub struct S3Options {
+    /// The URL of the object store, like `https://s3.eu-west-1.amazonaws.com` or
+    /// `http://localhost:9000`. The bucket is added to the path of this URL.
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
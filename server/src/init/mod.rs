@@ -1,2 +1,3 @@
+pub mod blob_storage_options;
 pub(crate) mod scheduler;
 pub mod start_options;
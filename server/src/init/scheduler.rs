@@ -3,9 +3,11 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_rwlock::RwLock;
+use chrono::Utc;
 use rocket::tokio;
 
 use crate::services::blob_service::BlobService;
+use crate::services::recurring_service::RecurringService;
 use crate::shared::DbPool;
 
 pub fn start_scheduler(blob_service: Arc<RwLock<BlobService>>, pool: Arc<RwLock<DbPool>>) {
@@ -25,8 +27,15 @@ pub fn start_scheduler(blob_service: Arc<RwLock<BlobService>>, pool: Arc<RwLock<
             let pool = pool.read().await;
             let cleanup_result = blob_service.cleanup(&pool).await;
 
-            if let Err(error) = cleanup_result {
-                warn!("Failed to run blob cleanup: {:?}", error);
+            match cleanup_result {
+                Ok(report) => info!("Blob cleanup reclaimed {} bytes", report.bytes_reclaimed),
+                Err(error) => warn!("Failed to run blob cleanup: {:?}", error),
+            }
+
+            let recurring_result = RecurringService::recompute_all(&pool, Utc::now()).await;
+
+            if let Err(error) = recurring_result {
+                warn!("Failed to detect recurring series: {:?}", error);
             }
         }
     });
@@ -1,5 +1,7 @@
 use jumpdrive_auth::services::jwt_service::RsaPrivateKey;
 
+use crate::init::blob_storage_options::BlobStorageOptions;
+
 /// Options for starting the server.
 pub struct StartOptions {
     pub database_url: String,
@@ -9,4 +11,5 @@ pub struct StartOptions {
     pub jwt_access_expire_seconds: u32,
     pub jwt_refresh_expire_seconds: u32,
     pub max_blob_unconfirmed: u32,
+    pub blob_storage: BlobStorageOptions,
 }
@@ -18,13 +18,22 @@ use crate::routes::aggregates::create_aggregate_routes;
 use crate::routes::auth::create_auth_routes;
 use crate::routes::bank_accounts::create_bank_account_routes;
 use crate::routes::blobs::create_blob_routes;
+use crate::routes::budgets::create_budget_routes;
 use crate::routes::categories::create_category_routes;
 use crate::routes::corrections::create_correction_routes;
+use crate::routes::exchange_rates::create_exchange_rate_routes;
 use crate::routes::external_accounts::create_external_account_routes;
+use crate::routes::forecast::create_forecast_routes;
 use crate::routes::importing::create_importing_routes;
+use crate::routes::planned_transactions::create_planned_transaction_routes;
+use crate::routes::reconciliations::create_reconciliation_routes;
+use crate::routes::recurring::create_recurring_routes;
+use crate::routes::rules::create_rule_routes;
+use crate::routes::tags::create_tag_routes;
 use crate::routes::transactions::create_transaction_routes;
 use crate::routes::users::create_user_routes;
 use crate::services::blob_service::BlobService;
+use crate::services::blob_storage::create_blob_storage;
 use crate::services::rate_limiter::RateLimiter;
 use crate::shared::PROJECT_DIRS;
 
@@ -103,8 +112,11 @@ pub async fn run(options: StartOptions) -> Result<(), rocket::Error> {
 
     // Create blob service
     trace!("Creating BLOB service");
+    let blob_storage = create_blob_storage(options.blob_storage)
+        .expect("Failed to create blob storage");
     let blob_service = BlobService::new(
         options.max_blob_unconfirmed,
+        blob_storage,
     ).expect("Failed to create blob service");
 
     // Create rate limiter
@@ -137,6 +149,14 @@ pub async fn run(options: StartOptions) -> Result<(), rocket::Error> {
         .mount("/external-accounts", create_external_account_routes())
         .mount("/aggregates", create_aggregate_routes())
         .mount("/import", create_importing_routes())
+        .mount("/rules", create_rule_routes())
+        .mount("/budgets", create_budget_routes())
+        .mount("/reconciliations", create_reconciliation_routes())
+        .mount("/recurring", create_recurring_routes())
+        .mount("/exchange-rates", create_exchange_rate_routes())
+        .mount("/planned-transactions", create_planned_transaction_routes())
+        .mount("/forecast", create_forecast_routes())
+        .mount("/tags", create_tag_routes())
         .mount("/blob", create_blob_routes())
         .launch()
         .await
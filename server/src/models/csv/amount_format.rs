@@ -0,0 +1,234 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Describes how the amounts in a CSV are written, as this differs per bank and per locale. The
+/// default accepts amounts like '-1234.56' and '1234,56'.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AmountFormat {
+    /// The character between the whole amount and the decimals. When it is not set, both a '.'
+    /// and a ',' are accepted, but only one of them may occur in the amount.
+    pub decimal_separator: Option<char>,
+
+    /// The character used to group the thousands, like the '.' in '1.234,56'. Grouping is not
+    /// allowed when it is not set.
+    pub thousands_separator: Option<char>,
+
+    pub negative_style: NegativeStyle,
+}
+
+/// How negative amounts are written. A leading sign is always accepted.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum NegativeStyle {
+    /// Like '-12.30'.
+    #[default]
+    Leading,
+
+    /// Like '12.30-', which some banks use in their exports.
+    Trailing,
+
+    /// Like '(12.30)', which is common in accounting.
+    Parentheses,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidAmount {
+    NotANumber,
+    TooManyDecimals(u32),
+    TooLarge,
+}
+
+impl AmountFormat {
+    /// Parses the amount into the minor unit of its currency, which has the given number of
+    /// decimals. The amount is parsed without going through a float, so no rounding errors can
+    /// occur. Trailing zeros are allowed, but any other digit beyond the decimals is an error as it
+    /// would otherwise get lost.
+    pub fn parse(&self, value: &str, decimals: u32) -> Result<i64, InvalidAmount> {
+        let value = value.trim();
+
+        let (mut negative, value) = match self.negative_style {
+            NegativeStyle::Parentheses => match value
+                .strip_prefix('(')
+                .and_then(|value| value.strip_suffix(')'))
+            {
+                Some(value) => (true, value.trim()),
+                None => (false, value),
+            },
+            NegativeStyle::Trailing => match value.strip_suffix('-') {
+                Some(value) => (true, value.trim_end()),
+                None => (false, value.strip_suffix('+').unwrap_or(value).trim_end()),
+            },
+            NegativeStyle::Leading => (false, value),
+        };
+
+        let value = match value.strip_prefix('-') {
+            Some(value) if !negative => {
+                negative = true;
+                value
+            }
+            Some(_) => return Err(InvalidAmount::NotANumber),
+            None => value.strip_prefix('+').unwrap_or(value),
+        };
+
+        let (whole, fraction) = self.split_decimals(value)?;
+
+        let whole = match self.thousands_separator {
+            Some(separator) => remove_grouping(whole, separator)?,
+            None => whole.to_string(),
+        };
+
+        let all_digits = |part: &str| part.chars().all(|char| char.is_ascii_digit());
+        if !all_digits(&whole) || !all_digits(fraction) || (whole.is_empty() && fraction.is_empty())
+        {
+            return Err(InvalidAmount::NotANumber);
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > decimals as usize {
+            return Err(InvalidAmount::TooManyDecimals(decimals));
+        }
+
+        let whole: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| InvalidAmount::TooLarge)?
+        };
+
+        let fraction: i64 = if decimals == 0 {
+            0
+        } else {
+            format!("{:0<width$}", fraction, width = decimals as usize)
+                .parse()
+                .map_err(|_| InvalidAmount::TooLarge)?
+        };
+
+        let amount = 10_i64
+            .checked_pow(decimals)
+            .and_then(|factor| whole.checked_mul(factor))
+            .and_then(|amount| amount.checked_add(fraction))
+            .ok_or(InvalidAmount::TooLarge)?;
+
+        Ok(if negative { -amount } else { amount })
+    }
+
+    fn split_decimals<'a>(&self, value: &'a str) -> Result<(&'a str, &'a str), InvalidAmount> {
+        let split = match self.decimal_separator {
+            Some(separator) => value.split_once(separator),
+            None => {
+                let split = value.split_once(['.', ',']);
+
+                if split.is_some_and(|(_, fraction)| fraction.contains(['.', ','])) {
+                    return Err(InvalidAmount::NotANumber);
+                }
+
+                split
+            }
+        };
+
+        Ok(split.unwrap_or((value, "")))
+    }
+}
+
+/// Removes the thousands separators, which have to be placed every three digits.
+fn remove_grouping(whole: &str, separator: char) -> Result<String, InvalidAmount> {
+    let groups: Vec<&str> = whole.split(separator).collect();
+
+    let (first, rest) = groups.split_first().ok_or(InvalidAmount::NotANumber)?;
+
+    if rest.iter().any(|group| group.len() != 3) || (!rest.is_empty() && first.is_empty()) {
+        return Err(InvalidAmount::NotANumber);
+    }
+
+    Ok(groups.concat())
+}
+
+impl Display for InvalidAmount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            InvalidAmount::NotANumber => "Not a valid amount".to_string(),
+            InvalidAmount::TooManyDecimals(decimals) => {
+                format!("The amount has more than {} decimals", decimals)
+            }
+            InvalidAmount::TooLarge => "The amount is too large".to_string(),
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::csv::amount_format::{AmountFormat, InvalidAmount, NegativeStyle};
+
+    #[test]
+    fn amounts_are_parsed_exactly() {
+        let format = AmountFormat::default();
+
+        assert_eq!(format.parse("0.29", 2), Ok(29));
+        assert_eq!(format.parse("1,15", 2), Ok(115));
+        assert_eq!(format.parse("-12.3", 2), Ok(-1230));
+        assert_eq!(format.parse("+4", 2), Ok(400));
+        assert_eq!(format.parse(".5", 2), Ok(50));
+        assert_eq!(format.parse("1500", 0), Ok(1500));
+        assert_eq!(format.parse("1.2345", 4), Ok(12345));
+    }
+
+    #[test]
+    fn thousands_separators_are_removed() {
+        let format = AmountFormat {
+            decimal_separator: Some(','),
+            thousands_separator: Some('.'),
+            ..Default::default()
+        };
+
+        assert_eq!(format.parse("1.234,56", 2), Ok(123456));
+        assert_eq!(format.parse("-1.234.567", 2), Ok(-123456700));
+        assert_eq!(format.parse("12,5", 2), Ok(1250));
+        assert_eq!(format.parse("1.23,45", 2), Err(InvalidAmount::NotANumber));
+        assert_eq!(format.parse(".123,45", 2), Err(InvalidAmount::NotANumber));
+
+        let format = AmountFormat {
+            decimal_separator: Some('.'),
+            thousands_separator: Some(' '),
+            ..Default::default()
+        };
+
+        assert_eq!(format.parse("12 345.60", 2), Ok(1234560));
+    }
+
+    #[test]
+    fn negative_styles_are_supported() {
+        let trailing = AmountFormat {
+            negative_style: NegativeStyle::Trailing,
+            ..Default::default()
+        };
+
+        assert_eq!(trailing.parse("12.30-", 2), Ok(-1230));
+        assert_eq!(trailing.parse("12.30+", 2), Ok(1230));
+        assert_eq!(trailing.parse("-12.30", 2), Ok(-1230));
+        assert_eq!(trailing.parse("-12.30-", 2), Err(InvalidAmount::NotANumber));
+
+        let parentheses = AmountFormat {
+            negative_style: NegativeStyle::Parentheses,
+            ..Default::default()
+        };
+
+        assert_eq!(parentheses.parse("(12.30)", 2), Ok(-1230));
+        assert_eq!(parentheses.parse("12.30", 2), Ok(1230));
+        assert_eq!(parentheses.parse("(12.30", 2), Err(InvalidAmount::NotANumber));
+    }
+
+    #[test]
+    fn invalid_amounts_are_rejected() {
+        let format = AmountFormat::default();
+
+        assert_eq!(format.parse("", 2), Err(InvalidAmount::NotANumber));
+        assert_eq!(format.parse("12a", 2), Err(InvalidAmount::NotANumber));
+        assert_eq!(format.parse("1.000,00", 2), Err(InvalidAmount::NotANumber));
+        assert_eq!(format.parse("0.291", 2), Err(InvalidAmount::TooManyDecimals(2)));
+        assert_eq!(format.parse("12.5", 0), Err(InvalidAmount::TooManyDecimals(0)));
+        assert_eq!(format.parse("99999999999999999999", 2), Err(InvalidAmount::TooLarge));
+    }
+}
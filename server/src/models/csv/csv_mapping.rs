@@ -1,34 +1,143 @@
-use serde::Deserialize;
+use csv::StringRecord;
+use serde::{Deserialize, Serialize};
 
-/// Used to map a column number to a required field of a transaction. The columns count starts
-/// as usual at 0.
-#[derive(Debug, Deserialize)]
+use crate::models::csv::amount_format::AmountFormat;
+
+/// Used to map a column to a field of a transaction. The required fields must always be mapped,
+/// the optional ones are only read when they are set.
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CsvMapping {
     pub date_mapping: DateMapping,
     pub amount_mapping: AmountMapping,
+
+    #[serde(default)]
+    pub amount_format: AmountFormat,
     pub ordering: CsvImportOrdering,
 
-    pub account_iban: u32,
-    pub date: u32,
-    pub follow_number: u32,
-    pub description: u32,
-    pub amount: u32,
-    pub external_account_name: u32,
+    pub account_iban: CsvColumn,
+    pub date: CsvColumn,
+    pub follow_number: CsvColumn,
+    pub description: CsvColumn,
+    pub amount: CsvColumn,
+    pub external_account_name: CsvColumn,
+
+    /// The column with the currency code of the amount. When it is not mapped, or empty for a
+    /// row, the [default_currency] is used, and otherwise the currency of the bank account.
+    #[serde(default)]
+    pub currency: Option<CsvColumn>,
+
+    #[serde(default)]
+    pub default_currency: Option<String>,
+
+    /// The IBAN of the other party of the transaction.
+    #[serde(default)]
+    pub external_account_iban: Option<CsvColumn>,
+
+    /// The date on which the transaction was settled. Parsed with the same [DateMapping] as the
+    /// booking date.
+    #[serde(default)]
+    pub value_date: Option<CsvColumn>,
+
+    /// Used for banks that only export positive amounts and put the direction of the transaction
+    /// in a separate column.
+    #[serde(default)]
+    pub debit_credit: Option<DebitCreditMapping>,
+
+    /// Used for banks that put money going out and money coming in in separate columns. When it
+    /// is set, the [amount] column holds the money going out and this column the money coming in.
+    /// Only one of them needs to have a value, and the sign of the values is ignored.
+    #[serde(default)]
+    pub credit_amount: Option<CsvColumn>,
+}
+
+/// Refers to a column either by its number, starting as usual at 0, or by its name in the header
+/// row of the CSV. In JSON a number is read as an index and a string as a name.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+pub enum CsvColumn {
+    Index(u32),
+    Name(String),
 }
 
-#[derive(Debug, Deserialize)]
+/// Used to configure the column that indicates whether money went out of or into the account.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DebitCreditMapping {
+    pub column: CsvColumn,
+
+    /// The value of the column for money going out, for example 'D' or 'Af'. Any other value is
+    /// treated as money coming in. Compared without regard to casing.
+    pub debit: String,
+}
+
+impl CsvColumn {
+    /// Returns the index of the column in the CSV with the given header. Names are compared the
+    /// same way as headers are normalized, so casing, surrounding whitespace and quotes don't
+    /// matter.
+    pub fn resolve(&self, header: &StringRecord) -> Option<usize> {
+        match self {
+            CsvColumn::Index(index) => Some(*index as usize),
+            CsvColumn::Name(name) => {
+                let name = normalize_column_name(name);
+
+                header
+                    .iter()
+                    .position(|column| normalize_column_name(column) == name)
+            }
+        }
+    }
+}
+
+impl CsvMapping {
+    /// Returns the names of the mapped columns that don't exist in the given header.
+    pub fn unknown_columns(&self, header: &StringRecord) -> Vec<String> {
+        let optional_columns = [
+            self.currency.as_ref(),
+            self.external_account_iban.as_ref(),
+            self.value_date.as_ref(),
+            self.debit_credit.as_ref().map(|debit_credit| &debit_credit.column),
+            self.credit_amount.as_ref(),
+        ];
+
+        [
+            &self.account_iban,
+            &self.date,
+            &self.follow_number,
+            &self.description,
+            &self.amount,
+            &self.external_account_name,
+        ]
+        .into_iter()
+        .chain(optional_columns.into_iter().flatten())
+        .filter_map(|column| match column {
+            CsvColumn::Name(name) if column.resolve(header).is_none() => Some(name.to_string()),
+            _ => None,
+        })
+        .collect()
+    }
+}
+
+/// Lowercases the name of a column and strips the whitespace and quotes around it.
+pub fn normalize_column_name(name: &str) -> String {
+    name.trim().trim_matches('"').to_lowercase()
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum AmountMapping {
-    /// Used when the value of the CSV looks like '129'
+    /// Used when the value of the CSV is already in the minor unit of the currency, so it looks
+    /// like '129'.
     Cents,
 
-    /// Used when the vale of the CSV looks like '1,29' or '1.29'
-    Euro,
+    /// Used when the value of the CSV looks like '1,29' or '1.29'. The number of decimals depends
+    /// on the currency, so '129' in yen stays 129, while it is 12900 in euro cents.
+    #[serde(alias = "euro")]
+    Decimal,
 }
 
 /// Used to configure the datetime mapping for the column.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DateMapping {
     /// Template for formatting the datetime. Used for when the actual value of the date column
@@ -46,7 +155,7 @@ pub struct DateMapping {
 
 /// Used for populating the [Transaction::order_indicator] field which helps with keeping the
 /// correct order of the transactions.
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum CsvImportOrdering {
     /// Indicates that the newest transaction is the first row in the CSV.
@@ -55,3 +164,65 @@ pub enum CsvImportOrdering {
     /// Indicates that the newest transaction is the last row in the CSV.
     NewestLast,
 }
+
+impl From<&str> for AmountMapping {
+    fn from(value: &str) -> Self {
+        match value {
+            "cents" => AmountMapping::Cents,
+            _ => AmountMapping::Decimal,
+        }
+    }
+}
+
+impl From<AmountMapping> for &str {
+    fn from(val: AmountMapping) -> Self {
+        match val {
+            AmountMapping::Cents => "cents",
+            AmountMapping::Decimal => "decimal",
+        }
+    }
+}
+
+impl From<&str> for CsvImportOrdering {
+    fn from(value: &str) -> Self {
+        match value {
+            "newestFirst" => CsvImportOrdering::NewestFirst,
+            _ => CsvImportOrdering::NewestLast,
+        }
+    }
+}
+
+impl From<CsvImportOrdering> for &str {
+    fn from(val: CsvImportOrdering) -> Self {
+        match val {
+            CsvImportOrdering::NewestFirst => "newestFirst",
+            CsvImportOrdering::NewestLast => "newestLast",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use csv::StringRecord;
+
+    use crate::models::csv::csv_mapping::CsvColumn;
+
+    #[test]
+    fn columns_are_read_as_index_or_name() {
+        let columns: Vec<CsvColumn> = serde_json::from_str(r#"[3, "Datum"]"#).unwrap();
+
+        assert_eq!(
+            columns,
+            vec![CsvColumn::Index(3), CsvColumn::Name("Datum".to_string())]
+        );
+    }
+
+    #[test]
+    fn names_are_resolved_through_header() {
+        let header = StringRecord::from(vec!["IBAN", " \"Datum\" ", "Bedrag"]);
+
+        assert_eq!(CsvColumn::Name("datum".to_string()).resolve(&header), Some(1));
+        assert_eq!(CsvColumn::Name("Amount".to_string()).resolve(&header), None);
+        assert_eq!(CsvColumn::Index(7).resolve(&header), Some(7));
+    }
+}
@@ -1 +1,2 @@
+pub mod amount_format;
 pub mod csv_mapping;
@@ -1 +1,4 @@
+pub mod series_bucket_dto;
+pub mod series_query_dto;
+pub mod tag_total_dto;
 pub mod user_total_dto;
@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+/// The totals for a single bucket of the series. Both [income] and [expense] are positive amounts
+/// in cents and [net] is the income minus the expense.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesBucketDto {
+    pub bucket: String,
+
+    /// The id of the category, subcategory, bank account or external account the bucket belongs
+    /// to when the series is grouped. This is [None] for transactions without the grouped entity
+    /// or when the series is not grouped at all.
+    pub group_id: Option<String>,
+    pub income: i64,
+    pub expense: i64,
+    pub net: i64,
+
+    /// The currencies that could not be converted, because there is no exchange rate for them.
+    pub missing_rates: Vec<String>,
+}
@@ -0,0 +1,49 @@
+/// Query parameters for the aggregate series. Dates are expected to be formatted as RFC 3339 and
+/// the `to` date is exclusive.
+#[derive(Debug, FromForm)]
+pub struct SeriesQueryDto {
+    pub from: Option<String>,
+    pub to: Option<String>,
+
+    #[field(default = SeriesInterval::Month)]
+    pub interval: SeriesInterval,
+
+    #[field(name = "groupBy")]
+    pub group_by: Option<SeriesGroupBy>,
+}
+
+/// The size of a single bucket in the series. Buckets follow the calendar in UTC, so weeks start on
+/// Monday.
+#[derive(Debug, FromFormField, PartialEq, Copy, Clone)]
+pub enum SeriesInterval {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl From<SeriesInterval> for &str {
+    fn from(val: SeriesInterval) -> Self {
+        match val {
+            SeriesInterval::Day => "day",
+            SeriesInterval::Week => "week",
+            SeriesInterval::Month => "month",
+            SeriesInterval::Year => "year",
+        }
+    }
+}
+
+#[derive(Debug, FromFormField, PartialEq, Copy, Clone)]
+pub enum SeriesGroupBy {
+    #[field(value = "category")]
+    Category,
+
+    #[field(value = "subcategory")]
+    Subcategory,
+
+    #[field(value = "bankAccount")]
+    BankAccount,
+
+    #[field(value = "externalAccount")]
+    ExternalAccount,
+}
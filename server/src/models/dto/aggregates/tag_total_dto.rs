@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+/// The income and expenses of the transactions with a tag, in the minor unit of the base currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagTotalDto {
+    pub tag_id: String,
+    pub name: String,
+    pub income: i64,
+    pub expense: i64,
+    pub net: i64,
+
+    /// The currencies that could not be converted, because there is no exchange rate for them.
+    pub missing_rates: Vec<String>,
+}
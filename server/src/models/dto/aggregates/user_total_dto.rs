@@ -3,5 +3,12 @@ use serde::Serialize;
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserTotalDto {
+    /// The total in the minor unit of the base currency.
     pub total: i64,
+
+    /// The base currency of the user.
+    pub currency: String,
+
+    /// The currencies that could not be converted, because there is no exchange rate for them.
+    pub missing_rates: Vec<String>,
 }
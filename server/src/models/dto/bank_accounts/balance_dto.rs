@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+/// The balance of a bank account at a point in time, in cents.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceDto {
+    pub date: String,
+    pub balance: i64,
+}
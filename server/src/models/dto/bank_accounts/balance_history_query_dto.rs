@@ -0,0 +1,12 @@
+use crate::models::dto::aggregates::series_query_dto::SeriesInterval;
+
+/// Query parameters for the balance history of a bank account. Dates are expected to be formatted
+/// as RFC 3339.
+#[derive(Debug, FromForm)]
+pub struct BalanceHistoryQueryDto {
+    pub from: String,
+    pub to: String,
+
+    #[field(default = SeriesInterval::Day)]
+    pub interval: SeriesInterval,
+}
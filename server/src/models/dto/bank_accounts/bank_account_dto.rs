@@ -8,5 +8,6 @@ pub struct BankAccountDto {
     pub name: String,
     pub description: String,
     pub hex_color: String,
+    pub currency: String,
     pub amount: i64,
 }
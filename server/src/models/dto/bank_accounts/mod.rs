@@ -1,3 +1,5 @@
+pub mod balance_dto;
+pub mod balance_history_query_dto;
 pub mod bank_account_dto;
 pub mod slim_bank_account_dto;
 pub mod update_bank_account_dto;
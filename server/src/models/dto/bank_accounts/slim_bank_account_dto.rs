@@ -8,4 +8,5 @@ pub struct SlimBankAccountDto {
     pub name: String,
     pub description: String,
     pub hex_color: String,
+    pub currency: String,
 }
@@ -6,4 +6,8 @@ pub struct UpdateBankAccountDto {
     pub name: String,
     pub description: String,
     pub hex_color: String,
+
+    /// Only changes the currency of the account itself, the amounts of existing transactions are
+    /// not converted.
+    pub currency: Option<String>,
 }
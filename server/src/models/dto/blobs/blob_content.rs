@@ -0,0 +1,20 @@
+use rocket::http::ContentType;
+use rocket::response::Responder;
+use rocket::{response, Request, Response};
+
+use crate::services::blob_storage::BlobReader;
+
+/// Streams the contents of a blob with the mime type it was uploaded with.
+pub struct BlobContent {
+    pub reader: BlobReader,
+    pub content_type: ContentType,
+}
+
+impl<'r> Responder<'r, 'static> for BlobContent {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(self.content_type)
+            .streamed_body(self.reader)
+            .ok()
+    }
+}
@@ -1 +1,2 @@
+pub mod blob_content;
 pub mod blob_token_dto;
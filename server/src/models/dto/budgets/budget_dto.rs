@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::models::entities::budget::budget_period::BudgetPeriod;
+use crate::models::entities::budget::Budget;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetDto {
+    pub id: String,
+    pub category_id: String,
+    pub subcategory_id: Option<String>,
+    pub amount: i64,
+    pub period: BudgetPeriod,
+    pub rollover: bool,
+    pub starts_at: String,
+}
+
+impl From<Budget> for BudgetDto {
+    fn from(budget: Budget) -> Self {
+        Self {
+            id: budget.id,
+            category_id: budget.category_id,
+            subcategory_id: budget.subcategory_id,
+            amount: budget.amount,
+            period: budget.period,
+            rollover: budget.rollover,
+            starts_at: budget.starts_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+/// The state of a budget for a single period. All amounts are in the minor unit of the base currency of the user and [actual] is the amount
+/// that was spent, so it is positive for money going out.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetOverviewDto {
+    pub budget_id: String,
+    pub category_id: String,
+    pub subcategory_id: Option<String>,
+    pub period_start: String,
+    pub period_end: String,
+    pub budgeted: i64,
+
+    /// The money that was left over from previous periods. Always zero when rollover is disabled.
+    pub rolled_over: i64,
+    pub actual: i64,
+    pub remaining: i64,
+
+    /// The currencies without any exchange rate, whose transactions are left out of the spending.
+    pub missing_rates: Vec<String>,
+}
@@ -0,0 +1,3 @@
+pub mod budget_dto;
+pub mod budget_overview_dto;
+pub mod new_budget_dto;
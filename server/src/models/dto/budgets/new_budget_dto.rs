@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use crate::models::entities::budget::budget_period::BudgetPeriod;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBudgetDto<'a> {
+    pub category_id: &'a str,
+    pub subcategory_id: Option<&'a str>,
+    pub amount: u32,
+    pub period: BudgetPeriod,
+    pub rollover: bool,
+
+    /// RFC 3339 datetime from which the budget starts counting. Defaults to the current period.
+    pub starts_at: Option<&'a str>,
+}
@@ -9,6 +9,10 @@ pub struct CategoryDto {
     pub name: String,
     pub description: String,
     pub hex_color: String,
+    /// In the minor unit of the base currency of the user.
     pub amount: i64,
+
+    /// The currencies without any exchange rate, whose transactions are left out of [amount].
+    pub missing_rates: Vec<String>,
     pub subcategories: Vec<SubcategoryDto>,
 }
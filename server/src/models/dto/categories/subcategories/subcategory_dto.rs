@@ -7,5 +7,9 @@ pub struct SubcategoryDto {
     pub name: String,
     pub description: String,
     pub hex_color: String,
+    /// In the minor unit of the base currency of the user.
     pub amount: i64,
+
+    /// The currencies without any exchange rate, whose transactions are left out of [amount].
+    pub missing_rates: Vec<String>,
 }
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeRateDto {
+    pub currency: String,
+    pub date: String,
+
+    /// The amount of the currency that is worth one euro.
+    pub rate: f64,
+}
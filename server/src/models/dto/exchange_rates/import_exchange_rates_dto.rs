@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportExchangeRatesDto {
+    /// The reference rates in the CSV format of the ECB.
+    pub csv: String,
+}
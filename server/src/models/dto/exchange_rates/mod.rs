@@ -0,0 +1,2 @@
+pub mod exchange_rate_dto;
+pub mod import_exchange_rates_dto;
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+/// Creates an external account from the suggestion with the given key. The name defaults to the
+/// suggested name of the cluster.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptExternalAccountSuggestionDto<'a> {
+    pub key: &'a str,
+    pub name: Option<&'a str>,
+
+    #[serde(default)]
+    pub description: &'a str,
+    pub hex_color: Option<&'a str>,
+    pub default_category_id: Option<&'a str>,
+    pub default_subcategory_id: Option<&'a str>,
+}
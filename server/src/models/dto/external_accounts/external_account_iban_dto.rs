@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAccountIbanDto {
+    pub id: String,
+    pub iban: String,
+    pub parent_external_account: String,
+}
@@ -1,9 +1,12 @@
 use serde::Serialize;
 
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExternalAccountNameDto {
     pub id: String,
     pub name: String,
     pub parent_external_account: String,
+    pub match_kind: NameMatchKind,
 }
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalAccountSuggestionDto {
+    pub key: String,
+    pub suggested_name: String,
+    pub names: Vec<String>,
+    pub transaction_count: i64,
+}
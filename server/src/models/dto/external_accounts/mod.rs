@@ -1,4 +1,8 @@
+pub mod accept_external_account_suggestion_dto;
 pub mod external_account_dto;
+pub mod external_account_iban_dto;
 pub mod external_account_name_dto;
+pub mod external_account_suggestion_dto;
 pub mod new_external_account_dto;
+pub mod new_external_account_iban_dto;
 pub mod new_external_account_name_dto;
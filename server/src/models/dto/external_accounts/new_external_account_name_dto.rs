@@ -1,7 +1,13 @@
 use serde::Deserialize;
 
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewExternalAccountNameDto<'a> {
     pub name: &'a str,
+
+    /// Defaults to an exact name.
+    #[serde(default)]
+    pub match_kind: NameMatchKind,
 }
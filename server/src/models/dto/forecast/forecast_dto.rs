@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+use crate::models::dto::bank_accounts::balance_dto::BalanceDto;
+use crate::models::dto::forecast::forecast_item_dto::ForecastItemDto;
+
+/// The projected balance of a bank account, in the minor unit of its currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastDto {
+    pub bank_account_id: String,
+    pub currency: String,
+
+    /// The current balance the projection starts from.
+    pub balance: i64,
+    pub end_balance: i64,
+    pub lowest_balance: i64,
+    pub items: Vec<ForecastItemDto>,
+
+    /// The projected balance at the end of every month.
+    pub balances: Vec<BalanceDto>,
+}
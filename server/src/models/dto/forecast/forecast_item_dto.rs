@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// A single planned occurrence in a forecast.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastItemDto {
+    pub planned_transaction_id: String,
+    pub description: String,
+    pub date: String,
+    pub amount: i64,
+
+    /// The projected balance right after this occurrence.
+    pub balance: i64,
+}
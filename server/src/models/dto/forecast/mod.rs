@@ -0,0 +1,2 @@
+pub mod forecast_dto;
+pub mod forecast_item_dto;
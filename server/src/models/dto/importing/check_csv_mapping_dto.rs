@@ -9,4 +9,7 @@ pub struct CheckCsvMappingDto {
     pub description: String,
     pub amount: i64,
     pub external_account_name: String,
+    pub currency: Option<String>,
+    pub external_account_iban: Option<String>,
+    pub value_date: Option<String>,
 }
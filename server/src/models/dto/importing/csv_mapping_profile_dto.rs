@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::models::csv::csv_mapping::CsvMapping;
+use crate::models::entities::csv_mapping_profile::CsvMappingProfile;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvMappingProfileDto {
+    pub id: String,
+    pub name: String,
+    pub header: Option<String>,
+    pub mapping: CsvMapping,
+}
+
+impl From<CsvMappingProfile> for CsvMappingProfileDto {
+    fn from(value: CsvMappingProfile) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            header: value.header,
+            mapping: value.mapping,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use crate::models::csv::csv_mapping::CsvMapping;
+use crate::models::dto::importing::csv_mapping_profile_dto::CsvMappingProfileDto;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvMappingSuggestionDto {
+    /// The saved profile for CSV files with the same header, if there is one.
+    pub profile: Option<CsvMappingProfileDto>,
+
+    /// The mapping of the profile, or a mapping guessed from the column names when there is no
+    /// profile. This is empty when no mapping could be guessed.
+    pub mapping: Option<CsvMapping>,
+
+    /// The column names used in the mapping that don't exist in the header of the CSV.
+    pub unknown_columns: Vec<String>,
+}
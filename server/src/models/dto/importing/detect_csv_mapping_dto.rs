@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectCsvMappingDto {
+    /// The header row of the CSV, exactly as it appears in the file.
+    pub header: String,
+}
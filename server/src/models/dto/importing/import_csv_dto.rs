@@ -2,9 +2,13 @@ use serde::Deserialize;
 
 use crate::models::csv::csv_mapping::CsvMapping;
 
+/// Either the mappings or the id of a saved mapping profile has to be given. When both are given,
+/// the mappings take precedence.
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ImportCsvDto {
-    pub mappings: CsvMapping,
+    pub mappings: Option<CsvMapping>,
+    pub profile_id: Option<String>,
     pub filename: String,
     pub csv: String,
 }
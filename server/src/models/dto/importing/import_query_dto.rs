@@ -0,0 +1,8 @@
+/// Query parameters for importing a CSV or a statement.
+#[derive(Debug, FromForm)]
+pub struct ImportQueryDto {
+    /// When set, every row is run through the import without saving anything, so the user can see
+    /// what the import would do.
+    #[field(name = "dryRun")]
+    pub dry_run: bool,
+}
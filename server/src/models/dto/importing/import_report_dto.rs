@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+use crate::models::dto::importing::import_row_dto::{ImportRowDto, ImportRowStatus};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReportDto {
+    /// Whether this was a dry run, in which case nothing was saved.
+    pub dry_run: bool,
+    pub inserted: usize,
+    pub duplicates: usize,
+    pub errors: usize,
+    pub new_bank_accounts: usize,
+    pub rows: Vec<ImportRowDto>,
+}
+
+impl ImportReportDto {
+    pub fn new(dry_run: bool, mut rows: Vec<ImportRowDto>) -> Self {
+        rows.sort_by_key(|row| row.row);
+
+        let count = |status: ImportRowStatus| rows.iter().filter(|row| row.status == status).count();
+
+        Self {
+            dry_run,
+            inserted: count(ImportRowStatus::Insert),
+            duplicates: count(ImportRowStatus::Duplicate),
+            errors: count(ImportRowStatus::Error),
+            new_bank_accounts: rows.iter().filter(|row| row.new_bank_account).count(),
+            rows,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+/// What happened, or would happen during a dry run, to a single row of an import.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowDto {
+    /// The line number in the CSV, or the position of the entry in a statement.
+    pub row: u64,
+    pub status: ImportRowStatus,
+    pub follow_number: Option<String>,
+
+    /// Whether the bank account of the row did not exist yet and is created by the import.
+    pub new_bank_account: bool,
+
+    /// The planned transaction the new transaction was matched with.
+    pub planned_transaction_id: Option<String>,
+
+    /// The transaction on another bank account of the user that the new transaction was linked
+    /// with as the other leg of a transfer.
+    pub transfer_transaction_id: Option<String>,
+
+    /// The field that could not be read, when the row has an error.
+    pub column: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Copy, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportRowStatus {
+    /// The row is imported as a new transaction.
+    Insert,
+
+    /// A transaction with the same follow number already exists, so the row is added to the
+    /// skipped transactions of the import.
+    Duplicate,
+
+    /// The row cannot be imported.
+    Error,
+}
@@ -0,0 +1,47 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ImportStatementDto {
+    pub filename: String,
+    pub content: String,
+
+    /// When no format is given, it is detected based on the content of the statement.
+    pub format: Option<StatementFormat>,
+}
+
+/// The bank statement formats that can be imported next to CSV. Unlike CSV, these formats have a
+/// fixed structure, so no mapping is required.
+#[derive(Debug, Deserialize, PartialEq, Copy, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum StatementFormat {
+    /// Open Financial Exchange, both the SGML (1.x) and XML (2.x) variants. QFX files are OFX
+    /// files with some additional Quicken specific tags, so they are read the same way.
+    Ofx,
+
+    /// The ISO 20022 XML bank to customer statement used by most European banks.
+    Camt053,
+
+    /// The SWIFT MT940 customer statement message.
+    Mt940,
+}
+
+impl StatementFormat {
+    /// Tries to recognize the format of the statement based on its content.
+    pub fn detect(content: &str) -> Option<StatementFormat> {
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+
+        if content.starts_with("OFXHEADER") || content.contains("<OFX>") {
+            return Some(StatementFormat::Ofx);
+        }
+
+        if content.contains("camt.053") || content.contains("BkToCstmrStmt") {
+            return Some(StatementFormat::Camt053);
+        }
+
+        if content.contains(":20:") && content.contains(":61:") {
+            return Some(StatementFormat::Mt940);
+        }
+
+        None
+    }
+}
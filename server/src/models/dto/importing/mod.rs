@@ -1,2 +1,10 @@
 pub mod check_csv_mapping_dto;
+pub mod csv_mapping_profile_dto;
+pub mod csv_mapping_suggestion_dto;
+pub mod detect_csv_mapping_dto;
 pub mod import_csv_dto;
+pub mod import_query_dto;
+pub mod import_report_dto;
+pub mod import_row_dto;
+pub mod import_statement_dto;
+pub mod new_csv_mapping_profile_dto;
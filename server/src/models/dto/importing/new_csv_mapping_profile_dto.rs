@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+use crate::models::csv::csv_mapping::CsvMapping;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewCsvMappingProfileDto {
+    pub name: String,
+
+    /// The header row of the CSV files this profile is meant for, exactly as it appears in the
+    /// file. It is used to suggest this profile when a file with the same header is imported.
+    pub header: Option<String>,
+
+    pub mapping: CsvMapping,
+}
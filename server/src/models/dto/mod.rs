@@ -3,11 +3,19 @@ pub mod aggregates;
 pub mod auth;
 pub mod bank_accounts;
 pub mod blobs;
+pub mod budgets;
 pub mod categories;
 pub mod error_dto;
+pub mod exchange_rates;
 pub mod external_accounts;
+pub mod forecast;
 pub mod import;
 pub mod importing;
 pub mod pagination;
+pub mod planned_transactions;
+pub mod reconciliations;
+pub mod recurring;
+pub mod rules;
+pub mod tags;
 pub mod transactions;
 pub mod users;
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::http_error::HttpError;
+use crate::prelude::*;
+
+/// Points at an item in a list, so the next page can continue right after it even when items
+/// were added in the meantime. Clients only get to see it as an opaque string.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Cursor {
+    /// RFC 3339 datetime of the item.
+    pub date: String,
+    pub order_indicator: i32,
+    pub id: String,
+}
+
+/// Items of a list that can be paginated with a cursor.
+pub trait CursorItem {
+    fn cursor(&self) -> Cursor;
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_string(self).expect("A cursor can always be serialized");
+
+        base64_url::encode(&json)
+    }
+
+    pub fn decode(value: &str) -> Result<Cursor> {
+        base64_url::decode(value)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| HttpError::new(400).message("The cursor is invalid").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::dto::pagination::cursor::Cursor;
+
+    #[test]
+    fn cursor_can_be_decoded() {
+        let cursor = Cursor {
+            date: "2023-02-11T11:00:00Z".to_string(),
+            order_indicator: 12,
+            id: "transaction-1".to_string(),
+        };
+
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(Cursor::decode("not a cursor").is_err());
+    }
+}
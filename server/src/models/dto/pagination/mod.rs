@@ -1,2 +1,3 @@
+pub mod cursor;
 pub mod pagination_query_dto;
 pub mod pagination_response_dto;
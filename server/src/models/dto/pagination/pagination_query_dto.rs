@@ -5,6 +5,10 @@ pub struct PaginationQueryDto {
 
     #[field(default = 50, validate = range(0..))]
     pub limit: i64,
+
+    /// The `nextCursor` of the previous page. When this is set, the page continues right after
+    /// the item the cursor points to and the page number is ignored.
+    pub cursor: Option<String>,
 }
 
 impl PaginationQueryDto {
@@ -17,6 +21,10 @@ impl PaginationQueryDto {
     }
 
     pub fn get_offset(&self) -> i64 {
+        if self.cursor.is_some() {
+            return 0;
+        }
+
         (self.get_page_input() - 1) * self.get_limit_input()
     }
 
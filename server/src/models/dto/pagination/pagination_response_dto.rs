@@ -1,5 +1,6 @@
 use serde::Serialize;
 
+use crate::models::dto::pagination::cursor::CursorItem;
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
 
 #[derive(Debug, Serialize)]
@@ -9,24 +10,42 @@ pub struct PaginationResponseDto<T> {
     limit: i64,
     items: Vec<T>,
     done: bool,
+
+    /// Pass this as the cursor to get the next page. Is [None] on the last page.
+    next_cursor: Option<String>,
 }
 
-impl<T> PaginationResponseDto<T> {
+impl<T: CursorItem> PaginationResponseDto<T> {
     pub fn from_query(query: PaginationQueryDto, items: Vec<T>) -> Self {
         let items_length = items.len();
 
-        let items = items
+        let items: Vec<T> = items
             .into_iter()
             .take(query.get_limit_input() as usize)
             .collect();
 
+        let done = items_length as i64 <= query.get_limit_input();
+
+        let next_cursor = match items.last() {
+            Some(item) if !done => Some(item.cursor().encode()),
+            _ => None,
+        };
+
         Self {
             page: query.get_page_input(),
             limit: query.get_limit_input(),
             items,
-            done: items_length as i64 <= query.get_limit_input(),
+            done,
+            next_cursor,
         }
     }
+}
+
+#[cfg(test)]
+impl<T> PaginationResponseDto<T> {
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.next_cursor.as_deref()
+    }
 
     pub fn is_done(&self) -> bool {
         self.done
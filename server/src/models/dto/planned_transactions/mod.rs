@@ -0,0 +1,2 @@
+pub mod new_planned_transaction_dto;
+pub mod planned_transaction_dto;
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPlannedTransactionDto<'a> {
+    pub bank_account_id: &'a str,
+    pub category_id: Option<&'a str>,
+    pub subcategory_id: Option<&'a str>,
+    pub external_account_id: Option<&'a str>,
+    pub description: &'a str,
+    pub amount: i64,
+
+    /// RFC 3339 datetime of the first occurrence.
+    pub date: &'a str,
+
+    /// Leave empty for a one-off transaction.
+    pub recurrence: Option<Recurrence>,
+
+    /// RFC 3339 datetime after which no occurrences are planned.
+    pub ends_at: Option<&'a str>,
+}
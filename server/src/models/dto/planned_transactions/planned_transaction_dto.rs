@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+use crate::models::entities::planned_transaction::PlannedTransaction;
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedTransactionDto {
+    pub id: String,
+    pub bank_account_id: String,
+    pub category_id: Option<String>,
+    pub subcategory_id: Option<String>,
+    pub external_account_id: Option<String>,
+    pub description: String,
+    pub amount: i64,
+
+    /// The date of the next occurrence that has not been matched with a transaction yet.
+    pub date: String,
+    pub recurrence: Option<Recurrence>,
+    pub ends_at: Option<String>,
+    pub completed: bool,
+    pub linked_transaction_ids: Vec<String>,
+}
+
+impl From<PlannedTransaction> for PlannedTransactionDto {
+    fn from(planned: PlannedTransaction) -> Self {
+        Self {
+            id: planned.id,
+            bank_account_id: planned.bank_account_id,
+            category_id: planned.category_id,
+            subcategory_id: planned.subcategory_id,
+            external_account_id: planned.external_account_id,
+            description: planned.description,
+            amount: planned.amount,
+            date: planned.date.to_rfc3339(),
+            recurrence: planned.recurrence,
+            ends_at: planned.ends_at.map(|ends_at| ends_at.to_rfc3339()),
+            completed: planned.completed,
+            linked_transaction_ids: planned.linked_transaction_ids,
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod new_reconciliation_dto;
+pub mod reconciliation_dto;
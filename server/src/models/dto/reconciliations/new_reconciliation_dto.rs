@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewReconciliationDto<'a> {
+    pub bank_account_id: &'a str,
+
+    /// The date of the statement, formatted as RFC 3339.
+    pub date: &'a str,
+
+    /// The balance in cents the bank reports at the date of the statement.
+    pub balance: i64,
+}
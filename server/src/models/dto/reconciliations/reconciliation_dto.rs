@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationDto {
+    pub id: String,
+    pub bank_account_id: String,
+    pub date: String,
+
+    /// The balance the bank reported at the date of the reconciliation.
+    pub statement_balance: i64,
+
+    /// The balance calculated from the transactions in the application at the same date.
+    pub computed_balance: i64,
+
+    /// The amount that is missing in the application to match the statement. A correction of this
+    /// amount closes the gap.
+    pub difference: i64,
+}
@@ -0,0 +1,3 @@
+pub mod recurring_series_dto;
+pub mod upcoming_transaction_dto;
+pub mod update_recurring_series_dto;
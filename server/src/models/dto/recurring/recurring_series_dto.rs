@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+use crate::models::entities::recurring_series::{RecurringSeries, RecurringStatus};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringSeriesDto {
+    pub id: String,
+    pub external_account_id: Option<String>,
+    pub external_account_name: String,
+    pub recurrence: Recurrence,
+    pub amount: i64,
+    pub currency: String,
+    pub occurrences: i32,
+    pub last_date: String,
+    pub next_date: String,
+    pub status: RecurringStatus,
+}
+
+impl From<RecurringSeries> for RecurringSeriesDto {
+    fn from(series: RecurringSeries) -> Self {
+        Self {
+            id: series.id,
+            external_account_id: series.external_account_id,
+            external_account_name: series.external_account_name,
+            recurrence: series.recurrence,
+            amount: series.amount,
+            currency: series.currency,
+            occurrences: series.occurrences,
+            last_date: series.last_date.to_rfc3339(),
+            next_date: series.next_date.to_rfc3339(),
+            status: series.status,
+        }
+    }
+}
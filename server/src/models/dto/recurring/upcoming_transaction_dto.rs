@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// A transaction that is expected to happen, because it is part of a recurring series.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingTransactionDto {
+    pub series_id: String,
+    pub external_account_name: String,
+    pub date: String,
+    pub amount: i64,
+    pub currency: String,
+}
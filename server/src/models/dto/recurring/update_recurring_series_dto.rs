@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+use crate::models::entities::recurring_series::RecurringStatus;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRecurringSeriesDto {
+    pub status: RecurringStatus,
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// Selects the existing transactions the rules should be run against. Dates are expected to be
+/// formatted as RFC 3339 and the `to` date is exclusive.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyRulesDto<'a> {
+    pub from: Option<&'a str>,
+    pub to: Option<&'a str>,
+    pub bank_account_id: Option<&'a str>,
+
+    #[serde(default)]
+    pub only_uncategorized: bool,
+}
@@ -0,0 +1,4 @@
+pub mod apply_rules_dto;
+pub mod new_rule_dto;
+pub mod rule_change_dto;
+pub mod rule_dto;
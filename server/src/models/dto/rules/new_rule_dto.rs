@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+/// Uses owned strings instead of borrowed ones because regexes almost always contain escaped
+/// characters, which cannot be borrowed from the request body.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewRuleDto {
+    pub name: String,
+    pub priority: i32,
+    pub description_contains: Option<String>,
+    pub description_regex: Option<String>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub iban: Option<String>,
+    pub bank_account_id: Option<String>,
+    pub weekday: Option<i32>,
+    pub set_category_id: Option<String>,
+    pub set_subcategory_id: Option<String>,
+    pub set_description: Option<String>,
+    pub set_external_account_id: Option<String>,
+}
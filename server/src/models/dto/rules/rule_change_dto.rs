@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+/// Describes how a single transaction changes when the rules are applied to it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleChangeDto {
+    pub transaction_id: String,
+    pub original_description: String,
+    pub before: RuleChangeValuesDto,
+    pub after: RuleChangeValuesDto,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleChangeValuesDto {
+    pub category_id: Option<String>,
+    pub subcategory_id: Option<String>,
+    pub description: String,
+    pub external_account_id: Option<String>,
+}
@@ -0,0 +1,43 @@
+use serde::Serialize;
+
+use crate::models::entities::rule::Rule;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleDto {
+    pub id: String,
+    pub name: String,
+    pub priority: i32,
+    pub description_contains: Option<String>,
+    pub description_regex: Option<String>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub iban: Option<String>,
+    pub bank_account_id: Option<String>,
+    pub weekday: Option<i32>,
+    pub set_category_id: Option<String>,
+    pub set_subcategory_id: Option<String>,
+    pub set_description: Option<String>,
+    pub set_external_account_id: Option<String>,
+}
+
+impl From<Rule> for RuleDto {
+    fn from(rule: Rule) -> Self {
+        Self {
+            id: rule.id,
+            name: rule.name,
+            priority: rule.priority,
+            description_contains: rule.description_contains,
+            description_regex: rule.description_regex,
+            min_amount: rule.min_amount,
+            max_amount: rule.max_amount,
+            iban: rule.iban,
+            bank_account_id: rule.bank_account_id,
+            weekday: rule.weekday,
+            set_category_id: rule.set_category_id,
+            set_subcategory_id: rule.set_subcategory_id,
+            set_description: rule.set_description,
+            set_external_account_id: rule.set_external_account_id,
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod new_tag_dto;
+pub mod tag_dto;
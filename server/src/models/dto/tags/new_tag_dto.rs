@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTagDto<'a> {
+    pub name: &'a str,
+    pub hex_color: &'a str,
+}
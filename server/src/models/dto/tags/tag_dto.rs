@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+use crate::models::entities::tag::Tag;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagDto {
+    pub id: String,
+    pub name: String,
+    pub hex_color: String,
+}
+
+impl From<Tag> for TagDto {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+            hex_color: tag.hex_color,
+        }
+    }
+}
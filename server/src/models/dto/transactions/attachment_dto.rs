@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+use crate::models::entities::transaction_attachment::TransactionAttachment;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentDto {
+    pub id: String,
+    pub transaction_id: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub created_at: String,
+}
+
+impl From<TransactionAttachment> for AttachmentDto {
+    fn from(attachment: TransactionAttachment) -> Self {
+        Self {
+            id: attachment.id,
+            transaction_id: attachment.transaction_id,
+            filename: attachment.filename,
+            mime_type: attachment.mime_type,
+            created_at: attachment.created_at.to_rfc3339(),
+        }
+    }
+}
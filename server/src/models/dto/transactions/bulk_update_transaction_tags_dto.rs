@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateTransactionTagsDto {
+    pub transactions: Vec<String>,
+
+    /// Tags to put on all the transactions. Transactions that already have one of them keep it.
+    #[serde(default)]
+    pub add: Vec<String>,
+
+    /// Tags to take off all the transactions.
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
@@ -6,3 +6,7 @@ pub mod transaction_set_category_dto;
 pub mod update_transaction_details_dto;
 pub mod update_transaction_dto;
 pub mod bulk_update_transaction_categories_dto;
+pub mod transaction_search_query_dto;
+pub mod bulk_update_transaction_tags_dto;
+pub mod attachment_dto;
+pub mod new_attachment_dto;
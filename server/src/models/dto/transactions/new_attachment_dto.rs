@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+/// Attaches a blob that was uploaded through `/blob/upload` to a transaction.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAttachmentDto<'a> {
+    pub token: &'a str,
+    pub filename: &'a str,
+}
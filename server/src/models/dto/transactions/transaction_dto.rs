@@ -4,6 +4,7 @@ use crate::models::dto::bank_accounts::slim_bank_account_dto::SlimBankAccountDto
 use crate::models::dto::categories::slim_category_dto::SlimCategoryDto;
 use crate::models::dto::categories::subcategories::slim_subcategory_dto::SlimSubcategoryDto;
 use crate::models::dto::external_accounts::external_account_dto::ExternalAccountDto;
+use crate::models::dto::pagination::cursor::{Cursor, CursorItem};
 use crate::models::entities::transaction::transaction_type::TransactionType;
 
 #[derive(Debug, Serialize)]
@@ -16,11 +17,40 @@ pub struct TransactionDto {
     pub description: String,
     pub complete_amount: i64,
     pub amount: i64,
+    pub currency: String,
     pub date: String,
+
+    /// Orders transactions on the same date. Only used to paginate, so it is not sent along.
+    #[serde(skip)]
+    pub order_indicator: i32,
     pub bank_account: Option<SlimBankAccountDto>,
     pub category: Option<SlimCategoryDto>,
     pub subcategory: Option<SlimSubcategoryDto>,
     pub external_account_name: String,
+    pub external_account_iban: Option<String>,
     pub external_account: Option<ExternalAccountDto>,
     pub related_move_transaction: Option<String>,
+
+    /// The other leg when the transaction is a transfer between bank accounts of the user.
+    pub related_transfer_transaction: Option<String>,
+
+    /// The balance of the bank account directly after this transaction. This is only filled in
+    /// when it is explicitly requested, as it has to be calculated over all the transactions of the
+    /// bank account.
+    pub running_balance: Option<i64>,
+
+    /// Locked transactions are part of a reconciled balance, so changes that would alter the
+    /// balance are rejected.
+    pub locked: bool,
+    pub tag_ids: Vec<String>,
+}
+
+impl CursorItem for TransactionDto {
+    fn cursor(&self) -> Cursor {
+        Cursor {
+            date: self.date.to_string(),
+            order_indicator: self.order_indicator,
+            id: self.id.to_string(),
+        }
+    }
 }
@@ -0,0 +1,61 @@
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::models::entities::transaction::transaction_type::TransactionType;
+
+/// Query parameters for searching transactions. Dates are expected to be formatted as RFC 3339.
+/// Both ends of the amount range are inclusive, while the `to` date is exclusive.
+#[derive(Debug, FromForm)]
+pub struct TransactionSearchQueryDto {
+    /// Text that has to occur in the description, the original description or the name of the
+    /// external account, ignoring case. Every word is looked for separately.
+    pub q: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+
+    /// In the minor unit of the currency, so -1000 is an expense of 10 euro.
+    #[field(name = "minAmount")]
+    pub min_amount: Option<i64>,
+
+    #[field(name = "maxAmount")]
+    pub max_amount: Option<i64>,
+
+    #[field(name = "categoryId")]
+    pub category_id: Option<String>,
+
+    #[field(name = "subcategoryId")]
+    pub subcategory_id: Option<String>,
+
+    /// Only returns transactions without a category. Cannot be combined with a category.
+    pub uncategorized: bool,
+
+    #[field(name = "bankAccountId")]
+    pub bank_account_id: Option<String>,
+
+    #[field(name = "tagId")]
+    pub tag_id: Option<String>,
+
+    #[field(name = "importId")]
+    pub import_id: Option<String>,
+
+    /// Splits are left out unless they are asked for, like in the list of all transactions.
+    #[field(name = "type")]
+    pub transaction_type: Option<TransactionType>,
+
+    // Rocket only allows a single trailing query parameter, so the pagination is part of the
+    // search parameters.
+    #[field(default = 1, validate = range(1..))]
+    pub page: i64,
+
+    #[field(default = 50, validate = range(0..))]
+    pub limit: i64,
+    pub cursor: Option<String>,
+}
+
+impl TransactionSearchQueryDto {
+    pub fn pagination(&self) -> PaginationQueryDto {
+        PaginationQueryDto {
+            page: self.page,
+            limit: self.limit,
+            cursor: self.cursor.clone(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 pub mod admin_update_user_password_dto;
 pub mod admin_user_info_dto;
 pub mod new_user_dto;
+pub mod update_base_currency_dto;
 pub mod update_user_password_dto;
 pub mod user_dto;
 pub mod user_info_dto;
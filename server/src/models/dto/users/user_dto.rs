@@ -9,4 +9,7 @@ pub struct UserDto {
     pub username: String,
     pub profile_picture: Option<String>,
     pub role: UserRole,
+
+    /// The currency amounts in other currencies are converted to when they are added together.
+    pub base_currency: String,
 }
@@ -3,6 +3,7 @@ use sqlx::{Executor, Postgres};
 
 use crate::db_executor;
 use crate::prelude::*;
+use crate::shared::DbPool;
 
 /// A bank account something like a single IBAN. Used to differentiate between for example a savings
 /// account and a 'regular' bank account.
@@ -14,6 +15,9 @@ pub struct BankAccount {
     pub name: String,
     pub description: String,
     pub hex_color: String,
+
+    /// The currency of the account, the amounts of the transactions on it are in this currency.
+    pub currency: String,
 }
 
 impl BankAccount {
@@ -21,18 +25,51 @@ impl BankAccount {
         sqlx::query!(
             r#"
                 INSERT INTO bank_accounts
-                VALUES ($1, $2, $3, $4, $5, $6);
+                VALUES ($1, $2, $3, $4, $5, $6, $7);
             "#,
             self.id,
             self.iban,
             self.user_id,
             self.name,
             self.description,
-            self.hex_color
+            self.hex_color,
+            self.currency
         )
         .execute(executor)
         .await?;
 
         Ok(())
     }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM bank_accounts
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_currency(pool: &DbPool, id: &str, user_id: &str) -> Result<String> {
+        let record = sqlx::query!(
+            r#"
+                SELECT currency
+                FROM bank_accounts
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record.currency)
+    }
 }
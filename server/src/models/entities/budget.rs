@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use sqlx::types::time::OffsetDateTime;
+
+use crate::models::entities::budget::budget_period::BudgetPeriod;
+use crate::prelude::*;
+use crate::shared::DbPool;
+use crate::utils::datetime::to_utc;
+
+pub mod budget_period;
+
+/// The amount a user plans to spend in a category (or a single subcategory) each period.
+#[derive(Debug)]
+pub struct Budget {
+    pub id: String,
+    pub user_id: String,
+    pub category_id: String,
+
+    /// When this is set, only the transactions in this subcategory count towards the budget.
+    /// Otherwise all the transactions of the category count, regardless of their subcategory.
+    pub subcategory_id: Option<String>,
+
+    /// The amount in cents that is available to spend each period.
+    pub amount: i64,
+    pub period: BudgetPeriod,
+
+    /// If this is true, the money that was not spent in a period is added to the next period.
+    pub rollover: bool,
+
+    /// The budget only starts counting from the period this datetime falls in.
+    pub starts_at: DateTime<Utc>,
+}
+
+impl Budget {
+    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO budgets
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
+            "#,
+            self.id,
+            self.user_id,
+            self.category_id,
+            self.subcategory_id,
+            self.amount,
+            <&str>::from(self.period),
+            self.rollover,
+            OffsetDateTime::from_unix_timestamp(self.starts_at.timestamp())?,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM budgets
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(pool: &DbPool, id: &str, user_id: &str) -> Result<Budget> {
+        let budgets = Budget::fetch(pool, Some(id), user_id).await?;
+
+        let Some(budget) = budgets.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(budget)
+    }
+
+    pub async fn find_all(pool: &DbPool, user_id: &str) -> Result<Vec<Budget>> {
+        Budget::fetch(pool, None, user_id).await
+    }
+
+    async fn fetch(pool: &DbPool, id: Option<&str>, user_id: &str) -> Result<Vec<Budget>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT budgets.*
+                FROM budgets
+                INNER JOIN categories c ON c.id = budgets.category_id
+                WHERE budgets.user_id = $1 AND ($2::varchar IS NULL OR budgets.id = $2)
+                ORDER BY c.order_index, budgets.subcategory_id NULLS FIRST;
+            "#,
+            user_id,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| Budget {
+                id: record.id,
+                user_id: record.user_id,
+                category_id: record.category_id,
+                subcategory_id: record.subcategory_id,
+                amount: record.amount,
+                period: BudgetPeriod::from(&*record.period),
+                rollover: record.rollover,
+                starts_at: to_utc(record.starts_at),
+            })
+            .collect())
+    }
+}
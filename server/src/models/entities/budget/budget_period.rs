@@ -0,0 +1,120 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The length of a single budget period. Periods follow the calendar, so a monthly budget always
+/// runs from the first of the month until the first of the next month and weeks start on Monday.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Copy, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum BudgetPeriod {
+    Week,
+    Month,
+    Year,
+}
+
+impl BudgetPeriod {
+    /// Returns the start of the period the given datetime falls in.
+    pub fn start_of(&self, datetime: DateTime<Utc>) -> DateTime<Utc> {
+        let date = datetime.date_naive();
+
+        let start = match self {
+            BudgetPeriod::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            BudgetPeriod::Month => date.with_day(1).expect("Every month has a first day"),
+            BudgetPeriod::Year => date
+                .with_ordinal(1)
+                .expect("Every year has a first day"),
+        };
+
+        Utc.from_utc_datetime(&start.and_hms_opt(0, 0, 0).expect("Midnight is a valid time"))
+    }
+
+    /// Returns the start of the period that comes after the period starting at the given datetime.
+    pub fn next(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            BudgetPeriod::Week => start + Duration::weeks(1),
+            BudgetPeriod::Month => {
+                let (year, month) = if start.month() == 12 {
+                    (start.year() + 1, 1)
+                } else {
+                    (start.year(), start.month() + 1)
+                };
+
+                Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .single()
+                    .expect("The first of the month is always valid")
+            }
+            BudgetPeriod::Year => Utc
+                .with_ymd_and_hms(start.year() + 1, 1, 1, 0, 0, 0)
+                .single()
+                .expect("The first of the year is always valid"),
+        }
+    }
+}
+
+impl From<&str> for BudgetPeriod {
+    fn from(value: &str) -> Self {
+        match value {
+            "week" => BudgetPeriod::Week,
+            "year" => BudgetPeriod::Year,
+            _ => BudgetPeriod::Month,
+        }
+    }
+}
+
+impl From<BudgetPeriod> for &str {
+    fn from(val: BudgetPeriod) -> Self {
+        match val {
+            BudgetPeriod::Week => "week",
+            BudgetPeriod::Month => "month",
+            BudgetPeriod::Year => "year",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::models::entities::budget::budget_period::BudgetPeriod;
+
+    #[test]
+    fn weeks_start_on_monday() {
+        // This is a Saturday
+        let date = Utc.with_ymd_and_hms(2023, 2, 11, 11, 0, 0).unwrap();
+
+        let start = BudgetPeriod::Week.start_of(date);
+
+        assert_eq!(start, Utc.with_ymd_and_hms(2023, 2, 6, 0, 0, 0).unwrap());
+        assert_eq!(
+            BudgetPeriod::Week.next(start),
+            Utc.with_ymd_and_hms(2023, 2, 13, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn months_wrap_around_the_year() {
+        let date = Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap();
+
+        let start = BudgetPeriod::Month.start_of(date);
+
+        assert_eq!(start, Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            BudgetPeriod::Month.next(start),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn years_start_on_the_first_of_january() {
+        let date = Utc.with_ymd_and_hms(2023, 6, 15, 12, 0, 0).unwrap();
+
+        let start = BudgetPeriod::Year.start_of(date);
+
+        assert_eq!(start, Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            BudgetPeriod::Year.next(start),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::csv::amount_format::AmountFormat;
+use crate::models::csv::csv_mapping::{
+    AmountMapping, CsvColumn, CsvImportOrdering, CsvMapping, DateMapping, DebitCreditMapping,
+};
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// A named [CsvMapping] the user saved, so it doesn't have to be sent again for every import.
+#[derive(Debug)]
+pub struct CsvMappingProfile {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+
+    /// The normalized header row of the CSV files this profile is meant for. When a CSV with the
+    /// same header is imported, this profile is suggested.
+    pub header: Option<String>,
+
+    pub mapping: CsvMapping,
+}
+
+/// The mapped columns of a profile, which are stored together as JSON. The default currency and
+/// the format of the amounts are stored here as well, as they are only used when reading the
+/// columns.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileColumns {
+    account_iban: CsvColumn,
+    date: CsvColumn,
+    follow_number: CsvColumn,
+    description: CsvColumn,
+    amount: CsvColumn,
+    external_account_name: CsvColumn,
+
+    #[serde(default)]
+    currency: Option<CsvColumn>,
+    #[serde(default)]
+    default_currency: Option<String>,
+    #[serde(default)]
+    external_account_iban: Option<CsvColumn>,
+    #[serde(default)]
+    value_date: Option<CsvColumn>,
+    #[serde(default)]
+    debit_credit: Option<DebitCreditMapping>,
+    #[serde(default)]
+    credit_amount: Option<CsvColumn>,
+    #[serde(default)]
+    amount_format: AmountFormat,
+}
+
+impl CsvMappingProfile {
+    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+        let mapping = &self.mapping;
+        let columns = CsvMappingProfile::serialize_columns(mapping)?;
+
+        sqlx::query!(
+            r#"
+                INSERT INTO csv_mapping_profiles
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);
+            "#,
+            self.id,
+            self.user_id,
+            self.name,
+            self.header,
+            mapping.date_mapping.template,
+            mapping.date_mapping.format,
+            <&str>::from(mapping.amount_mapping),
+            <&str>::from(mapping.ordering),
+            columns,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Serializes the mapped columns of the mapping to the JSON that is stored in the `columns`
+    /// column.
+    pub fn serialize_columns(mapping: &CsvMapping) -> Result<String> {
+        let columns = serde_json::to_string(&ProfileColumns {
+            account_iban: mapping.account_iban.clone(),
+            date: mapping.date.clone(),
+            follow_number: mapping.follow_number.clone(),
+            description: mapping.description.clone(),
+            amount: mapping.amount.clone(),
+            external_account_name: mapping.external_account_name.clone(),
+            currency: mapping.currency.clone(),
+            default_currency: mapping.default_currency.clone(),
+            external_account_iban: mapping.external_account_iban.clone(),
+            value_date: mapping.value_date.clone(),
+            debit_credit: mapping.debit_credit.clone(),
+            credit_amount: mapping.credit_amount.clone(),
+            amount_format: mapping.amount_format.clone(),
+        })?;
+
+        Ok(columns)
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM csv_mapping_profiles
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(pool: &DbPool, id: &str, user_id: &str) -> Result<CsvMappingProfile> {
+        let profiles = CsvMappingProfile::fetch(pool, Some(id), None, user_id).await?;
+
+        let Some(profile) = profiles.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(profile)
+    }
+
+    /// Returns the profile that was saved for CSV files with the given normalized header, if any.
+    pub async fn find_by_header(
+        pool: &DbPool,
+        header: &str,
+        user_id: &str,
+    ) -> Result<Option<CsvMappingProfile>> {
+        let profiles = CsvMappingProfile::fetch(pool, None, Some(header), user_id).await?;
+
+        Ok(profiles.into_iter().next())
+    }
+
+    pub async fn find_all(pool: &DbPool, user_id: &str) -> Result<Vec<CsvMappingProfile>> {
+        CsvMappingProfile::fetch(pool, None, None, user_id).await
+    }
+
+    async fn fetch(
+        pool: &DbPool,
+        id: Option<&str>,
+        header: Option<&str>,
+        user_id: &str,
+    ) -> Result<Vec<CsvMappingProfile>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT *
+                FROM csv_mapping_profiles
+                WHERE user_id = $1
+                    AND ($2::varchar IS NULL OR id = $2)
+                    AND ($3::varchar IS NULL OR header = $3)
+                ORDER BY name;
+            "#,
+            user_id,
+            id,
+            header
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut profiles = vec![];
+
+        for record in records {
+            let columns: ProfileColumns = serde_json::from_str(&record.columns)?;
+
+            profiles.push(CsvMappingProfile {
+                id: record.id,
+                user_id: record.user_id,
+                name: record.name,
+                header: record.header,
+                mapping: CsvMapping {
+                    date_mapping: DateMapping {
+                        template: record.date_template,
+                        format: record.date_format,
+                    },
+                    amount_mapping: AmountMapping::from(&*record.amount_mapping),
+                    amount_format: columns.amount_format,
+                    ordering: CsvImportOrdering::from(&*record.ordering),
+                    account_iban: columns.account_iban,
+                    date: columns.date,
+                    follow_number: columns.follow_number,
+                    description: columns.description,
+                    amount: columns.amount,
+                    external_account_name: columns.external_account_name,
+                    currency: columns.currency,
+                    default_currency: columns.default_currency,
+                    external_account_iban: columns.external_account_iban,
+                    value_date: columns.value_date,
+                    debit_credit: columns.debit_credit,
+                    credit_amount: columns.credit_amount,
+                },
+            });
+        }
+
+        Ok(profiles)
+    }
+}
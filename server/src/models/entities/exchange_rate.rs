@@ -0,0 +1,64 @@
+use sqlx::types::time::Date;
+
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// The reference rate of a currency against the euro on a date, so the amount of the currency that
+/// is worth one euro.
+#[derive(Debug)]
+pub struct ExchangeRate {
+    pub user_id: String,
+    pub currency: String,
+    pub date: Date,
+    pub rate: f64,
+}
+
+impl ExchangeRate {
+    /// Inserts all the rates at once. Rates that already exist for the same currency and date are
+    /// replaced.
+    pub async fn upsert_all(pool: &DbPool, rates: &[ExchangeRate]) -> Result<u64> {
+        let user_ids: Vec<String> = rates.iter().map(|rate| rate.user_id.clone()).collect();
+        let currencies: Vec<String> = rates.iter().map(|rate| rate.currency.clone()).collect();
+        let dates: Vec<Date> = rates.iter().map(|rate| rate.date).collect();
+        let values: Vec<f64> = rates.iter().map(|rate| rate.rate).collect();
+
+        let result = sqlx::query!(
+            r#"
+                INSERT INTO exchange_rates (user_id, currency, date, rate)
+                SELECT *
+                FROM UNNEST($1::varchar[], $2::varchar[], $3::date[], $4::float8[])
+                ON CONFLICT (user_id, currency, date) DO UPDATE SET rate = EXCLUDED.rate;
+            "#,
+            &user_ids,
+            &currencies,
+            &dates,
+            &values,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_all(
+        pool: &DbPool,
+        user_id: &str,
+        currency: Option<&str>,
+    ) -> Result<Vec<ExchangeRate>> {
+        let rates = sqlx::query_as!(
+            ExchangeRate,
+            r#"
+                SELECT *
+                FROM exchange_rates
+                WHERE user_id = $1 AND ($2::varchar IS NULL OR currency = $2)
+                ORDER BY currency, date DESC;
+            "#,
+            user_id,
+            currency,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rates)
+    }
+}
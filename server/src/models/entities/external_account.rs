@@ -1,5 +1,6 @@
-use sqlx::FromRow;
+use sqlx::{Executor, FromRow, Postgres};
 
+use crate::db_executor;
 use crate::prelude::*;
 use crate::shared::DbPool;
 
@@ -20,7 +21,7 @@ pub struct ExternalAccount {
 }
 
 impl ExternalAccount {
-    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+    pub async fn create<'d>(&self, executor: db_executor!('d)) -> Result<()> {
         sqlx::query!(
             r#"
                 INSERT INTO external_accounts
@@ -34,7 +35,7 @@ impl ExternalAccount {
             self.default_subcategory_id,
             self.hex_color,
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
 
         Ok(())
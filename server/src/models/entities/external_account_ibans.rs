@@ -0,0 +1,58 @@
+use sqlx::FromRow;
+
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// Used to link an IBAN in the [Transaction::external_account_iban] to an actual [ExternalAccount].
+#[derive(Debug, FromRow)]
+pub struct ExternalAccountIban {
+    pub id: String,
+    pub user_id: String,
+
+    /// The normalized IBAN of the external account. An IBAN can only belong to a single external
+    /// account of the user.
+    pub iban: String,
+    pub parent_external_account: String,
+}
+
+impl ExternalAccountIban {
+    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO external_account_ibans
+                VALUES ($1, $2, $3, $4);
+            "#,
+            self.id,
+            self.user_id,
+            self.iban,
+            self.parent_external_account
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(
+        pool: &DbPool,
+        id: &str,
+        external_account_id: &str,
+        user_id: &str,
+    ) -> Result<ExternalAccountIban> {
+        let record = sqlx::query_as!(
+            ExternalAccountIban,
+            r#"
+                SELECT *
+                FROM external_account_ibans
+                WHERE id = $1 AND parent_external_account = $2 AND user_id = $3;
+            "#,
+            id,
+            external_account_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+}
@@ -1,8 +1,12 @@
-use sqlx::FromRow;
+use sqlx::{Executor, FromRow, Postgres};
 
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+use crate::db_executor;
 use crate::prelude::*;
 use crate::shared::DbPool;
 
+pub mod name_match_kind;
+
 /// Used to link a name in the [Transaction::external_account_name] to an actual [ExternalAccount].
 #[derive(Debug, FromRow)]
 pub struct ExternalAccountName {
@@ -14,21 +18,25 @@ pub struct ExternalAccountName {
     /// account.
     pub name: String,
     pub parent_external_account: String,
+
+    /// How the [name] is matched, either as the exact name or as a pattern.
+    pub match_kind: NameMatchKind,
 }
 
 impl ExternalAccountName {
-    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+    pub async fn create<'d>(&self, executor: db_executor!('d)) -> Result<()> {
         sqlx::query!(
             r#"
                 INSERT INTO external_account_names
-                VALUES ($1, $2, $3, $4);
+                VALUES ($1, $2, $3, $4, $5);
             "#,
             self.id,
             self.user_id,
             self.name,
-            self.parent_external_account
+            self.parent_external_account,
+            <&str>::from(self.match_kind),
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
 
         Ok(())
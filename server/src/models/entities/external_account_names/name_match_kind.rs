@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// How the name of an [ExternalAccountName] is compared with the names of transactions. Only exact
+/// names are case-sensitive, the patterns ignore case unless a regex says otherwise.
+#[derive(Debug, Type, Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Default)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
+pub enum NameMatchKind {
+    /// The name of the transaction has to be the same as the name.
+    #[default]
+    Exact,
+
+    /// The name of the transaction starts with the name.
+    Prefix,
+
+    /// The name of the transaction contains the name anywhere.
+    Contains,
+
+    /// The name is a glob where `*` matches any number of characters and `?` a single one.
+    Glob,
+
+    /// The name is a regex that has to match somewhere in the name of the transaction.
+    Regex,
+}
+
+impl From<&str> for NameMatchKind {
+    fn from(value: &str) -> Self {
+        match value {
+            "prefix" => NameMatchKind::Prefix,
+            "contains" => NameMatchKind::Contains,
+            "glob" => NameMatchKind::Glob,
+            "regex" => NameMatchKind::Regex,
+            _ => NameMatchKind::Exact,
+        }
+    }
+}
+
+impl From<NameMatchKind> for &str {
+    fn from(val: NameMatchKind) -> Self {
+        match val {
+            NameMatchKind::Exact => "exact",
+            NameMatchKind::Prefix => "prefix",
+            NameMatchKind::Contains => "contains",
+            NameMatchKind::Glob => "glob",
+            NameMatchKind::Regex => "regex",
+        }
+    }
+}
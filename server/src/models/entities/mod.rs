@@ -1,9 +1,19 @@
 pub mod bank_account;
+pub mod budget;
 pub mod category;
+pub mod csv_mapping_profile;
+pub mod exchange_rate;
 pub mod external_account;
+pub mod external_account_ibans;
 pub mod external_account_names;
 pub mod grant;
 pub mod import;
+pub mod planned_transaction;
+pub mod reconciliation;
+pub mod recurring_series;
+pub mod rule;
 pub mod subcategory;
+pub mod tag;
 pub mod transaction;
+pub mod transaction_attachment;
 pub mod user;
@@ -0,0 +1,291 @@
+use chrono::{DateTime, Utc};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{Executor, Postgres};
+
+use crate::db_executor;
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+use crate::prelude::*;
+use crate::shared::DbPool;
+use crate::utils::datetime::to_utc;
+
+/// A transaction the user expects to happen on a bank account, once or at a regular interval.
+/// When an imported transaction fulfils the next occurrence, the two are linked and the planned
+/// transaction moves on to the occurrence after it.
+#[derive(Debug)]
+pub struct PlannedTransaction {
+    pub id: String,
+    pub user_id: String,
+    pub bank_account_id: String,
+    pub category_id: Option<String>,
+    pub subcategory_id: Option<String>,
+
+    /// Only planned transactions with an external account are matched with imported transactions.
+    pub external_account_id: Option<String>,
+    pub description: String,
+
+    /// The expected amount in the minor unit of the currency of the bank account.
+    pub amount: i64,
+
+    /// The date of the next occurrence that has not been matched with a transaction yet.
+    pub date: DateTime<Utc>,
+
+    /// Is [None] for a one-off transaction.
+    pub recurrence: Option<Recurrence>,
+
+    /// No occurrences are planned after this datetime.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub completed: bool,
+
+    /// The transactions that were matched with an occurrence, oldest first.
+    pub linked_transaction_ids: Vec<String>,
+}
+
+impl PlannedTransaction {
+    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO planned_transactions
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12);
+            "#,
+            self.id,
+            self.user_id,
+            self.bank_account_id,
+            self.category_id,
+            self.subcategory_id,
+            self.external_account_id,
+            self.description,
+            self.amount,
+            OffsetDateTime::from_unix_timestamp(self.date.timestamp())?,
+            self.recurrence.map(<&str>::from),
+            self.ends_at
+                .map(|ends_at| OffsetDateTime::from_unix_timestamp(ends_at.timestamp()))
+                .transpose()?,
+            self.completed,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the dates of the occurrences from the next one up to and including `until`.
+    pub fn occurrences(&self, until: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let mut dates = vec![];
+
+        if self.completed {
+            return dates;
+        }
+
+        let until = match self.ends_at {
+            Some(ends_at) => until.min(ends_at),
+            None => until,
+        };
+
+        let mut date = self.date;
+
+        while date <= until {
+            dates.push(date);
+
+            let Some(recurrence) = self.recurrence else {
+                break;
+            };
+
+            date = recurrence.next(date);
+        }
+
+        dates
+    }
+
+    /// Moves on to the occurrence after the current one. One-off transactions and transactions
+    /// whose next occurrence falls after `ends_at` are completed instead.
+    pub fn advance(&mut self) {
+        let next = self.recurrence.map(|recurrence| recurrence.next(self.date));
+
+        match next {
+            Some(next) if self.ends_at.is_none_or(|ends_at| next <= ends_at) => {
+                self.date = next;
+            }
+            _ => self.completed = true,
+        }
+    }
+
+    /// Links the transaction to the current occurrence and stores the schedule after advancing it.
+    pub async fn link_and_advance<'r>(
+        &mut self,
+        executor: db_executor!('r),
+        transaction_id: &str,
+    ) -> Result<()> {
+        let occurrence_date = OffsetDateTime::from_unix_timestamp(self.date.timestamp())?;
+
+        self.advance();
+
+        sqlx::query!(
+            r#"
+                WITH link AS (
+                    INSERT INTO planned_transaction_links
+                    VALUES ($1, $2, $3, $4)
+                )
+                UPDATE planned_transactions
+                SET date = $5, completed = $6
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            self.id,
+            self.user_id,
+            transaction_id,
+            occurrence_date,
+            OffsetDateTime::from_unix_timestamp(self.date.timestamp())?,
+            self.completed,
+        )
+        .execute(executor)
+        .await?;
+
+        self.linked_transaction_ids.push(transaction_id.to_string());
+
+        Ok(())
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM planned_transactions
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(pool: &DbPool, id: &str, user_id: &str) -> Result<PlannedTransaction> {
+        let planned = PlannedTransaction::fetch(pool, Some(id), user_id).await?;
+
+        let Some(planned) = planned.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(planned)
+    }
+
+    pub async fn find_all(pool: &DbPool, user_id: &str) -> Result<Vec<PlannedTransaction>> {
+        PlannedTransaction::fetch(pool, None, user_id).await
+    }
+
+    async fn fetch(
+        pool: &DbPool,
+        id: Option<&str>,
+        user_id: &str,
+    ) -> Result<Vec<PlannedTransaction>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT
+                    planned_transactions.*,
+                    ARRAY(
+                        SELECT l.transaction_id
+                        FROM planned_transaction_links l
+                        WHERE l.planned_transaction_id = planned_transactions.id
+                            AND l.user_id = planned_transactions.user_id
+                        ORDER BY l.occurrence_date
+                    ) AS "linked_transaction_ids!"
+                FROM planned_transactions
+                WHERE user_id = $1 AND ($2::varchar IS NULL OR id = $2)
+                ORDER BY completed, date, description;
+            "#,
+            user_id,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| PlannedTransaction {
+                id: record.id,
+                user_id: record.user_id,
+                bank_account_id: record.bank_account_id,
+                category_id: record.category_id,
+                subcategory_id: record.subcategory_id,
+                external_account_id: record.external_account_id,
+                description: record.description,
+                amount: record.amount,
+                date: to_utc(record.date),
+                recurrence: record.recurrence.as_deref().map(Recurrence::from),
+                ends_at: record.ends_at.map(to_utc),
+                completed: record.completed,
+                linked_transaction_ids: record.linked_transaction_ids,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use crate::models::entities::planned_transaction::PlannedTransaction;
+    use crate::models::entities::recurring_series::recurrence::Recurrence;
+
+    fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn planned(
+        recurrence: Option<Recurrence>,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> PlannedTransaction {
+        PlannedTransaction {
+            id: "planned".to_string(),
+            user_id: "user".to_string(),
+            bank_account_id: "bank-account".to_string(),
+            category_id: None,
+            subcategory_id: None,
+            external_account_id: None,
+            description: "Rent".to_string(),
+            amount: -95000,
+            date: date(2023, 1, 15),
+            recurrence,
+            ends_at,
+            completed: false,
+            linked_transaction_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn occurrences_stop_at_end() {
+        let planned = planned(Some(Recurrence::Month), Some(date(2023, 3, 31)));
+
+        assert_eq!(
+            planned.occurrences(date(2023, 6, 1)),
+            vec![date(2023, 1, 15), date(2023, 2, 15), date(2023, 3, 15)]
+        );
+    }
+
+    #[test]
+    fn one_off_occurs_once() {
+        let planned = planned(None, None);
+
+        assert_eq!(planned.occurrences(date(2023, 6, 1)), vec![date(2023, 1, 15)]);
+        assert!(planned.occurrences(date(2023, 1, 1)).is_empty());
+    }
+
+    #[test]
+    fn advance_completes_last_occurrence() {
+        let mut recurring = planned(Some(Recurrence::Month), Some(date(2023, 3, 1)));
+
+        recurring.advance();
+        assert_eq!(recurring.date, date(2023, 2, 15));
+        assert!(!recurring.completed);
+
+        recurring.advance();
+        assert!(recurring.completed);
+
+        let mut one_off = planned(None, None);
+
+        one_off.advance();
+        assert!(one_off.completed);
+        assert!(one_off.occurrences(date(2023, 6, 1)).is_empty());
+    }
+}
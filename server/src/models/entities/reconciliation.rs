@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::types::time::OffsetDateTime;
+
+use crate::error::http_error::HttpError;
+use crate::prelude::*;
+use crate::shared::DbPool;
+use crate::utils::datetime::to_utc;
+
+/// A checkpoint of a bank statement. All the transactions of the bank account up to and including
+/// the date of the reconciliation are locked, so the reconciled balance cannot change afterwards.
+#[derive(Debug)]
+pub struct Reconciliation {
+    pub id: String,
+    pub user_id: String,
+    pub bank_account_id: String,
+    pub date: DateTime<Utc>,
+
+    /// The balance in cents the bank reported at the date of the reconciliation.
+    pub balance: i64,
+}
+
+impl Reconciliation {
+    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO reconciliations
+                VALUES ($1, $2, $3, $4, $5);
+            "#,
+            self.id,
+            self.user_id,
+            self.bank_account_id,
+            OffsetDateTime::from_unix_timestamp(self.date.timestamp())?,
+            self.balance,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM reconciliations
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(pool: &DbPool, id: &str, user_id: &str) -> Result<Reconciliation> {
+        let reconciliations = Reconciliation::fetch(pool, Some(id), user_id).await?;
+
+        let Some(reconciliation) = reconciliations.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(reconciliation)
+    }
+
+    pub async fn find_all(pool: &DbPool, user_id: &str) -> Result<Vec<Reconciliation>> {
+        Reconciliation::fetch(pool, None, user_id).await
+    }
+
+    /// Returns the date of the latest reconciliation for every bank account of the user that has
+    /// been reconciled.
+    pub async fn locked_until(
+        pool: &DbPool,
+        user_id: &str,
+    ) -> Result<HashMap<String, DateTime<Utc>>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT bank_account_id, MAX(date) AS "date!"
+                FROM reconciliations
+                WHERE user_id = $1
+                GROUP BY bank_account_id;
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| (record.bank_account_id, to_utc(record.date)))
+            .collect())
+    }
+
+    /// Rejects the request when a transaction at the given date in the given bank account would
+    /// change a reconciled balance.
+    pub async fn guard_unlocked(
+        pool: &DbPool,
+        user_id: &str,
+        bank_account_id: &str,
+        date: OffsetDateTime,
+    ) -> Result<()> {
+        let record = sqlx::query!(
+            r#"
+                SELECT EXISTS(
+                    SELECT id
+                    FROM reconciliations
+                    WHERE user_id = $1 AND bank_account_id = $2 AND date >= $3
+                ) AS "locked!";
+            "#,
+            user_id,
+            bank_account_id,
+            date
+        )
+        .fetch_one(pool)
+        .await?;
+
+        if record.locked {
+            return Err(Reconciliation::locked_error());
+        }
+
+        Ok(())
+    }
+
+    /// Rejects the request when any of the given transactions is locked by a reconciliation.
+    pub async fn guard_transactions_unlocked(
+        pool: &DbPool,
+        user_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<()> {
+        let record = sqlx::query!(
+            r#"
+                SELECT EXISTS(
+                    SELECT transactions.id
+                    FROM transactions
+                    INNER JOIN reconciliations r
+                        ON r.user_id = transactions.user_id
+                        AND r.bank_account_id = transactions.bank_account_id
+                        AND r.date >= transactions.date
+                    WHERE transactions.user_id = $1 AND transactions.id = ANY($2)
+                ) AS "locked!";
+            "#,
+            user_id,
+            transaction_ids
+        )
+        .fetch_one(pool)
+        .await?;
+
+        if record.locked {
+            return Err(Reconciliation::locked_error());
+        }
+
+        Ok(())
+    }
+
+    pub fn locked_error() -> Error {
+        HttpError::new(409) // Conflict
+            .message("Cannot change transactions that are part of a reconciled balance")
+            .into()
+    }
+
+    async fn fetch(
+        pool: &DbPool,
+        id: Option<&str>,
+        user_id: &str,
+    ) -> Result<Vec<Reconciliation>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT *
+                FROM reconciliations
+                WHERE user_id = $1 AND ($2::varchar IS NULL OR id = $2)
+                ORDER BY date DESC;
+            "#,
+            user_id,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| Reconciliation {
+                id: record.id,
+                user_id: record.user_id,
+                bank_account_id: record.bank_account_id,
+                date: to_utc(record.date),
+                balance: record.balance,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{Executor, Postgres};
+
+use crate::db_executor;
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+use crate::prelude::*;
+use crate::shared::DbPool;
+use crate::utils::datetime::to_utc;
+
+pub mod recurrence;
+
+/// Transactions with the same external account that happen at a regular interval and have about
+/// the same amount, like rent, a subscription or salary.
+#[derive(Debug)]
+pub struct RecurringSeries {
+    pub id: String,
+    pub user_id: String,
+
+    /// Identifies the series when it is detected again: the id of the external account when the
+    /// transactions have one, otherwise the lowercased name of the external account.
+    pub series_key: String,
+    pub external_account_id: Option<String>,
+    pub external_account_name: String,
+    pub recurrence: Recurrence,
+
+    /// The typical amount of a single transaction in the minor unit of the currency.
+    pub amount: i64,
+    pub currency: String,
+    pub occurrences: i32,
+    pub last_date: DateTime<Utc>,
+
+    /// The date the next transaction of the series is expected.
+    pub next_date: DateTime<Utc>,
+    pub status: RecurringStatus,
+}
+
+/// Series are detected automatically. The user can confirm a series so it's kept even when it is
+/// not detected anymore, or dismiss it so it won't be suggested again.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum RecurringStatus {
+    Detected,
+    Confirmed,
+    Dismissed,
+}
+
+impl RecurringSeries {
+    /// Inserts the series, or updates the detected values of the series with the same key. The id
+    /// and the status of an existing series are kept.
+    pub async fn upsert<'r>(&self, executor: db_executor!('r)) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO recurring_series
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                ON CONFLICT (user_id, series_key) DO UPDATE
+                SET external_account_id = EXCLUDED.external_account_id,
+                    external_account_name = EXCLUDED.external_account_name,
+                    recurrence = EXCLUDED.recurrence,
+                    amount = EXCLUDED.amount,
+                    currency = EXCLUDED.currency,
+                    occurrences = EXCLUDED.occurrences,
+                    last_date = EXCLUDED.last_date,
+                    next_date = EXCLUDED.next_date;
+            "#,
+            self.id,
+            self.user_id,
+            self.series_key,
+            self.external_account_id,
+            self.external_account_name,
+            <&str>::from(self.recurrence),
+            self.amount,
+            self.currency,
+            self.occurrences,
+            OffsetDateTime::from_unix_timestamp(self.last_date.timestamp())?,
+            OffsetDateTime::from_unix_timestamp(self.next_date.timestamp())?,
+            <&str>::from(self.status),
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes the series that are only detected, and not confirmed or dismissed by the user,
+    /// which don't have one of the given keys.
+    pub async fn delete_detected_except<'r>(
+        executor: db_executor!('r),
+        user_id: &str,
+        series_keys: &[String],
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM recurring_series
+                WHERE user_id = $1 AND status = 'detected' AND series_key <> ALL($2);
+            "#,
+            user_id,
+            series_keys,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM recurring_series
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(pool: &DbPool, id: &str, user_id: &str) -> Result<RecurringSeries> {
+        let series = RecurringSeries::fetch(pool, Some(id), user_id).await?;
+
+        let Some(series) = series.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(series)
+    }
+
+    pub async fn find_all(pool: &DbPool, user_id: &str) -> Result<Vec<RecurringSeries>> {
+        RecurringSeries::fetch(pool, None, user_id).await
+    }
+
+    async fn fetch(pool: &DbPool, id: Option<&str>, user_id: &str) -> Result<Vec<RecurringSeries>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT *
+                FROM recurring_series
+                WHERE user_id = $1 AND ($2::varchar IS NULL OR id = $2)
+                ORDER BY next_date, external_account_name;
+            "#,
+            user_id,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| RecurringSeries {
+                id: record.id,
+                user_id: record.user_id,
+                series_key: record.series_key,
+                external_account_id: record.external_account_id,
+                external_account_name: record.external_account_name,
+                recurrence: Recurrence::from(&*record.recurrence),
+                amount: record.amount,
+                currency: record.currency,
+                occurrences: record.occurrences,
+                last_date: to_utc(record.last_date),
+                next_date: to_utc(record.next_date),
+                status: RecurringStatus::from(&*record.status),
+            })
+            .collect())
+    }
+}
+
+impl From<&str> for RecurringStatus {
+    fn from(value: &str) -> Self {
+        match value {
+            "confirmed" => RecurringStatus::Confirmed,
+            "dismissed" => RecurringStatus::Dismissed,
+            _ => RecurringStatus::Detected,
+        }
+    }
+}
+
+impl From<RecurringStatus> for &str {
+    fn from(val: RecurringStatus) -> Self {
+        match val {
+            RecurringStatus::Detected => "detected",
+            RecurringStatus::Confirmed => "confirmed",
+            RecurringStatus::Dismissed => "dismissed",
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How often the transactions of a recurring series happen.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Copy, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum Recurrence {
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Recurrence {
+    /// Returns the recurrence whose interval contains the given number of days between two
+    /// transactions.
+    pub fn from_interval(days: i64) -> Option<Recurrence> {
+        [
+            Recurrence::Week,
+            Recurrence::Month,
+            Recurrence::Quarter,
+            Recurrence::Year,
+        ]
+        .into_iter()
+        .find(|recurrence| recurrence.interval_days().contains(&days))
+    }
+
+    /// The number of days that can be between two transactions of the series. Banks don't book on
+    /// weekends and months differ in length, so this leaves a few days of slack on either side.
+    pub fn interval_days(&self) -> RangeInclusive<i64> {
+        match self {
+            Recurrence::Week => 5..=9,
+            Recurrence::Month => 25..=36,
+            Recurrence::Quarter => 83..=99,
+            Recurrence::Year => 350..=380,
+        }
+    }
+
+    /// Returns the date of the transaction that follows the one on the given date. Months are
+    /// added on the calendar, so the 31st of January is followed by the last day of February.
+    pub fn next(&self, date: DateTime<Utc>) -> DateTime<Utc> {
+        let months = match self {
+            Recurrence::Week => return date + Duration::weeks(1),
+            Recurrence::Month => 1,
+            Recurrence::Quarter => 3,
+            Recurrence::Year => 12,
+        };
+
+        date.checked_add_months(Months::new(months))
+            .expect("Dates close to now can always be moved a year ahead")
+    }
+}
+
+impl From<&str> for Recurrence {
+    fn from(value: &str) -> Self {
+        match value {
+            "week" => Recurrence::Week,
+            "quarter" => Recurrence::Quarter,
+            "year" => Recurrence::Year,
+            _ => Recurrence::Month,
+        }
+    }
+}
+
+impl From<Recurrence> for &str {
+    fn from(val: Recurrence) -> Self {
+        match val {
+            Recurrence::Week => "week",
+            Recurrence::Month => "month",
+            Recurrence::Quarter => "quarter",
+            Recurrence::Year => "year",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::models::entities::recurring_series::recurrence::Recurrence;
+
+    #[test]
+    fn interval_is_classified() {
+        assert_eq!(Recurrence::from_interval(7), Some(Recurrence::Week));
+        assert_eq!(Recurrence::from_interval(28), Some(Recurrence::Month));
+        assert_eq!(Recurrence::from_interval(92), Some(Recurrence::Quarter));
+        assert_eq!(Recurrence::from_interval(366), Some(Recurrence::Year));
+        assert_eq!(Recurrence::from_interval(14), None);
+    }
+
+    #[test]
+    fn next_date_follows_calendar() {
+        let date = Utc.with_ymd_and_hms(2023, 1, 31, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            Recurrence::Month.next(date),
+            Utc.with_ymd_and_hms(2023, 2, 28, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            Recurrence::Quarter.next(date),
+            Utc.with_ymd_and_hms(2023, 4, 30, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            Recurrence::Week.next(date),
+            Utc.with_ymd_and_hms(2023, 2, 7, 0, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use sqlx::{Executor, FromRow, Postgres};
+
+use crate::db_executor;
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// A user defined rule which is used to automatically organize transactions. A rule consists of a
+/// set of conditions and a set of actions. Every condition that is set has to match for the rule
+/// to apply, conditions that are [None] are ignored. When a rule applies, all the actions that are
+/// set are performed on the transaction.
+#[derive(Debug, FromRow)]
+pub struct Rule {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+
+    /// Rules are evaluated in ascending order of priority. When multiple rules perform the same
+    /// action, the rule that is evaluated first wins.
+    pub priority: i32,
+
+    /// Matches when the description contains the value. The check is case-insensitive.
+    pub description_contains: Option<String>,
+
+    /// Matches when the regex matches the description.
+    pub description_regex: Option<String>,
+
+    /// Matches when the amount (in cents) is bigger or equal to the value.
+    pub min_amount: Option<i64>,
+
+    /// Matches when the amount (in cents) is smaller or equal to the value.
+    pub max_amount: Option<i64>,
+
+    /// Matches when the IBAN of the other party of the transaction is equal to the value.
+    pub iban: Option<String>,
+
+    /// Matches when the transaction belongs to the given bank account.
+    pub bank_account_id: Option<String>,
+
+    /// Matches when the transaction took place on the given day of the week, where 0 is Monday and
+    /// 6 is Sunday.
+    pub weekday: Option<i32>,
+
+    /// Sets the category of the transaction. The subcategory is always set together with the
+    /// category, so a rule that sets a category without a subcategory clears the subcategory.
+    pub set_category_id: Option<String>,
+    pub set_subcategory_id: Option<String>,
+
+    /// Replaces the description of the transaction. The original description is left untouched.
+    pub set_description: Option<String>,
+
+    /// Links the transaction to the given external account.
+    pub set_external_account_id: Option<String>,
+}
+
+impl Rule {
+    pub async fn create<'d>(&self, executor: db_executor!('d)) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO rules
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15);
+            "#,
+            self.id,
+            self.user_id,
+            self.name,
+            self.priority,
+            self.description_contains,
+            self.description_regex,
+            self.min_amount,
+            self.max_amount,
+            self.iban,
+            self.bank_account_id,
+            self.weekday,
+            self.set_category_id,
+            self.set_subcategory_id,
+            self.set_description,
+            self.set_external_account_id,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM rules
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -29,4 +29,26 @@ impl Subcategory {
 
         Ok(())
     }
+
+    pub async fn guard_one(
+        pool: &DbPool,
+        id: &str,
+        parent_category: &str,
+        user_id: &str,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM subcategories
+                WHERE id = $1 AND parent_category = $2 AND user_id = $3;
+            "#,
+            id,
+            parent_category,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// A label that can be put on any number of transactions, regardless of their category.
+#[derive(Debug)]
+pub struct Tag {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub hex_color: String,
+}
+
+impl Tag {
+    pub async fn create(&self, pool: &DbPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO tags
+                VALUES ($1, $2, $3, $4);
+            "#,
+            self.id,
+            self.user_id,
+            self.name,
+            self.hex_color,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn guard_one(pool: &DbPool, id: &str, user_id: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT id
+                FROM tags
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Checks that all the tags exist and belong to the user.
+    pub async fn guard_all(pool: &DbPool, ids: &[String], user_id: &str) -> Result<()> {
+        let record = sqlx::query!(
+            r#"
+                SELECT COUNT(*) AS "count!"
+                FROM tags
+                WHERE id = ANY($1) AND user_id = $2;
+            "#,
+            ids,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let unique_ids: HashSet<&String> = ids.iter().collect();
+
+        if record.count as usize != unique_ids.len() {
+            return Err(sqlx::Error::RowNotFound.into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn find_one(pool: &DbPool, id: &str, user_id: &str) -> Result<Tag> {
+        let tags = Tag::fetch(pool, Some(id), user_id).await?;
+
+        let Some(tag) = tags.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(tag)
+    }
+
+    pub async fn find_all(pool: &DbPool, user_id: &str) -> Result<Vec<Tag>> {
+        Tag::fetch(pool, None, user_id).await
+    }
+
+    async fn fetch(pool: &DbPool, id: Option<&str>, user_id: &str) -> Result<Vec<Tag>> {
+        let tags = sqlx::query_as!(
+            Tag,
+            r#"
+                SELECT id, user_id, name, hex_color
+                FROM tags
+                WHERE user_id = $1 AND ($2::varchar IS NULL OR id = $2)
+                ORDER BY name;
+            "#,
+            user_id,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tags)
+    }
+}
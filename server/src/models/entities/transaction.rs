@@ -30,10 +30,10 @@ pub struct Transaction {
     /// The description of the transaction. This is used to tell transactions apart from each-other.
     pub description: String,
 
-    /// The actual amount of the complete transaction in euro cents, so 1,54 would be 156 in this
-    /// field. The difference with [amount] is that amount may be changed by creating a split, while
-    /// the complete amount should only be changed when there is an actual change to the transaction
-    /// itself.
+    /// The actual amount of the complete transaction in the minor unit of the [currency], so for
+    /// euro 1,54 would be 154 in this field. The difference with [amount] is that amount may be
+    /// changed by creating a split, while the complete amount should only be changed when there is
+    /// an actual change to the transaction itself.
     pub complete_amount: i64,
 
     /// The current amount of the transaction. This may be changed by creating a split.
@@ -64,6 +64,10 @@ pub struct Transaction {
     /// Strictly links a name entry to a transaction so it can be better managed.
     pub external_account_name_id: Option<String>,
 
+    /// The normalized IBAN of the other account in this transaction, if the import contained it.
+    /// Matched with the IBANs of external accounts before the [external_account_name] is.
+    pub external_account_iban: Option<String>,
+
     /// The id of the parent [Import]. Used to group transactions that were created in an import.
     pub parent_import_id: Option<String>,
 
@@ -79,6 +83,13 @@ pub struct Transaction {
     /// consists of two transactions that are created. Both point to each-other using the related
     /// move transaction.
     pub related_move_transaction: Option<String>,
+
+    /// The other leg of a transfer between two bank accounts of the user. Both legs are of the
+    /// [TransactionType::Transaction] type, but are left out of the income and expenses.
+    pub related_transfer_transaction: Option<String>,
+
+    /// The ISO 4217 code of the currency of the amounts.
+    pub currency: String,
 }
 
 impl Transaction {
@@ -88,7 +99,7 @@ impl Transaction {
         sqlx::query!(
             r#"
                 INSERT INTO transactions
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19);
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22);
             "#,
             self.id,
             self.user_id,
@@ -109,6 +120,9 @@ impl Transaction {
             self.order_indicator,
             self.external_account_name_id,
             self.related_move_transaction,
+            self.currency,
+            self.related_transfer_transaction,
+            self.external_account_iban,
         )
         .execute(executor)
         .await?;
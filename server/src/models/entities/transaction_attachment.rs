@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{Executor, Postgres};
+
+use crate::db_executor;
+use crate::prelude::*;
+use crate::shared::DbPool;
+use crate::utils::datetime::to_utc;
+
+/// A receipt or other document attached to a transaction or split. The file is stored as a blob,
+/// which is deleted together with the attachment.
+#[derive(Debug)]
+pub struct TransactionAttachment {
+    pub id: String,
+    pub user_id: String,
+    pub transaction_id: String,
+    pub blob_token: String,
+
+    /// The name of the file as it was on the device of the user.
+    pub filename: String,
+
+    /// Comes from the blob, so it's not stored with the attachment itself.
+    pub mime_type: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TransactionAttachment {
+    pub async fn create<'d>(&self, executor: db_executor!('d)) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO transaction_attachments
+                VALUES ($1, $2, $3, $4, $5, $6);
+            "#,
+            self.id,
+            self.user_id,
+            self.transaction_id,
+            self.blob_token,
+            self.filename,
+            OffsetDateTime::from_unix_timestamp(self.created_at.timestamp())?,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_one(
+        pool: &DbPool,
+        id: &str,
+        transaction_id: &str,
+        user_id: &str,
+    ) -> Result<TransactionAttachment> {
+        let attachments =
+            TransactionAttachment::fetch(pool, Some(id), transaction_id, user_id).await?;
+
+        let Some(attachment) = attachments.into_iter().next() else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(attachment)
+    }
+
+    pub async fn find_all(
+        pool: &DbPool,
+        transaction_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<TransactionAttachment>> {
+        TransactionAttachment::fetch(pool, None, transaction_id, user_id).await
+    }
+
+    async fn fetch(
+        pool: &DbPool,
+        id: Option<&str>,
+        transaction_id: &str,
+        user_id: &str,
+    ) -> Result<Vec<TransactionAttachment>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT a.*, b.mime_type
+                FROM transaction_attachments a
+                JOIN blobs b ON b.token = a.blob_token AND b.user_id = a.user_id
+                WHERE a.transaction_id = $1 AND a.user_id = $2
+                    AND ($3::varchar IS NULL OR a.id = $3)
+                ORDER BY a.created_at, a.filename;
+            "#,
+            transaction_id,
+            user_id,
+            id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| TransactionAttachment {
+                id: record.id,
+                user_id: record.user_id,
+                transaction_id: record.transaction_id,
+                blob_token: record.blob_token,
+                filename: record.filename,
+                mime_type: record.mime_type,
+                created_at: to_utc(record.created_at),
+            })
+            .collect())
+    }
+}
@@ -34,4 +34,20 @@ impl User {
 
         Ok(())
     }
+
+    /// Returns the currency the amounts of the user are converted to when they are added together.
+    pub async fn base_currency<'r>(pool: db_executor!('r), user_id: &str) -> Result<String> {
+        let record = sqlx::query!(
+            r#"
+                SELECT base_currency
+                FROM users
+                WHERE id = $1;
+            "#,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record.base_currency)
+    }
 }
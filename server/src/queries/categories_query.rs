@@ -21,18 +21,31 @@ impl<'a> CategoriesQuery<'a> {
                 SELECT
                     categories.id as category_id, categories.name as category_name, categories.description as category_description, categories.hex_color as category_hex_color,
                     s.id as subcategory_id, s.name as subcategory_name, s.description as subcategory_description, s.hex_color as subcategory_hex_color,
-                (
-                    SELECT SUM(amount)::bigint
-                    FROM transactions
-                    WHERE categories.id = transactions.category_id
-                ) AS amount,
-                (
-                    SELECT SUM(amount)::bigint
-                    FROM transactions
-                    WHERE categories.id = transactions.category_id AND s.Id = transactions.subcategory_id
-                ) AS subcategory_amount
+                    category_totals.amount, category_totals.missing_rates,
+                    subcategory_totals.amount AS subcategory_amount, subcategory_totals.missing_rates AS subcategory_missing_rates
                 FROM categories
-                lEFT JOIN subcategories s ON categories.id = s.parent_category
+                LEFT JOIN subcategories s ON categories.id = s.parent_category
+                CROSS JOIN LATERAL (
+                    SELECT
+                        SUM(converted)::bigint AS amount,
+                        COALESCE(ARRAY_AGG(DISTINCT currency) FILTER (WHERE converted IS NULL), ARRAY[]::varchar[]) AS missing_rates
+                    FROM (
+                        SELECT currency, convert_to_base(transactions.user_id, amount, currency, date) AS converted
+                        FROM transactions
+                        WHERE categories.id = transactions.category_id AND related_transfer_transaction IS NULL
+                    ) category_transactions
+                ) category_totals
+                CROSS JOIN LATERAL (
+                    SELECT
+                        SUM(converted)::bigint AS amount,
+                        COALESCE(ARRAY_AGG(DISTINCT currency) FILTER (WHERE converted IS NULL), ARRAY[]::varchar[]) AS missing_rates
+                    FROM (
+                        SELECT currency, convert_to_base(transactions.user_id, amount, currency, date) AS converted
+                        FROM transactions
+                        WHERE categories.id = transactions.category_id AND s.id = transactions.subcategory_id
+                            AND related_transfer_transaction IS NULL
+                    ) subcategory_transactions
+                ) subcategory_totals
                 WHERE categories.user_id =
             "#,
         );
@@ -84,6 +97,7 @@ impl<'a> CategoriesQuery<'a> {
                         description: record.category_description,
                         hex_color: record.category_hex_color,
                         amount: record.amount.unwrap_or(0),
+                        missing_rates: record.missing_rates,
                         subcategories: vec![],
                     },
                 );
@@ -106,6 +120,7 @@ impl<'a> CategoriesQuery<'a> {
                         .subcategory_hex_color
                         .expect("Subcategory id is set, but the hex color is not"),
                     amount: record.subcategory_amount.unwrap_or(0),
+                    missing_rates: record.subcategory_missing_rates,
                 });
             }
         }
@@ -4,6 +4,8 @@ use sqlx::FromRow;
 pub struct CategoryRecord {
     pub amount: Option<i64>,
     pub subcategory_amount: Option<i64>,
+    pub missing_rates: Vec<String>,
+    pub subcategory_missing_rates: Vec<String>,
     pub category_id: String,
     pub category_name: String,
     pub category_description: String,
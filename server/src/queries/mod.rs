@@ -1,2 +1,3 @@
 pub mod categories_query;
+pub mod series_query;
 pub mod transactions_query;
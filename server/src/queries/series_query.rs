@@ -0,0 +1,109 @@
+use rocket::time::format_description::well_known::Rfc3339;
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::models::dto::aggregates::series_bucket_dto::SeriesBucketDto;
+use crate::models::dto::aggregates::series_query_dto::{SeriesGroupBy, SeriesInterval};
+use crate::prelude::*;
+use crate::queries::series_query::series_record::SeriesRecord;
+use crate::shared::DbPool;
+
+mod series_record;
+
+/// Sums the income and expenses of the user per bucket of time. Only the `amount` of a transaction
+/// is used, so the parts of a transaction that have been split off are counted through their split
+/// instead of twice. Moves only shift money between categories, so they are only taken into
+/// account when grouping by category or subcategory where they would otherwise cancel out.
+/// Transfers between bank accounts of the user are neither income nor expense, so they are only
+/// taken into account when grouping by bank account.
+///
+/// Amounts are converted to the base currency of the user. Currencies without an exchange rate are
+/// left out of the totals and listed per bucket instead.
+pub struct SeriesQuery<'a> {
+    builder: QueryBuilder<'a, Postgres>,
+}
+
+impl<'a> SeriesQuery<'a> {
+    pub fn new(
+        user_id: impl Into<String>,
+        interval: SeriesInterval,
+        group_by: Option<SeriesGroupBy>,
+    ) -> Self {
+        let group_column = match group_by {
+            Some(SeriesGroupBy::Category) => "category_id",
+            Some(SeriesGroupBy::Subcategory) => "subcategory_id",
+            Some(SeriesGroupBy::BankAccount) => "bank_account_id",
+            Some(SeriesGroupBy::ExternalAccount) => "external_account_id",
+            None => "NULL::varchar",
+        };
+
+        let transaction_types = match group_by {
+            Some(SeriesGroupBy::Category) | Some(SeriesGroupBy::Subcategory) => {
+                "'transaction', 'split', 'correction', 'move'"
+            }
+            _ => "'transaction', 'split', 'correction'",
+        };
+
+        let transfer_filter = match group_by {
+            Some(SeriesGroupBy::BankAccount) => "",
+            _ => "AND related_transfer_transaction IS NULL",
+        };
+
+        let mut builder = QueryBuilder::new(format!(
+            r#"
+                SELECT
+                    date_trunc('{}', date, 'UTC') AS bucket,
+                    {} AS group_id,
+                    COALESCE(SUM(CASE WHEN amount > 0 THEN convert_to_base(user_id, amount, currency, date) ELSE 0 END), 0)::bigint AS income,
+                    COALESCE(SUM(CASE WHEN amount < 0 THEN -convert_to_base(user_id, amount, currency, date) ELSE 0 END), 0)::bigint AS expense,
+                    COALESCE(
+                        ARRAY_AGG(DISTINCT currency) FILTER (
+                            WHERE convert_to_base(user_id, amount, currency, date) IS NULL
+                        ),
+                        ARRAY[]::varchar[]
+                    ) AS missing_rates
+                FROM transactions
+                WHERE transaction_type IN ({}) {} AND user_id =
+            "#,
+            <&str>::from(interval),
+            group_column,
+            transaction_types,
+            transfer_filter,
+        ));
+
+        builder.push_bind(user_id.into());
+
+        Self { builder }
+    }
+
+    pub fn where_date_from(mut self, from: OffsetDateTime) -> Self {
+        self.builder.push(" AND date >= ");
+        self.builder.push_bind(from);
+        self
+    }
+
+    pub fn where_date_to(mut self, to: OffsetDateTime) -> Self {
+        self.builder.push(" AND date < ");
+        self.builder.push_bind(to);
+        self
+    }
+
+    pub async fn fetch_all(mut self, pool: &DbPool) -> Result<Vec<SeriesBucketDto>> {
+        self.builder
+            .push(" GROUP BY bucket, group_id ORDER BY bucket, group_id NULLS FIRST ");
+
+        let records: Vec<SeriesRecord> = self.builder.build_query_as().fetch_all(pool).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| SeriesBucketDto {
+                bucket: record.bucket.format(&Rfc3339).expect("Incorrect formatting"),
+                group_id: record.group_id,
+                income: record.income,
+                expense: record.expense,
+                net: record.income - record.expense,
+                missing_rates: record.missing_rates,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,11 @@
+use sqlx::types::time::OffsetDateTime;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub struct SeriesRecord {
+    pub bucket: OffsetDateTime,
+    pub group_id: Option<String>,
+    pub income: i64,
+    pub expense: i64,
+    pub missing_rates: Vec<String>,
+}
@@ -1,21 +1,30 @@
 use rocket::time::format_description::well_known::Rfc3339;
-use sqlx::{Postgres, QueryBuilder};
+use sqlx::types::time::OffsetDateTime;
+use sqlx::{Executor, Postgres, QueryBuilder};
 
+use crate::db_executor;
 use crate::models::dto::bank_accounts::slim_bank_account_dto::SlimBankAccountDto;
 use crate::models::dto::categories::slim_category_dto::SlimCategoryDto;
 use crate::models::dto::categories::subcategories::slim_subcategory_dto::SlimSubcategoryDto;
 use crate::models::dto::external_accounts::external_account_dto::ExternalAccountDto;
+use crate::models::dto::pagination::cursor::Cursor;
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
 use crate::models::dto::transactions::transaction_dto::TransactionDto;
 use crate::models::entities::transaction::transaction_type::TransactionType;
 use crate::prelude::*;
 use crate::queries::transactions_query::transaction_record::TransactionRecord;
 use crate::shared::DbPool;
+use crate::utils::datetime::parse_rfc3339;
 
 mod transaction_record;
 
 pub struct TransactionQuery<'a> {
     builder: QueryBuilder<'a, Postgres>,
+
+    // The ordering and pagination are only added to the query when it is fetched, as a cursor
+    // adds a condition that has to come before the ordering.
+    ordered: bool,
+    pagination: Option<(Option<String>, i64, i64)>,
 }
 
 impl<'a> TransactionQuery<'a> {
@@ -23,11 +32,22 @@ impl<'a> TransactionQuery<'a> {
         let mut builder = QueryBuilder::new(
             r#"
                 SELECT
-                    transactions.id as transaction_id, transaction_type, follow_number, original_description, transactions.description, date, complete_amount, amount, external_account_name, related_move_transaction,
+                    transactions.id as transaction_id, transaction_type, follow_number, original_description, transactions.description, date, order_indicator, complete_amount, amount, transactions.currency, external_account_name, external_account_iban, related_move_transaction, related_transfer_transaction,
                     c.Id as "category_id?", c.name as "category_name?", c.description as "category_description?", c.hex_color as "category_hex_color?",
                     s.Id as "subcategory_id?", s.name as "subcategory_name?", s.description as "subcategory_description?", s.hex_color as "subcategory_hex_color?",
-                    b.Id as "bank_account_id?", b.iban as "bank_account_iban?", b.name as "bank_account_name?", b.description as "bank_account_description?", b.hex_color as "bank_account_hex_color?",
-                    e.Id as "external_account_id?", e.name as "external_account_entity_name?", e.description as "external_account_description?", e.hex_color as "external_account_hex_color?", e.default_category_id as "external_account_default_category_id?", e.default_subcategory_id as "external_account_default_subcategory_id?"
+                    b.Id as "bank_account_id?", b.iban as "bank_account_iban?", b.name as "bank_account_name?", b.description as "bank_account_description?", b.hex_color as "bank_account_hex_color?", b.currency as "bank_account_currency?",
+                    e.Id as "external_account_id?", e.name as "external_account_entity_name?", e.description as "external_account_description?", e.hex_color as "external_account_hex_color?", e.default_category_id as "external_account_default_category_id?", e.default_subcategory_id as "external_account_default_subcategory_id?",
+                    EXISTS(
+                        SELECT r.id
+                        FROM reconciliations r
+                        WHERE r.user_id = transactions.user_id AND r.bank_account_id = transactions.bank_account_id AND r.date >= transactions.date
+                    ) as locked,
+                    ARRAY(
+                        SELECT tt.tag_id
+                        FROM transaction_tags tt
+                        WHERE tt.transaction_id = transactions.id AND tt.user_id = transactions.user_id
+                        ORDER BY tt.tag_id
+                    ) as tag_ids
                 FROM transactions
                 LEFT JOIN categories c on transactions.category_id = c.id
                 LEFT JOIN subcategories s on transactions.subcategory_id = s.id
@@ -39,7 +59,11 @@ impl<'a> TransactionQuery<'a> {
 
         builder.push_bind(user_id.into());
 
-        Self { builder }
+        Self {
+            builder,
+            ordered: false,
+            pagination: None,
+        }
     }
 
     pub fn where_id(mut self, transaction_id: impl Into<String>) -> Self {
@@ -60,6 +84,15 @@ impl<'a> TransactionQuery<'a> {
         self
     }
 
+    pub fn where_tag(mut self, tag_id: impl Into<String>) -> Self {
+        self.builder.push(
+            " AND EXISTS(SELECT tt.tag_id FROM transaction_tags tt WHERE tt.transaction_id = transactions.id AND tt.user_id = transactions.user_id AND tt.tag_id = ",
+        );
+        self.builder.push_bind(tag_id.into());
+        self.builder.push(")");
+        self
+    }
+
     pub fn where_type(mut self, transaction_type: TransactionType) -> Self {
         self.builder.push(" AND transaction_type = ");
         self.builder.push_bind::<&str>(transaction_type.into());
@@ -84,28 +117,134 @@ impl<'a> TransactionQuery<'a> {
         self
     }
 
+    pub fn where_date_from(mut self, from: OffsetDateTime) -> Self {
+        self.builder.push(" AND date >= ");
+        self.builder.push_bind(from);
+        self
+    }
+
+    pub fn where_date_to(mut self, to: OffsetDateTime) -> Self {
+        self.builder.push(" AND date < ");
+        self.builder.push_bind(to);
+        self
+    }
+
+    pub fn where_uncategorized(mut self) -> Self {
+        self.builder.push(" AND transactions.category_id IS NULL ");
+        self
+    }
+
+    pub fn where_import(mut self, import_id: impl Into<String>) -> Self {
+        self.builder.push(" AND transactions.parent_import = ");
+        self.builder.push_bind(import_id.into());
+        self
+    }
+
+    pub fn where_amount_from(mut self, min: i64) -> Self {
+        self.builder.push(" AND transactions.amount >= ");
+        self.builder.push_bind(min);
+        self
+    }
+
+    pub fn where_amount_to(mut self, max: i64) -> Self {
+        self.builder.push(" AND transactions.amount <= ");
+        self.builder.push_bind(max);
+        self
+    }
+
+    /// Only keeps the transactions where every word of the text occurs in the description, the
+    /// original description or the name of the external account, ignoring case. The columns have
+    /// trigram indexes, so this doesn't need to scan every transaction.
+    pub fn where_text(mut self, text: &str) -> Self {
+        for word in text.split_whitespace() {
+            let pattern = format!("%{}%", escape_like(word));
+
+            self.builder.push(" AND (transactions.description ILIKE ");
+            self.builder.push_bind(pattern.to_string());
+            self.builder.push(" OR transactions.original_description ILIKE ");
+            self.builder.push_bind(pattern.to_string());
+            self.builder.push(" OR transactions.external_account_name ILIKE ");
+            self.builder.push_bind(pattern);
+            self.builder.push(")");
+        }
+
+        self
+    }
+
+    /// Limits the results to a single page. With a cursor, the page starts right after the
+    /// transaction the cursor points to, so it stays correct when new transactions are added.
     pub fn paginate(mut self, pagination: &PaginationQueryDto) -> Self {
-        self.builder.push(" OFFSET ");
-        self.builder.push_bind(pagination.get_offset());
-        self.builder.push(" LIMIT ");
-        self.builder.push_bind(pagination.get_limit());
+        self.pagination = Some((
+            pagination.cursor.clone(),
+            pagination.get_offset(),
+            pagination.get_limit(),
+        ));
         self
     }
 
+    /// Orders the transactions from newest to oldest. The id orders transactions that are on the
+    /// same date and have the same order indicator, so cursors always point at a unique position.
     pub fn order(mut self) -> Self {
-        self.builder
-            .push(" ORDER BY date DESC, order_indicator DESC ");
+        self.ordered = true;
         self
     }
 
+    fn finish(&mut self) -> Result<()> {
+        let Some((cursor, offset, limit)) = self.pagination.take() else {
+            if self.ordered {
+                self.push_order();
+            }
+
+            return Ok(());
+        };
+
+        if let Some(cursor) = cursor {
+            let cursor = Cursor::decode(&cursor)?;
+            let date = parse_rfc3339(&cursor.date)?;
+
+            self.builder
+                .push(" AND ((transactions.date, transactions.order_indicator) < (");
+            self.builder.push_bind(date);
+            self.builder.push(", ");
+            self.builder.push_bind(cursor.order_indicator);
+            self.builder.push(") OR (transactions.date = ");
+            self.builder.push_bind(date);
+            self.builder.push(" AND transactions.order_indicator = ");
+            self.builder.push_bind(cursor.order_indicator);
+            self.builder.push(" AND transactions.id > ");
+            self.builder.push_bind(cursor.id);
+            self.builder.push(")) ");
+        }
+
+        if self.ordered {
+            self.push_order();
+        }
+
+        self.builder.push(" OFFSET ");
+        self.builder.push_bind(offset);
+        self.builder.push(" LIMIT ");
+        self.builder.push_bind(limit);
+
+        Ok(())
+    }
+
+    fn push_order(&mut self) {
+        self.builder
+            .push(" ORDER BY date DESC, order_indicator DESC, transactions.id ");
+    }
+
     pub async fn fetch_one(mut self, pool: &DbPool) -> Result<TransactionDto> {
+        self.finish()?;
+
         let record = self.builder.build_query_as().fetch_one(pool).await?;
 
         Ok(TransactionQuery::map_record(record))
     }
 
-    pub async fn fetch_all(mut self, pool: &DbPool) -> Result<Vec<TransactionDto>> {
-        let records = self.builder.build_query_as().fetch_all(pool).await?;
+    pub async fn fetch_all<'d>(mut self, executor: db_executor!('d)) -> Result<Vec<TransactionDto>> {
+        self.finish()?;
+
+        let records = self.builder.build_query_as().fetch_all(executor).await?;
 
         let transactions = records
             .into_iter()
@@ -124,13 +263,20 @@ impl<'a> TransactionQuery<'a> {
             description: record.description,
             complete_amount: record.complete_amount,
             amount: record.amount,
+            currency: record.currency,
             date: record.date.format(&Rfc3339).expect("Incorrect formatting"),
+            order_indicator: record.order_indicator,
             bank_account: None,
             category: None,
             subcategory: None,
             external_account_name: record.external_account_name,
+            external_account_iban: record.external_account_iban,
             external_account: None,
             related_move_transaction: record.related_move_transaction,
+            related_transfer_transaction: record.related_transfer_transaction,
+            running_balance: None,
+            locked: record.locked,
+            tag_ids: record.tag_ids,
         };
 
         if let Some(id) = record.bank_account_id {
@@ -148,6 +294,9 @@ impl<'a> TransactionQuery<'a> {
                 hex_color: record
                     .bank_account_hex_color
                     .expect("Bank account id was not null, but bank account hex color was"),
+                currency: record
+                    .bank_account_currency
+                    .expect("Bank account id was not null, but bank account currency was"),
             });
         }
 
@@ -201,3 +350,30 @@ impl<'a> TransactionQuery<'a> {
         transaction
     }
 }
+
+/// Escapes the characters that have a special meaning in a LIKE pattern, so they are matched
+/// literally.
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        if matches!(char, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+
+        escaped.push(char);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::queries::transactions_query::escape_like;
+
+    #[test]
+    fn like_wildcards_are_escaped() {
+        assert_eq!(escape_like("100%_sure\\"), "100\\%\\_sure\\\\");
+        assert_eq!(escape_like("Jumbo"), "Jumbo");
+    }
+}
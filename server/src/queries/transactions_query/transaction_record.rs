@@ -10,8 +10,13 @@ pub struct TransactionRecord {
     pub description: String,
     pub complete_amount: i64,
     pub amount: i64,
+    pub currency: String,
     pub date: OffsetDateTime,
+    pub order_indicator: i32,
     pub related_move_transaction: Option<String>,
+    pub related_transfer_transaction: Option<String>,
+    pub locked: bool,
+    pub tag_ids: Vec<String>,
 
     #[sqlx(rename = "bank_account_id?")]
     pub bank_account_id: Option<String>,
@@ -28,8 +33,12 @@ pub struct TransactionRecord {
     #[sqlx(rename = "bank_account_hex_color?")]
     pub bank_account_hex_color: Option<String>,
 
+    #[sqlx(rename = "bank_account_currency?")]
+    pub bank_account_currency: Option<String>,
+
     #[sqlx(rename = "external_account_name")]
     pub external_account_name: String,
+    pub external_account_iban: Option<String>,
 
     #[sqlx(rename = "category_id?")]
     pub category_id: Option<String>,
@@ -2,15 +2,24 @@ use rocket::serde::json::Json;
 use rocket::Route;
 
 use crate::db_inner;
+use crate::models::dto::aggregates::series_bucket_dto::SeriesBucketDto;
+use crate::models::dto::aggregates::series_query_dto::SeriesQueryDto;
+use crate::models::dto::aggregates::tag_total_dto::TagTotalDto;
 use crate::models::dto::aggregates::user_total_dto::UserTotalDto;
+use crate::models::entities::user::User;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
+use crate::queries::series_query::SeriesQuery;
 use crate::shared::SharedPool;
+use crate::utils::datetime::parse_rfc3339;
 
 pub fn create_aggregate_routes() -> Vec<Route> {
-    routes![get_user_total_amount,]
+    routes![get_user_total_amount, get_series, get_tag_totals,]
 }
 
+/// Returns the total of all transactions of the user in the minor unit of the base currency.
+/// Transactions in other currencies are converted with the exchange rate of their date. Currencies
+/// without any exchange rate are left out of the total and listed in `missingRates`.
 #[get("/total")]
 pub async fn get_user_total_amount(
     pool: &SharedPool,
@@ -20,7 +29,14 @@ pub async fn get_user_total_amount(
 
     let record = sqlx::query!(
         r#"
-            SELECT SUM(complete_amount)::bigint AS total
+            SELECT
+                SUM(convert_to_base(user_id, complete_amount, currency, date))::bigint AS total,
+                COALESCE(
+                    ARRAY_AGG(DISTINCT currency) FILTER (
+                        WHERE convert_to_base(user_id, complete_amount, currency, date) IS NULL
+                    ),
+                    ARRAY[]::varchar[]
+                ) AS "missing_rates!"
             FROM transactions
             WHERE transaction_type = 'transaction' AND user_id = $1
         "#,
@@ -31,5 +47,96 @@ pub async fn get_user_total_amount(
 
     Ok(Json(UserTotalDto {
         total: record.total.unwrap_or(0),
+        currency: User::base_currency(pool, &user.uuid).await?,
+        missing_rates: record.missing_rates,
     }))
 }
+
+/// Returns the income, expense and net amount per period between the given dates. The `to` date is
+/// exclusive.
+#[get("/series?<query..>")]
+pub async fn get_series(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    query: SeriesQueryDto,
+) -> Result<Json<Vec<SeriesBucketDto>>> {
+    let pool = db_inner!(pool);
+
+    let mut series_query = SeriesQuery::new(&user.uuid, query.interval, query.group_by);
+
+    if let Some(from) = &query.from {
+        series_query = series_query.where_date_from(parse_rfc3339(from)?);
+    }
+
+    if let Some(to) = &query.to {
+        series_query = series_query.where_date_to(parse_rfc3339(to)?);
+    }
+
+    debug!("Querying series for user '{}'", user);
+    let buckets = series_query.fetch_all(pool).await?;
+
+    Ok(Json(buckets))
+}
+
+/// Returns the income, expense and net amount per tag between the given dates. The `to` date is
+/// exclusive. A transaction with several tags counts towards each of them. Currencies without any
+/// exchange rate are left out of the totals and listed in `missingRates`.
+#[get("/tags?<from>&<to>")]
+pub async fn get_tag_totals(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Json<Vec<TagTotalDto>>> {
+    let pool = db_inner!(pool);
+
+    let from = from.as_deref().map(parse_rfc3339).transpose()?;
+    let to = to.as_deref().map(parse_rfc3339).transpose()?;
+
+    debug!("Querying tag totals for user '{}'", user);
+    let records = sqlx::query!(
+        r#"
+            SELECT
+                tags.id,
+                tags.name,
+                COALESCE(SUM(CASE WHEN t.amount > 0 THEN convert_to_base(t.user_id, t.amount, t.currency, t.date) ELSE 0 END), 0)::bigint AS "income!",
+                COALESCE(SUM(CASE WHEN t.amount < 0 THEN -convert_to_base(t.user_id, t.amount, t.currency, t.date) ELSE 0 END), 0)::bigint AS "expense!",
+                COALESCE(
+                    ARRAY_AGG(DISTINCT t.currency) FILTER (
+                        WHERE t.id IS NOT NULL
+                            AND convert_to_base(t.user_id, t.amount, t.currency, t.date) IS NULL
+                    ),
+                    ARRAY[]::varchar[]
+                ) AS "missing_rates!"
+            FROM tags
+            LEFT JOIN transaction_tags tt ON tt.tag_id = tags.id AND tt.user_id = tags.user_id
+            LEFT JOIN transactions t ON t.id = tt.transaction_id AND t.user_id = tt.user_id
+                AND t.transaction_type IN ('transaction', 'split', 'correction')
+                AND t.related_transfer_transaction IS NULL
+                AND ($2::timestamptz IS NULL OR t.date >= $2)
+                AND ($3::timestamptz IS NULL OR t.date < $3)
+            WHERE tags.user_id = $1
+            GROUP BY tags.id, tags.name
+            ORDER BY tags.name;
+        "#,
+        user.uuid,
+        from,
+        to,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Json(
+        records
+            .into_iter()
+            .map(|record| TagTotalDto {
+                tag_id: record.id,
+                name: record.name,
+                income: record.income,
+                expense: record.expense,
+                net: record.income - record.expense,
+                missing_rates: record.missing_rates,
+            })
+            .collect(),
+    ))
+}
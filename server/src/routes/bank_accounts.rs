@@ -1,19 +1,31 @@
 use rocket::serde::json::Json;
+use rocket::time::format_description::well_known::Rfc3339;
 use rocket::Route;
+use sqlx::types::time::OffsetDateTime;
 use sqlx::Error::Database;
 
 use crate::db_inner;
 use crate::error::http_error::HttpError;
+use crate::models::dto::aggregates::series_query_dto::SeriesInterval;
+use crate::models::dto::bank_accounts::balance_dto::BalanceDto;
+use crate::models::dto::bank_accounts::balance_history_query_dto::BalanceHistoryQueryDto;
 use crate::models::dto::bank_accounts::bank_account_dto::BankAccountDto;
 use crate::models::dto::bank_accounts::update_bank_account_dto::UpdateBankAccountDto;
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
 use crate::models::dto::pagination::pagination_response_dto::PaginationResponseDto;
 use crate::models::dto::transactions::transaction_dto::TransactionDto;
+use crate::models::entities::bank_account::BankAccount;
 use crate::models::entities::transaction::transaction_type::TransactionType;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
 use crate::queries::transactions_query::TransactionQuery;
+use crate::services::balance_service::BalanceService;
 use crate::shared::SharedPool;
+use crate::utils::currency::parse_currency;
+use crate::utils::datetime::parse_rfc3339;
+
+/// The most periods a balance history can consist of.
+const MAX_HISTORY_PERIODS: i64 = 1000;
 
 pub fn create_bank_account_routes() -> Vec<Route> {
     routes![
@@ -22,6 +34,8 @@ pub fn create_bank_account_routes() -> Vec<Route> {
         update_bank_account,
         delete_bank_account,
         get_transactions_for_bank_account,
+        get_bank_account_balance,
+        get_bank_account_balance_history,
     ]
 }
 
@@ -57,6 +71,7 @@ pub async fn get_bank_accounts(
             name: record.name,
             description: record.description,
             hex_color: record.hex_color,
+            currency: record.currency,
             amount: record.amount.unwrap_or(0),
         })
         .collect();
@@ -96,6 +111,7 @@ pub async fn get_bank_account_by_id(
         name: record.name,
         description: record.description,
         hex_color: record.hex_color,
+        currency: record.currency,
         amount: record.amount.unwrap_or(0),
     }))
 }
@@ -110,18 +126,21 @@ pub async fn update_bank_account(
     let inner_pool = db_inner!(pool);
     let body = body.0;
 
+    let currency = body.currency.as_deref().map(parse_currency).transpose()?;
+
     debug!("Updating bank account '{}'", id);
     sqlx::query!(
         r#"
             UPDATE bank_accounts
-            SET name = $3, description = $4, hex_color = $5
+            SET name = $3, description = $4, hex_color = $5, currency = COALESCE($6, currency)
             WHERE id = $1 AND user_id = $2;
         "#,
         id,
         user.uuid,
         body.name,
         body.description,
-        body.hex_color
+        body.hex_color,
+        currency
     )
     .execute(inner_pool)
     .await?;
@@ -177,25 +196,117 @@ pub async fn delete_bank_account(
     Ok(())
 }
 
-#[get("/<id>/transactions?<pagination..>")]
+/// Returns the transactions of the bank account. When `balance` is set, every transaction also
+/// includes the balance of the bank account directly after it.
+#[get("/<id>/transactions?<balance>&<pagination..>")]
 pub async fn get_transactions_for_bank_account(
     pool: &SharedPool,
     user: JwtUserPayload,
     id: String,
     pagination: PaginationQueryDto,
+    balance: bool,
 ) -> Result<Json<PaginationResponseDto<TransactionDto>>> {
     let inner_pool = db_inner!(pool);
 
-    let transactions = TransactionQuery::new(user.uuid)
+    let mut transactions = TransactionQuery::new(&user.uuid)
         .where_type_not(TransactionType::Split)
-        .where_bank_account(id)
+        .where_bank_account(&id)
         .order()
         .paginate(&pagination)
         .fetch_all(inner_pool)
         .await?;
 
+    if balance {
+        let ids: Vec<String> = transactions
+            .iter()
+            .map(|transaction| transaction.id.to_string())
+            .collect();
+
+        let running_balances =
+            BalanceService::running_balances(inner_pool, &user.uuid, &id, &ids).await?;
+
+        for transaction in &mut transactions {
+            transaction.running_balance = running_balances.get(&transaction.id).copied();
+        }
+    }
+
     Ok(Json(PaginationResponseDto::from_query(
         pagination,
         transactions,
     )))
 }
+
+/// Returns the balance of the bank account at the given date, including the transactions on that
+/// exact moment. When no date is given, the current balance is returned.
+#[get("/<id>/balance?<date>")]
+pub async fn get_bank_account_balance(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    date: Option<&str>,
+) -> Result<Json<BalanceDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing bank account guard for id '{}' with user '{}'", id, user);
+    BankAccount::guard_one(pool, id, &user.uuid).await?;
+
+    let at = match date {
+        Some(date) => parse_rfc3339(date)?,
+        None => OffsetDateTime::now_utc(),
+    };
+
+    let balance = BalanceService::balance_at(pool, &user.uuid, id, at).await?;
+
+    Ok(Json(BalanceDto {
+        date: at.format(&Rfc3339).expect("Incorrect formatting"),
+        balance,
+    }))
+}
+
+/// Returns the balance of the bank account at the end of every period between the given dates.
+/// The `to` date is exclusive.
+#[get("/<id>/balance-history?<query..>")]
+pub async fn get_bank_account_balance_history(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    query: BalanceHistoryQueryDto,
+) -> Result<Json<Vec<BalanceDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing bank account guard for id '{}' with user '{}'", id, user);
+    BankAccount::guard_one(pool, id, &user.uuid).await?;
+
+    let from = parse_rfc3339(&query.from)?;
+    let to = parse_rfc3339(&query.to)?;
+
+    if from >= to {
+        return Err(HttpError::new(400)
+            .message("The 'from' date should be before the 'to' date")
+            .into());
+    }
+
+    if period_count(from, to, query.interval) > MAX_HISTORY_PERIODS {
+        return Err(HttpError::new(400)
+            .message(format!("The history can be at most {} periods long", MAX_HISTORY_PERIODS))
+            .into());
+    }
+
+    let history =
+        BalanceService::history(pool, &user.uuid, id, from, to, query.interval).await?;
+
+    Ok(Json(history))
+}
+
+/// Returns the number of periods between the given dates, counting the periods both dates fall in.
+/// As the `to` date is exclusive, this can be one more than the periods in the history.
+fn period_count(from: OffsetDateTime, to: OffsetDateTime, interval: SeriesInterval) -> i64 {
+    let years = (to.year() - from.year()) as i64;
+
+    match interval {
+        SeriesInterval::Day => (to - from).whole_days() + 1,
+        SeriesInterval::Week => (to - from).whole_weeks() + 1,
+        SeriesInterval::Month => years * 12 + to.month() as i64 - from.month() as i64 + 1,
+        SeriesInterval::Year => years + 1,
+    }
+}
@@ -3,6 +3,7 @@ use rocket::serde::json::Json;
 use rocket::{Data, Route};
 
 use crate::db_inner;
+use crate::models::dto::blobs::blob_content::BlobContent;
 use crate::models::dto::blobs::blob_token_dto::BlobTokenDto;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
@@ -10,7 +11,7 @@ use crate::prelude::*;
 use crate::shared::{SharedBlobService, SharedPool};
 
 pub fn create_blob_routes() -> Vec<Route> {
-    routes![upload_blob,]
+    routes![upload_blob, get_blob,]
 }
 
 #[post("/upload", data = "<stream>")]
@@ -31,3 +32,23 @@ pub async fn upload_blob(
 
     Ok(Json(BlobTokenDto { token }))
 }
+
+/// Streams a confirmed blob of the user. Only the mime types that are allowed where the blob is
+/// used are returned, and blobs that are not used anywhere cannot be downloaded.
+#[get("/<token>")]
+pub async fn get_blob(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    blob_service: &SharedBlobService,
+    token: &str,
+) -> Result<BlobContent> {
+    let inner_pool = db_inner!(pool);
+    let blob_service = blob_service.read().await;
+
+    debug!("Finding use of blob '{}' for user '{}'", token, user);
+    let blob_use = blob_service.find_use(&user.uuid, inner_pool, token).await?;
+
+    blob_service
+        .get_blob_with_mimetype(token, blob_use.mimetypes())
+        .await
+}
@@ -0,0 +1,218 @@
+use chrono::{DateTime, Utc};
+use rocket::serde::json::Json;
+use rocket::Route;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::models::dto::budgets::budget_dto::BudgetDto;
+use crate::models::dto::budgets::budget_overview_dto::BudgetOverviewDto;
+use crate::models::dto::budgets::new_budget_dto::NewBudgetDto;
+use crate::models::entities::budget::Budget;
+use crate::models::entities::category::Category;
+use crate::models::entities::subcategory::Subcategory;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::budget_service::BudgetService;
+use crate::shared::{DbPool, SharedPool};
+use crate::utils::datetime::{parse_rfc3339, to_utc};
+
+pub fn create_budget_routes() -> Vec<Route> {
+    routes![
+        get_all_budgets,
+        create_new_budget,
+        get_budgets_overview,
+        get_budget_by_id,
+        get_budget_overview,
+        update_budget,
+        delete_budget,
+    ]
+}
+
+#[get("/")]
+pub async fn get_all_budgets(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<BudgetDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all budgets for user '{}'", user);
+    let budgets = Budget::find_all(pool, &user.uuid).await?;
+
+    Ok(Json(budgets.into_iter().map(BudgetDto::from).collect()))
+}
+
+#[post("/", data = "<body>")]
+pub async fn create_new_budget(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<NewBudgetDto<'_>>,
+) -> Result<Json<BudgetDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    guard_budget_categories(inner_pool, &user, &body).await?;
+
+    let budget = Budget {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        category_id: body.category_id.to_string(),
+        subcategory_id: body.subcategory_id.map(|v| v.to_string()),
+        amount: body.amount as i64,
+        period: body.period,
+        rollover: body.rollover,
+        starts_at: parse_starts_at(&body)?,
+    };
+
+    debug!("Creating new budget for user '{}'", user);
+    budget.create(inner_pool).await?;
+
+    debug!("Created budget '{}'", budget.id);
+    get_budget_by_id(pool, user, &budget.id).await
+}
+
+/// Returns the state of all the budgets of the user for the period the given date falls in. When
+/// no date is given, the current period is used.
+#[get("/overview?<date>")]
+pub async fn get_budgets_overview(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    date: Option<&str>,
+) -> Result<Json<Vec<BudgetOverviewDto>>> {
+    let pool = db_inner!(pool);
+    let at = parse_overview_date(date)?;
+
+    debug!("Querying all budgets for user '{}'", user);
+    let budgets = Budget::find_all(pool, &user.uuid).await?;
+
+    Ok(Json(BudgetService::overviews(pool, &user.uuid, &budgets, at).await?))
+}
+
+#[get("/<id>")]
+pub async fn get_budget_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<BudgetDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying budget with id '{}'", id);
+    let budget = Budget::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(budget.into()))
+}
+
+#[get("/<id>/overview?<date>")]
+pub async fn get_budget_overview(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    date: Option<&str>,
+) -> Result<Json<BudgetOverviewDto>> {
+    let pool = db_inner!(pool);
+    let at = parse_overview_date(date)?;
+
+    debug!("Querying budget with id '{}'", id);
+    let budget = Budget::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(BudgetService::overview(pool, &budget, at).await?))
+}
+
+#[put("/<id>", data = "<body>")]
+pub async fn update_budget(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    body: Json<NewBudgetDto<'_>>,
+) -> Result<Json<BudgetDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    debug!("Executing budget guard for id '{}' with user '{}'", id, user);
+    Budget::guard_one(inner_pool, id, &user.uuid).await?;
+
+    guard_budget_categories(inner_pool, &user, &body).await?;
+
+    debug!("Updating budget with id '{}'", id);
+    sqlx::query!(
+        r#"
+            UPDATE budgets
+            SET category_id = $3, subcategory_id = $4, amount = $5, period = $6, rollover = $7, starts_at = $8
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid,
+        body.category_id,
+        body.subcategory_id,
+        body.amount as i64,
+        <&str>::from(body.period),
+        body.rollover,
+        OffsetDateTime::from_unix_timestamp(parse_starts_at(&body)?.timestamp())?,
+    )
+    .execute(inner_pool)
+    .await?;
+
+    debug!("Updated budget '{}'", id);
+    get_budget_by_id(pool, user, id).await
+}
+
+#[delete("/<id>")]
+pub async fn delete_budget(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing budget guard for id '{}' with user '{}'", id, user);
+    Budget::guard_one(pool, id, &user.uuid).await?;
+
+    debug!("Deleting budget with id '{}'", id);
+    sqlx::query!(
+        r#"
+            DELETE FROM budgets
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn guard_budget_categories(
+    pool: &DbPool,
+    user: &JwtUserPayload,
+    body: &NewBudgetDto<'_>,
+) -> Result<()> {
+    debug!("Executing category guard for id '{}' with user '{}'", body.category_id, user);
+    Category::guard_one(pool, body.category_id, &user.uuid).await?;
+
+    if let Some(subcategory_id) = body.subcategory_id {
+        debug!("Executing subcategory guard for id '{}' with user '{}'", subcategory_id, user);
+        Subcategory::guard_one(pool, subcategory_id, body.category_id, &user.uuid).await?;
+    }
+
+    Ok(())
+}
+
+/// Budgets start at the beginning of their first period, so the start is moved back to the start
+/// of the period it falls in.
+fn parse_starts_at(body: &NewBudgetDto) -> Result<DateTime<Utc>> {
+    let starts_at = match body.starts_at {
+        Some(starts_at) => to_utc(parse_rfc3339(starts_at)?),
+        None => Utc::now(),
+    };
+
+    Ok(body.period.start_of(starts_at))
+}
+
+fn parse_overview_date(date: Option<&str>) -> Result<DateTime<Utc>> {
+    let Some(date) = date else {
+        return Ok(Utc::now());
+    };
+
+    Ok(to_utc(parse_rfc3339(date)?))
+}
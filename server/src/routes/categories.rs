@@ -13,6 +13,7 @@ use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
 use crate::queries::categories_query::CategoriesQuery;
 use crate::queries::transactions_query::TransactionQuery;
+use crate::routes::categories::merging::{merge_category, merge_subcategory};
 use crate::routes::categories::moving::{delete_move, move_money_between_categories};
 use crate::routes::categories::ordering::category_ordering;
 use crate::routes::categories::subcategories::{
@@ -24,6 +25,7 @@ use crate::shared::SharedPool;
 pub mod ordering;
 pub mod subcategories;
 pub mod moving;
+pub mod merging;
 
 pub fn create_category_routes() -> Vec<Route> {
     routes![
@@ -42,6 +44,8 @@ pub fn create_category_routes() -> Vec<Route> {
         category_ordering,
         move_money_between_categories,
         delete_move,
+        merge_category,
+        merge_subcategory,
     ]
 }
 
@@ -0,0 +1,81 @@
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::entities::category::Category;
+use crate::models::entities::subcategory::Subcategory;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::merge_service::MergeService;
+use crate::shared::SharedPool;
+
+#[post("/<id>/merge-into/<target>")]
+pub async fn merge_category(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    target: &str,
+) -> Result<()> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Executing category guard for '{}' and '{}' with user '{}'", id, target, user);
+    Category::guard_one(inner_pool, id, &user.uuid).await?;
+    Category::guard_one(inner_pool, target, &user.uuid).await?;
+
+    let mut db_transaction = inner_pool.begin().await?;
+
+    MergeService::merge_categories(&mut db_transaction, &user.uuid, id, target).await?;
+
+    db_transaction.commit().await?;
+
+    debug!("Merged category '{}' into '{}'", id, target);
+    Ok(())
+}
+
+/// Merges the subcategory into another subcategory, which doesn't have to be part of the same
+/// category.
+#[post("/<category_id>/subcategories/<subcategory_id>/merge-into/<target>")]
+pub async fn merge_subcategory(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    category_id: &str,
+    subcategory_id: &str,
+    target: &str,
+) -> Result<()> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Executing subcategory guard for '{}' with user '{}'", subcategory_id, user);
+    Subcategory::guard_one(inner_pool, subcategory_id, category_id, &user.uuid).await?;
+
+    let target_category_id = sqlx::query_scalar!(
+        r#"
+            SELECT parent_category
+            FROM subcategories
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        target,
+        user.uuid
+    )
+    .fetch_optional(inner_pool)
+    .await?;
+
+    let Some(target_category_id) = target_category_id else {
+        return HttpError::new(404)
+            .message("The subcategory to merge into could not be found")
+            .into();
+    };
+
+    let mut db_transaction = inner_pool.begin().await?;
+
+    MergeService::merge_subcategories(
+        &mut db_transaction,
+        &user.uuid,
+        subcategory_id,
+        &target_category_id,
+        target,
+    )
+    .await?;
+
+    db_transaction.commit().await?;
+
+    debug!("Merged subcategory '{}' into '{}'", subcategory_id, target);
+    Ok(())
+}
@@ -6,6 +6,7 @@ use crate::error::http_error::HttpError;
 use crate::models::dto::categories::move_between_categories_dto::MoveBetweenCategoriesDto;
 use crate::models::entities::transaction::Transaction;
 use crate::models::entities::transaction::transaction_type::TransactionType;
+use crate::models::entities::user::User;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
 use crate::shared::SharedPool;
@@ -19,6 +20,10 @@ pub async fn move_money_between_categories(
     let inner_pool = db_inner!(pool);
     let body = body.0;
 
+    // Moves don't belong to a bank account, so they are in the currency the user keeps their
+    // totals in.
+    let currency = User::base_currency(inner_pool, &user.uuid).await?;
+
     let uuid_a = Uuid::new_v4();
     let uuid_b = Uuid::new_v4();
 
@@ -38,10 +43,13 @@ pub async fn move_money_between_categories(
         external_account_name: "".to_string(),
         external_account_id: None,
         external_account_name_id: None,
+        external_account_iban: None,
         parent_import_id: None,
         subcategory_id: body.from_subcategory_id,
         order_indicator: 0,
         related_move_transaction: Some(uuid_b.to_string()),
+        related_transfer_transaction: None,
+        currency: currency.to_string(),
     };
 
     let transaction_to = Transaction {
@@ -60,10 +68,13 @@ pub async fn move_money_between_categories(
         external_account_name: "".to_string(),
         external_account_id: None,
         external_account_name_id: None,
+        external_account_iban: None,
         parent_import_id: None,
         subcategory_id: body.to_subcategory_id,
         order_indicator: 0,
         related_move_transaction: Some(uuid_a.to_string()),
+        related_transfer_transaction: None,
+        currency,
     };
 
     let mut db_transaction = inner_pool.begin().await?;
@@ -25,12 +25,19 @@ pub async fn get_subcategory_by_id(
 
     let record = sqlx::query!(
         r#"
-            SELECT *, (
-                SELECT SUM(amount)::bigint
-                FROM transactions
-                WHERE subcategories.parent_category = transactions.category_id AND subcategories.id = transactions.subcategory_id
-            )::bigint AS amount
+            SELECT subcategories.*, totals.amount, totals.missing_rates AS "missing_rates!"
             FROM subcategories
+            CROSS JOIN LATERAL (
+                SELECT
+                    SUM(converted)::bigint AS amount,
+                    COALESCE(ARRAY_AGG(DISTINCT currency) FILTER (WHERE converted IS NULL), ARRAY[]::varchar[]) AS missing_rates
+                FROM (
+                    SELECT currency, convert_to_base(transactions.user_id, amount, currency, date) AS converted
+                    FROM transactions
+                    WHERE subcategories.parent_category = transactions.category_id AND subcategories.id = transactions.subcategory_id
+                        AND related_transfer_transaction IS NULL
+                ) subcategory_transactions
+            ) totals
             WHERE id = $1 AND parent_category = $2 AND user_id = $3;
         "#,
         subcategory_id,
@@ -46,6 +53,7 @@ pub async fn get_subcategory_by_id(
         description: record.description,
         hex_color: record.hex_color,
         amount: record.amount.unwrap_or(0),
+        missing_rates: record.missing_rates,
     }))
 }
 
@@ -83,12 +91,19 @@ pub async fn get_subcategories(
 
     let records = sqlx::query!(
         r#"
-            SELECT *, (
-                SELECT SUM(amount)::bigint
-                FROM transactions
-                WHERE subcategories.parent_category = transactions.category_id AND subcategories.id = transactions.subcategory_id
-            )::bigint AS amount
+            SELECT subcategories.*, totals.amount, totals.missing_rates AS "missing_rates!"
             FROM subcategories
+            CROSS JOIN LATERAL (
+                SELECT
+                    SUM(converted)::bigint AS amount,
+                    COALESCE(ARRAY_AGG(DISTINCT currency) FILTER (WHERE converted IS NULL), ARRAY[]::varchar[]) AS missing_rates
+                FROM (
+                    SELECT currency, convert_to_base(transactions.user_id, amount, currency, date) AS converted
+                    FROM transactions
+                    WHERE subcategories.parent_category = transactions.category_id AND subcategories.id = transactions.subcategory_id
+                        AND related_transfer_transaction IS NULL
+                ) subcategory_transactions
+            ) totals
             WHERE parent_category = $1 AND user_id = $2;
         "#,
         category_id,
@@ -105,6 +120,7 @@ pub async fn get_subcategories(
             description: record.description,
             hex_color: record.hex_color,
             amount: record.amount.unwrap_or(0),
+            missing_rates: record.missing_rates,
         })
         .collect();
 
@@ -1,18 +1,18 @@
 use chrono::Utc;
 use rocket::serde::json::Json;
 use rocket::Route;
-use uuid::Uuid;
+use sqlx::types::time::OffsetDateTime;
 
 use crate::db_inner;
 use crate::error::http_error::HttpError;
 use crate::models::dto::transactions::new_correction_dto::NewCorrectionDto;
 use crate::models::dto::transactions::transaction_dto::TransactionDto;
 use crate::models::entities::transaction::transaction_type::TransactionType;
-use crate::models::entities::transaction::Transaction;
+use crate::models::entities::reconciliation::Reconciliation;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
 use crate::queries::transactions_query::TransactionQuery;
-
+use crate::services::correction_service::CorrectionService;
 use crate::shared::SharedPool;
 
 pub fn create_correction_routes() -> Vec<Route> {
@@ -52,47 +52,21 @@ pub async fn create_correction(
     let inner_pool = db_inner!(pool);
     let body = body.0;
 
-    trace!("Finding max order index");
-    let record = sqlx::query!(
-        r#"
-            SELECT MAX(order_indicator)
-            FROM transactions
-            WHERE user_id = $1;
-        "#,
-        user.uuid
+    let date = Utc::now();
+
+    trace!("Checking bank account '{}' is not locked", body.bank_account_id);
+    Reconciliation::guard_unlocked(
+        inner_pool,
+        &user.uuid,
+        &body.bank_account_id,
+        OffsetDateTime::from_unix_timestamp(date.timestamp())?,
     )
-    .fetch_one(inner_pool)
     .await?;
 
-    let uuid = Uuid::new_v4();
-
-    let transaction = Transaction {
-        id: uuid.to_string(),
-        user_id: user.uuid.to_string(),
-        transaction_type: TransactionType::Correction,
-        follow_number: Uuid::new_v4().to_string(),
-        original_description: body.description.to_string(),
-        description: body.description.to_string(),
-        complete_amount: body.amount,
-        amount: body.amount,
-        date: Utc::now(),
-        bank_account_id: Some(body.bank_account_id),
-        category_id: body.category_id,
-        parent_transaction_id: None,
-        external_account_name: "Correction".to_string(),
-        external_account_id: None,
-        external_account_name_id: None,
-        parent_import_id: None,
-        subcategory_id: body.subcategory_id,
-        order_indicator: record.max.unwrap_or(0) + 1,
-        related_move_transaction: None,
-    };
-
-    debug!("Creating new correction '{}'", transaction.id);
-    transaction.create(inner_pool).await?;
+    let id = CorrectionService::create_correction(inner_pool, &user.uuid, body, date).await?;
 
     let transaction = TransactionQuery::new(user.uuid)
-        .where_id(uuid.to_string())
+        .where_id(id)
         .fetch_one(inner_pool)
         .await?;
 
@@ -112,9 +86,9 @@ pub async fn update_correction(
 
     // Checks if the transaction exists and if a correction
     debug!("Checking if correction '{}' exists", id);
-    sqlx::query!(
+    let record = sqlx::query!(
         r#"
-            SELECT id
+            SELECT date
             FROM transactions
             WHERE id = $1 AND user_id = $2 AND transaction_type = 'correction';
         "#,
@@ -124,6 +98,13 @@ pub async fn update_correction(
     .fetch_one(inner_pool)
     .await?;
 
+    // Both the bank account the correction is moved from and the one it is moved to must not be
+    // reconciled at the date of the correction.
+    trace!("Checking correction '{}' is not locked", id);
+    Reconciliation::guard_transactions_unlocked(inner_pool, &user.uuid, &[id.to_string()]).await?;
+    Reconciliation::guard_unlocked(inner_pool, &user.uuid, &body.bank_account_id, record.date)
+        .await?;
+
     debug!("Updating correction '{}'", id);
     sqlx::query!(
         r#"
@@ -180,6 +161,9 @@ pub async fn delete_correction(pool: &SharedPool, user: JwtUserPayload, id: Stri
             .into());
     }
 
+    trace!("Checking correction '{}' is not locked", id);
+    Reconciliation::guard_transactions_unlocked(inner_pool, &user.uuid, &[id.to_string()]).await?;
+
     debug!("Deleting correction '{}'", id);
     sqlx::query!(
         r#"
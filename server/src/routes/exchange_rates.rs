@@ -0,0 +1,58 @@
+use rocket::serde::json::Json;
+use rocket::Route;
+
+use crate::db_inner;
+use crate::models::dto::exchange_rates::exchange_rate_dto::ExchangeRateDto;
+use crate::models::dto::exchange_rates::import_exchange_rates_dto::ImportExchangeRatesDto;
+use crate::models::entities::exchange_rate::ExchangeRate;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::exchange_rate_service::parse_ecb_rates;
+use crate::shared::SharedPool;
+use crate::utils::currency::parse_currency;
+
+pub fn create_exchange_rate_routes() -> Vec<Route> {
+    routes![get_all_exchange_rates, import_exchange_rates]
+}
+
+#[get("/?<currency>")]
+pub async fn get_all_exchange_rates(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    currency: Option<&str>,
+) -> Result<Json<Vec<ExchangeRateDto>>> {
+    let pool = db_inner!(pool);
+    let currency = currency.map(parse_currency).transpose()?;
+
+    debug!("Querying all exchange rates for user '{}'", user);
+    let rates = ExchangeRate::find_all(pool, &user.uuid, currency.as_deref()).await?;
+
+    let dtos = rates
+        .into_iter()
+        .map(|rate| ExchangeRateDto {
+            currency: rate.currency,
+            date: rate.date.to_string(),
+            rate: rate.rate,
+        })
+        .collect();
+
+    Ok(Json(dtos))
+}
+
+/// Imports the reference rates from a CSV as published by the ECB. Rates that were imported before
+/// for the same currency and date are overwritten. Returns the number of imported rates.
+#[post("/import", data = "<body>")]
+pub async fn import_exchange_rates(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<ImportExchangeRatesDto>,
+) -> Result<Json<u64>> {
+    let pool = db_inner!(pool);
+
+    let rates = parse_ecb_rates(&user.uuid, &body.csv)?;
+
+    debug!("Importing {} exchange rates for user '{}'", rates.len(), user);
+    let imported = ExchangeRate::upsert_all(pool, &rates).await?;
+
+    Ok(Json(imported))
+}
@@ -3,20 +3,31 @@ use rocket::Route;
 use uuid::Uuid;
 
 use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::external_accounts::accept_external_account_suggestion_dto::AcceptExternalAccountSuggestionDto;
 use crate::models::dto::external_accounts::external_account_dto::ExternalAccountDto;
+use crate::models::dto::external_accounts::external_account_iban_dto::ExternalAccountIbanDto;
 use crate::models::dto::external_accounts::external_account_name_dto::ExternalAccountNameDto;
+use crate::models::dto::external_accounts::external_account_suggestion_dto::ExternalAccountSuggestionDto;
 use crate::models::dto::external_accounts::new_external_account_dto::NewExternalAccountDto;
+use crate::models::dto::external_accounts::new_external_account_iban_dto::NewExternalAccountIbanDto;
 use crate::models::dto::external_accounts::new_external_account_name_dto::NewExternalAccountNameDto;
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
 use crate::models::dto::pagination::pagination_response_dto::PaginationResponseDto;
 use crate::models::dto::transactions::transaction_dto::TransactionDto;
 use crate::models::entities::category::Category;
 use crate::models::entities::external_account::ExternalAccount;
+use crate::models::entities::external_account_ibans::ExternalAccountIban;
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
 use crate::models::entities::external_account_names::ExternalAccountName;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
 use crate::queries::transactions_query::TransactionQuery;
-use crate::shared::SharedPool;
+use crate::services::external_account_matcher::{ExternalAccountMatcher, NamePattern};
+use crate::services::external_account_suggestion_service::ExternalAccountSuggestionService;
+use crate::services::merge_service::MergeService;
+use crate::shared::{DbPool, SharedPool};
+use crate::utils::iban::normalize_iban;
 
 pub fn create_external_account_routes() -> Vec<Route> {
     routes![
@@ -25,12 +36,19 @@ pub fn create_external_account_routes() -> Vec<Route> {
         get_external_account_by_id,
         update_external_account,
         delete_external_account,
+        merge_external_account,
         get_external_account_names,
         add_external_account_name,
         delete_external_account_name,
         get_transactions_for_external_account,
         apply_external_account_name,
         remove_external_account_name_associations,
+        get_external_account_ibans,
+        add_external_account_iban,
+        delete_external_account_iban,
+        apply_external_account_iban,
+        get_external_account_suggestions,
+        accept_external_account_suggestion,
     ]
 }
 
@@ -194,6 +212,29 @@ pub async fn delete_external_account(
     Ok(())
 }
 
+#[post("/<id>/merge-into/<target>")]
+pub async fn merge_external_account(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: String,
+    target: String,
+) -> Result<()> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Executing external account guard for '{}' and '{}' with user '{}'", id, target, user);
+    ExternalAccount::guard_one(inner_pool, &id, &user.uuid).await?;
+    ExternalAccount::guard_one(inner_pool, &target, &user.uuid).await?;
+
+    let mut db_transaction = inner_pool.begin().await?;
+
+    MergeService::merge_external_accounts(&mut db_transaction, &user.uuid, &id, &target).await?;
+
+    db_transaction.commit().await?;
+
+    debug!("Merged external account '{}' into '{}'", id, target);
+    Ok(())
+}
+
 #[get("/<id>/names")]
 pub async fn get_external_account_names(
     pool: &SharedPool,
@@ -226,6 +267,7 @@ pub async fn get_external_account_names(
                 id: record.id,
                 name: record.name,
                 parent_external_account: record.parent_external_account,
+                match_kind: NameMatchKind::from(&*record.match_kind),
             })
             .collect(),
     ))
@@ -249,8 +291,33 @@ pub async fn add_external_account_name(
         user_id: user.uuid,
         name: body.name.to_string(),
         parent_external_account: id,
+        match_kind: body.match_kind,
     };
 
+    let pattern = NamePattern::new(
+        &external_account_name.id,
+        &external_account_name.parent_external_account,
+        None,
+        &external_account_name.name,
+        external_account_name.match_kind,
+    )?;
+
+    // A name that would be matched by the entries of two different external accounts can only end
+    // up at one of them, so overlapping entries are rejected.
+    let user_id = &external_account_name.user_id;
+    let matcher = ExternalAccountMatcher::load(inner_pool, user_id).await?;
+    let transaction_names = get_transaction_names(inner_pool, user_id).await?;
+    let conflicts = matcher.conflicts(&pattern, &transaction_names);
+
+    if !conflicts.is_empty() {
+        return HttpError::new(409)
+            .message(format!(
+                "The name overlaps with another external account for '{}'",
+                conflicts.join("', '")
+            ))
+            .into();
+    }
+
     debug!("Creating new external account name '{}'", external_account_name.id);
     external_account_name.create(inner_pool).await?;
 
@@ -262,6 +329,7 @@ pub async fn add_external_account_name(
         id: external_account_name.id,
         name: external_account_name.name,
         parent_external_account: external_account_name.parent_external_account,
+        match_kind: external_account_name.match_kind,
     }))
 }
 
@@ -329,7 +397,7 @@ pub async fn apply_external_account_name(
 
     let record = sqlx::query!(
         r#"
-            SELECT name
+            SELECT name, match_kind
             FROM external_account_names
             WHERE user_id = $1 AND parent_external_account = $2 AND id = $3;
         "#,
@@ -340,14 +408,40 @@ pub async fn apply_external_account_name(
         .fetch_one(inner_pool)
         .await?;
 
+    // The names are matched the same way as when importing, so patterns are evaluated here
+    // instead of in the database. A name only goes to this entry when no entry that takes
+    // precedence matches it as well.
+    NamePattern::new(
+        &name_id,
+        &id,
+        None,
+        record.name,
+        NameMatchKind::from(&*record.match_kind),
+    )?;
+
+    let matcher = ExternalAccountMatcher::load(inner_pool, &user.uuid).await?;
+    let names: Vec<String> = get_transaction_names(inner_pool, &user.uuid)
+        .await?
+        .into_iter()
+        .filter(|name| matcher.find(name).is_some_and(|pattern| pattern.name_id == name_id))
+        .collect();
+
+    // The IBAN of the other party wins over its name, so transactions with an IBAN of another
+    // external account are left alone.
     sqlx::query!(
         r#"
             UPDATE transactions
             SET external_account_id = $3, external_account_name_id = $4
-            WHERE user_id = $1 AND external_account_name = $2;
+            WHERE user_id = $1 AND external_account_name = ANY($2) AND NOT EXISTS (
+                SELECT 1
+                FROM external_account_ibans
+                WHERE external_account_ibans.user_id = transactions.user_id
+                    AND external_account_ibans.iban = transactions.external_account_iban
+                    AND external_account_ibans.parent_external_account != $3
+            );
         "#,
         user.uuid,
-        record.name,
+        &names,
         id,
         name_id
     )
@@ -395,3 +489,236 @@ pub async fn remove_external_account_name_associations(
 
     Ok(())
 }
+
+#[get("/<id>/ibans")]
+pub async fn get_external_account_ibans(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: String,
+) -> Result<Json<Vec<ExternalAccountIbanDto>>> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Executing external account guard for '{}' with user '{}'", id, user);
+    ExternalAccount::guard_one(inner_pool, &id, &user.uuid).await?;
+
+    trace!("Querying external account IBANs");
+    let records = sqlx::query!(
+        r#"
+            SELECT *
+            FROM external_account_ibans
+            WHERE user_id = $1 AND parent_external_account = $2
+            ORDER BY iban;
+        "#,
+        user.uuid,
+        id,
+    )
+    .fetch_all(inner_pool)
+    .await?;
+
+    Ok(Json(
+        records
+            .into_iter()
+            .map(|record| ExternalAccountIbanDto {
+                id: record.id,
+                iban: record.iban,
+                parent_external_account: record.parent_external_account,
+            })
+            .collect(),
+    ))
+}
+
+/// Adds an IBAN to the external account. Imported transactions with this IBAN as the other party
+/// are linked to the external account, regardless of their name.
+#[post("/<id>/ibans", data = "<body>")]
+pub async fn add_external_account_iban(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: String,
+    body: Json<NewExternalAccountIbanDto<'_>>,
+) -> Result<Json<ExternalAccountIbanDto>> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Executing external account guard for '{}' with user '{}'", id, user);
+    ExternalAccount::guard_one(inner_pool, &id, &user.uuid).await?;
+
+    let iban = normalize_iban(body.iban);
+
+    if iban.is_empty() || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return HttpError::new(400)
+            .message(format!("'{}' is not a valid IBAN", body.iban))
+            .into();
+    }
+
+    let external_account_iban = ExternalAccountIban {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid,
+        iban,
+        parent_external_account: id,
+    };
+
+    debug!("Creating new external account IBAN '{}'", external_account_iban.id);
+    external_account_iban
+        .create(inner_pool)
+        .await
+        .map_err(duplicate_iban_error)?;
+
+    Ok(Json(ExternalAccountIbanDto {
+        id: external_account_iban.id,
+        iban: external_account_iban.iban,
+        parent_external_account: external_account_iban.parent_external_account,
+    }))
+}
+
+#[delete("/<id>/ibans/<iban_id>")]
+pub async fn delete_external_account_iban(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: String,
+    iban_id: String,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    ExternalAccountIban::find_one(pool, &iban_id, &id, &user.uuid).await?;
+
+    debug!("Deleting IBAN '{}' from '{}'", iban_id, id);
+    sqlx::query!(
+        r#"
+            DELETE FROM external_account_ibans
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        iban_id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    debug!("Deleted IBAN '{}' from '{}'", iban_id, id);
+    Ok(())
+}
+
+/// Links all existing transactions with the IBAN as the other party to the external account.
+#[patch("/<id>/ibans/<iban_id>/apply")]
+pub async fn apply_external_account_iban(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: String,
+    iban_id: String,
+) -> Result<()> {
+    let inner_pool = db_inner!(pool);
+
+    let external_account_iban =
+        ExternalAccountIban::find_one(inner_pool, &iban_id, &id, &user.uuid).await?;
+
+    sqlx::query!(
+        r#"
+            UPDATE transactions
+            SET external_account_id = $3, external_account_name_id = null
+            WHERE user_id = $1 AND external_account_iban = $2;
+        "#,
+        user.uuid,
+        external_account_iban.iban,
+        id,
+    )
+    .execute(inner_pool)
+    .await?;
+
+    Ok(())
+}
+
+#[get("/suggestions")]
+pub async fn get_external_account_suggestions(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<ExternalAccountSuggestionDto>>> {
+    let pool = db_inner!(pool);
+
+    let clusters = ExternalAccountSuggestionService::get_clusters(pool, &user.uuid).await?;
+
+    Ok(Json(
+        clusters
+            .into_iter()
+            .map(|cluster| ExternalAccountSuggestionDto {
+                key: cluster.key,
+                suggested_name: cluster.suggested_name,
+                names: cluster.names,
+                transaction_count: cluster.transaction_count,
+            })
+            .collect(),
+    ))
+}
+
+#[post("/suggestions", data = "<body>")]
+pub async fn accept_external_account_suggestion(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<AcceptExternalAccountSuggestionDto<'_>>,
+) -> Result<Json<ExternalAccountDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    if let Some(category_id) = &body.default_category_id {
+        Category::guard_one(inner_pool, category_id, &user.uuid).await?;
+    }
+
+    // The clusters are computed again, so only names that are still unlinked are attached.
+    let clusters = ExternalAccountSuggestionService::get_clusters(inner_pool, &user.uuid).await?;
+    let Some(cluster) = clusters.iter().find(|cluster| cluster.key == body.key) else {
+        return HttpError::new(404)
+            .message("The suggestion could not be found")
+            .into();
+    };
+
+    let external_account = ExternalAccount {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        name: body.name.unwrap_or(&cluster.suggested_name).to_string(),
+        description: body.description.to_string(),
+        hex_color: body.hex_color.unwrap_or("ffffff").to_string(),
+        default_category_id: body.default_category_id.map(|v| v.to_string()),
+        default_subcategory_id: body.default_subcategory_id.map(|v| v.to_string()),
+    };
+
+    let mut db_transaction = inner_pool.begin().await?;
+
+    ExternalAccountSuggestionService::accept(&mut db_transaction, &external_account, cluster)
+        .await?;
+
+    db_transaction.commit().await?;
+
+    debug!(
+        "Created external account '{}' from {} suggested names",
+        external_account.id,
+        cluster.names.len()
+    );
+    get_external_account_by_id(pool, user.clone(), external_account.id).await
+}
+
+/// Returns every distinct name of the other party in the transactions of the user.
+async fn get_transaction_names(pool: &DbPool, user_id: &str) -> Result<Vec<String>> {
+    let names = sqlx::query_scalar!(
+        r#"
+            SELECT DISTINCT external_account_name
+            FROM transactions
+            WHERE user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(names)
+}
+
+/// An IBAN can only belong to a single external account, which is reported as a conflict instead
+/// of a database error.
+fn duplicate_iban_error(error: Error) -> Error {
+    let Error::Sqlx(wrapped_error) = &error else {
+        return error;
+    };
+
+    if wrapped_error.get_constraint() != Some("unique_external_account_iban") {
+        return error;
+    }
+
+    Error::HttpError(HttpError::new(409).message("The IBAN already belongs to an external account"))
+}
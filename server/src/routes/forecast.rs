@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::Route;
+use sqlx::types::time::OffsetDateTime;
+
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::forecast::forecast_dto::ForecastDto;
+use crate::models::entities::planned_transaction::PlannedTransaction;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::balance_service::BalanceService;
+use crate::services::planned_transaction_service::project;
+use crate::shared::SharedPool;
+
+/// The furthest a forecast can look ahead, in months.
+const MAX_MONTHS: u32 = 24;
+
+pub fn create_forecast_routes() -> Vec<Route> {
+    routes![get_forecast]
+}
+
+/// Projects the balance of every bank account of the user forward by the given number of months,
+/// which defaults to 3, starting from the current balance and adding the planned transactions.
+#[get("/?<months>")]
+pub async fn get_forecast(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    months: Option<u32>,
+) -> Result<Json<Vec<ForecastDto>>> {
+    let pool = db_inner!(pool);
+    let months = months.unwrap_or(3);
+
+    if months == 0 || months > MAX_MONTHS {
+        return Err(HttpError::new(400)
+            .message(format!("The forecast can be between 1 and {} months", MAX_MONTHS))
+            .into());
+    }
+
+    debug!("Querying all bank accounts for user '{}'", user);
+    let bank_accounts = sqlx::query!(
+        r#"
+            SELECT id, currency
+            FROM bank_accounts
+            WHERE user_id = $1
+            ORDER BY name;
+        "#,
+        user.uuid
+    )
+    .fetch_all(pool)
+    .await?;
+
+    debug!("Querying all planned transactions for user '{}'", user);
+    let mut planned_by_bank_account = HashMap::new();
+
+    for planned in PlannedTransaction::find_all(pool, &user.uuid).await? {
+        planned_by_bank_account
+            .entry(planned.bank_account_id.to_string())
+            .or_insert_with(Vec::new)
+            .push(planned);
+    }
+
+    let now = Utc::now();
+    let mut forecasts = vec![];
+
+    for bank_account in bank_accounts {
+        let balance = BalanceService::balance_at(
+            pool,
+            &user.uuid,
+            &bank_account.id,
+            OffsetDateTime::from_unix_timestamp(now.timestamp())?,
+        )
+        .await?;
+
+        let planned = planned_by_bank_account.remove(&bank_account.id).unwrap_or_default();
+        let projection = project(balance, &planned, now, months);
+
+        forecasts.push(ForecastDto {
+            bank_account_id: bank_account.id,
+            currency: bank_account.currency,
+            balance,
+            end_balance: projection.end_balance,
+            lowest_balance: projection.lowest_balance,
+            items: projection.items,
+            balances: projection.balances,
+        });
+    }
+
+    Ok(Json(forecasts))
+}
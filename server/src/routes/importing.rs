@@ -5,19 +5,34 @@ use crate::db_inner;
 use crate::models::dto::import::import_dto::ImportDto;
 use crate::models::dto::import::import_dto_with_numbers::ImportDtoWithNumbers;
 use crate::models::entities::import::Import;
+use crate::models::entities::reconciliation::Reconciliation;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
 use crate::routes::importing::check_csv_mapping::check_csv_mapping as check_csv_mapping_route;
 use crate::routes::importing::csv_import::import_csv;
+use crate::routes::importing::mapping_profiles::*;
+use crate::routes::importing::statement_import::import_statement;
 use crate::shared::SharedPool;
 
 pub mod check_csv_mapping;
 pub mod csv_import;
+pub mod guess_csv_mapping;
+pub mod import_records;
 pub mod map_csv_record;
+pub mod mapping_profiles;
+pub mod statement_formats;
+pub mod statement_import;
 
 pub fn create_importing_routes() -> Vec<Route> {
     routes![
         import_csv,
+        import_statement,
+        detect_csv_mapping,
+        get_all_csv_mapping_profiles,
+        create_csv_mapping_profile,
+        get_csv_mapping_profile_by_id,
+        update_csv_mapping_profile,
+        delete_csv_mapping_profile,
         check_csv_mapping_route,
         get_all_imports,
         get_import_by_id,
@@ -100,6 +115,24 @@ pub async fn delete_import(pool: &SharedPool, user: JwtUserPayload, id: String)
 
     Import::guard_one(inner_pool, &id, &user.uuid).await?;
 
+    trace!("Checking transactions of import '{}' are not locked", id);
+    let transaction_ids: Vec<String> = sqlx::query!(
+        r#"
+            SELECT id
+            FROM transactions
+            WHERE parent_import = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .fetch_all(inner_pool)
+    .await?
+    .into_iter()
+    .map(|record| record.id)
+    .collect();
+
+    Reconciliation::guard_transactions_unlocked(inner_pool, &user.uuid, &transaction_ids).await?;
+
     sqlx::query!(
         r#"
             DELETE FROM imports
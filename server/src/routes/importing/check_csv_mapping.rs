@@ -2,6 +2,7 @@ use std::io::Cursor;
 
 use rocket::serde::json::Json;
 
+use crate::error::http_error::HttpError;
 use crate::error::import_error::ImportError;
 use crate::models::dto::importing::check_csv_mapping_dto::CheckCsvMappingDto;
 use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
@@ -12,14 +13,28 @@ use crate::routes::importing::map_csv_record::map_csv_record;
 pub async fn check_csv_mapping(body: Json<ImportCsvDto>) -> Result<Json<CheckCsvMappingDto>> {
     let body = body.0;
 
+    let Some(mapping) = body.mappings else {
+        return HttpError::new(400)
+            .message("The mappings are required to check them")
+            .into();
+    };
+
     let mut reader = csv::Reader::from_reader(Cursor::new(body.csv));
+    let header = reader.headers()?.clone();
+
+    let unknown_columns = mapping.unknown_columns(&header);
+
+    if !unknown_columns.is_empty() {
+        return Err(ImportError::missing_column(unknown_columns.join(", ")).into());
+    }
+
     let read_record = reader.records().next();
 
     let Some(record) = read_record else {
         return Err(ImportError::NoRows.into());
     };
 
-    let mapped_record = map_csv_record(record?, &body.mappings)?;
+    let mapped_record = map_csv_record(record?, &header, &mapping)?;
 
     Ok(Json(CheckCsvMappingDto {
         account_iban: mapped_record.account_iban,
@@ -28,5 +43,8 @@ pub async fn check_csv_mapping(body: Json<ImportCsvDto>) -> Result<Json<CheckCsv
         description: mapped_record.description,
         amount: mapped_record.amount,
         external_account_name: mapped_record.external_account_name,
+        currency: mapped_record.currency,
+        external_account_iban: mapped_record.external_account_iban,
+        value_date: mapped_record.value_date.map(|date| date.to_string()),
     }))
 }
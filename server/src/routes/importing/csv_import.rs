@@ -1,239 +1,128 @@
-use std::collections::HashMap;
 use std::io::Cursor;
 
-use chrono::Utc;
-use csv::StringRecord;
 use rocket::serde::json::Json;
-use uuid::Uuid;
 
 use crate::db_inner;
-use crate::error::Error::Sqlx;
-use crate::models::csv::csv_mapping::CsvImportOrdering::NewestFirst;
+use crate::error::error_dto_trait::ToErrorDto;
+use crate::error::http_error::HttpError;
+use crate::error::import_error::ImportError;
+use crate::models::csv::csv_mapping::CsvMapping;
 use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
-use crate::models::entities::bank_account::BankAccount;
-use crate::models::entities::import::Import;
-use crate::models::entities::transaction::transaction_type::TransactionType;
-use crate::models::entities::transaction::Transaction;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::importing::import_report_dto::ImportReportDto;
+use crate::models::dto::importing::import_row_dto::{ImportRowDto, ImportRowStatus};
+use crate::models::entities::csv_mapping_profile::CsvMappingProfile;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
 use crate::prelude::*;
-use crate::routes::importing::map_csv_record::map_csv_record;
+use crate::routes::importing::import_records::import_records;
+use crate::routes::importing::map_csv_record::{map_csv_record, record_row};
 use crate::shared::{DbPool, SharedPool};
-use crate::utils::try_collect::try_collect;
 
-#[post("/csv", data = "<body>")]
+/// Imports a CSV with the given mapping or mapping profile. With `dryRun`, every row is run through
+/// the import without saving anything and rows that cannot be read are reported instead of
+/// failing the whole import.
+#[post("/csv?<query..>", data = "<body>")]
 pub async fn import_csv(
     pool: &SharedPool,
     user: JwtUserPayload,
     body: Json<ImportCsvDto>,
-) -> Result<()> {
+    query: ImportQueryDto,
+) -> Result<Json<ImportReportDto>> {
     let pool = db_inner!(pool);
     let body = body.0;
 
-    // Start a database transaction.
-    let mut db_transaction = pool.begin().await?;
-
-    // Get required maps used when importing
-    let mut bank_account_map = get_bank_accounts_map(pool, &user.uuid).await?;
-    let external_account_map = get_external_accounts_map(pool, &user.uuid).await?;
-    let mut order_indicator = get_order_indicator(pool, &user.uuid).await?;
-
-    // Create an import record where all the transactions will be added to.
-    let import_uuid = Uuid::new_v4();
-    let import = Import {
-        id: import_uuid.to_string(),
-        user_id: user.uuid.to_string(),
-        imported_at: Utc::now(),
-        filename: body.filename,
+    let Some(mapping) =
+        resolve_mapping(pool, &user.uuid, body.mappings, body.profile_id.as_deref()).await?
+    else {
+        return HttpError::new(400)
+            .message("Either the mappings or a mapping profile is required")
+            .into();
     };
 
-    // Create the parent import in the database
-    import.create(&mut *db_transaction).await?;
+    let mut reader = csv::Reader::from_reader(Cursor::new(body.csv));
+    let header = reader.headers()?.clone();
 
-    let records: Vec<StringRecord> =
-        try_collect(csv::Reader::from_reader(Cursor::new(body.csv)).records())?;
+    let unknown_columns = mapping.unknown_columns(&header);
 
-    // If the first record is the newest, the order indicator should count down, so the indicator
-    // is set to the highest value for the import (the number of transactions to import)
-    if NewestFirst == body.mappings.ordering {
-        order_indicator += records.len() as i32;
+    if !unknown_columns.is_empty() {
+        return Err(ImportError::missing_column(unknown_columns.join(", ")).into());
     }
 
-    for record in records {
-        let mapped_record = map_csv_record(record, &body.mappings)?;
-
-        let bank_account_id: Result<String> = match bank_account_map
-            .get(&*mapped_record.account_iban)
-        {
-            Some(id) => Ok(id.to_string()),
-            None => {
-                let bank_account = BankAccount {
-                    id: Uuid::new_v4().to_string(),
-                    iban: mapped_record.account_iban.to_string(),
-                    user_id: user.uuid.to_string(),
-                    name: mapped_record.account_iban.to_string(),
-                    description: "A new bank account".to_string(),
-                    hex_color: "ffffff".to_string(),
-                };
-
-                bank_account.create(&mut *db_transaction).await?;
-
-                bank_account_map.insert(mapped_record.account_iban, bank_account.id.to_string());
-
-                Ok(bank_account.id)
-            }
-        };
-
-        if NewestFirst == body.mappings.ordering {
-            order_indicator -= 1;
-        } else {
-            order_indicator += 1;
-        }
+    let mut mapped_records = vec![];
+    let mut failed_rows = vec![];
 
-        let mut transaction = Transaction {
-            id: Uuid::new_v4().to_string(),
-            user_id: user.uuid.to_string(),
-            transaction_type: TransactionType::Transaction,
-            follow_number: mapped_record.follow_number,
-            original_description: mapped_record.description.to_string(),
-            description: mapped_record.description,
-            complete_amount: mapped_record.amount,
-            amount: mapped_record.amount,
-            date: mapped_record.date,
-            bank_account_id: Some(bank_account_id?),
-            category_id: None,
-            parent_transaction_id: None,
-            external_account_name: mapped_record.external_account_name.to_string(),
-            external_account_id: None,
-            external_account_name_id: None,
-            parent_import_id: Some(import_uuid.to_string()),
-            subcategory_id: None,
-            order_indicator,
-            related_move_transaction: None,
+    for record in reader.records() {
+        let (row, result) = match record {
+            Ok(record) => (record_row(&record), map_csv_record(record, &header, &mapping)),
+            Err(error) => (
+                error.position().map(|position| position.line()).unwrap_or_default(),
+                Err(error.into()),
+            ),
         };
 
-        let external_account_id = external_account_map
-            .get(&*mapped_record.external_account_name)
-            .map(|(id, category)| (id.to_string(), category.to_owned()));
-
-        if let Some((external_id, category_id)) = external_account_id {
-            transaction.external_account_id = Some(external_id);
-            transaction.category_id = category_id;
+        match result {
+            Ok(mapped_record) => mapped_records.push(mapped_record),
+            Err(error) if query.dry_run => failed_rows.push(failed_row(row, error)?),
+            Err(error) => return Err(error),
         }
-
-        // Because Postgres does an implicit rollback when a statement fails, a savepoint is created
-        // so if the insert fails like we expect, the savepoint is the one that is implicitly
-        // rolled back instead of the actual transaction.
-        sqlx::query!("SAVEPOINT T")
-            .execute(&mut *db_transaction)
-            .await?;
-
-        let result = transaction.create(&mut *db_transaction).await;
-
-        // If the result is Ok the transactions is guaranteed to be a new transaction.
-        if result.is_ok() {
-            continue;
-        }
-
-        sqlx::query!("ROLLBACK TO T")
-            .execute(&mut *db_transaction)
-            .await?;
-
-        // If the database returned an Err, the transaction may be a duplicate, so that is checked
-        // here and if it is a duplicate, a link is created between the duplicate transaction and
-        // the import record.
-        let error = result.expect_err("Was Ok but also an error?");
-
-        let Sqlx(wrapped_error) = &error else {
-            return Err(error);
-        };
-
-        let Some(constraint) = wrapped_error.get_constraint() else {
-            return Err(error);
-        };
-
-        // If the Err was not caused by the unique constrained the error import fails.
-        if constraint != "unique_follow_number" {
-            return Err(error);
-        }
-
-        sqlx::query!(
-            r#"
-                INSERT INTO skipped_transactions
-                VALUES ($1, $2, $3);
-            "#,
-            import_uuid.to_string(),
-            user.uuid.to_string(),
-            transaction.follow_number
-        )
-        .execute(&mut *db_transaction)
-        .await?;
     }
 
-    db_transaction.commit().await?;
-
-    Ok(())
-}
-
-async fn get_bank_accounts_map(pool: &DbPool, user_id: &String) -> Result<HashMap<String, String>> {
-    let records = sqlx::query!(
-        r#"
-            SELECT id, iban
-            FROM bank_accounts
-            WHERE user_id = $1;
-        "#,
-        user_id
+    let mut rows = import_records(
+        pool,
+        &user.uuid,
+        body.filename,
+        mapped_records,
+        &mapping.ordering,
+        query.dry_run,
     )
-    .fetch_all(pool)
     .await?;
 
-    let mut map = HashMap::new();
-
-    for record in records {
-        map.insert(record.iban, record.id);
-    }
+    rows.append(&mut failed_rows);
 
-    Ok(map)
+    Ok(Json(ImportReportDto::new(query.dry_run, rows)))
 }
 
-async fn get_external_accounts_map(
-    pool: &DbPool,
-    user_id: &String,
-) -> Result<HashMap<String, (String, Option<String>)>> {
-    let records = sqlx::query!(
-        r#"
-            SELECT external_account_names.name, parent_external_account, e.default_category_id
-            FROM external_account_names
-            INNER JOIN external_accounts e ON e.id = external_account_names.parent_external_account
-            WHERE external_account_names.user_id = $1;
-        "#,
-        user_id
-    )
-    .fetch_all(pool)
-    .await?;
+/// Turns an error of reading a row into a row of the report. Errors that are not about the content
+/// of the CSV still fail the import.
+fn failed_row(row: u64, error: Error) -> Result<ImportRowDto> {
+    let (column, message) = match error {
+        Error::ImportError(ImportError::InvalidValue { column, reason, .. }) => {
+            (Some(column), reason)
+        }
+        Error::ImportError(error @ ImportError::MissingColumn(_)) => (None, error.to_string()),
+        Error::Csv(error) => (None, error.get_description()),
+        error => return Err(error),
+    };
 
-    let mut map = HashMap::new();
+    Ok(ImportRowDto {
+        row,
+        status: ImportRowStatus::Error,
+        follow_number: None,
+        new_bank_account: false,
+        planned_transaction_id: None,
+        transfer_transaction_id: None,
+        column,
+        error: Some(message),
+    })
+}
 
-    for record in records {
-        map.insert(
-            record.name,
-            (record.parent_external_account, record.default_category_id),
-        );
+/// Returns the mapping that was sent along or the mapping of the referenced profile.
+pub async fn resolve_mapping(
+    pool: &DbPool,
+    user_id: &str,
+    mappings: Option<CsvMapping>,
+    profile_id: Option<&str>,
+) -> Result<Option<CsvMapping>> {
+    if mappings.is_some() {
+        return Ok(mappings);
     }
 
-    Ok(map)
-}
+    let Some(profile_id) = profile_id else {
+        return Ok(None);
+    };
 
-async fn get_order_indicator(pool: &DbPool, user_id: &String) -> Result<i32> {
-    let record = sqlx::query!(
-        r#"
-            SELECT MAX(order_indicator) AS max_indicator
-            FROM transactions
-            WHERE user_id = $1;
-        "#,
-        user_id
-    )
-    .fetch_one(pool)
-    .await?;
+    debug!("Querying CSV mapping profile with id '{}'", profile_id);
+    let profile = CsvMappingProfile::find_one(pool, profile_id, user_id).await?;
 
-    Ok(record.max_indicator.unwrap_or(0))
+    Ok(Some(profile.mapping))
 }
@@ -0,0 +1,326 @@
+use chrono::NaiveDate;
+use csv::StringRecord;
+
+use crate::error::import_error::ImportError;
+use crate::models::csv::amount_format::{AmountFormat, NegativeStyle};
+use crate::models::csv::csv_mapping::{
+    normalize_column_name, AmountMapping, CsvColumn, CsvImportOrdering, CsvMapping, DateMapping,
+};
+use crate::prelude::*;
+
+/// Words in column names that indicate the column is about the other party of the transaction
+/// instead of the account of the user.
+const COUNTERPARTY_WORDS: [&str; 5] = ["tegen", "counter", "contra", "payee", "beneficiary"];
+
+/// The date formats that are tried, in order, when guessing the format of the date column.
+const DATE_FORMATS: [&str; 6] = [
+    "%Y-%m-%d", "%d-%m-%Y", "%Y%m%d", "%d/%m/%Y", "%m/%d/%Y", "%d.%m.%Y",
+];
+
+/// Reads a single header row, as it appears in a CSV file.
+pub fn parse_header(header: &str) -> Result<StringRecord> {
+    let record = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(header.as_bytes())
+        .records()
+        .next()
+        .ok_or(ImportError::NoRows)??;
+
+    Ok(record)
+}
+
+/// Normalizes a header row so headers that only differ in casing, whitespace or quoting are
+/// considered the same.
+pub fn normalize_header(header: &StringRecord) -> String {
+    header
+        .iter()
+        .map(normalize_column_name)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Guesses the mapping based on the names of the columns in the header. The rows are used to
+/// guess the date format and the ordering. Returns [None] when not every required column could be
+/// found.
+///
+/// Columns are mapped by their name, so the mapping keeps working when columns are added to the
+/// CSV later. Only columns with a name that occurs more than once are mapped by their index.
+pub fn guess_csv_mapping(header: &StringRecord, rows: &[StringRecord]) -> Option<CsvMapping> {
+    let columns: Vec<String> = header.iter().map(normalize_column_name).collect();
+
+    let mut taken = vec![];
+
+    let mut find = |keywords: &[&str], counterparty: Option<bool>| {
+        let index = find_column(&columns, &taken, keywords, counterparty)?;
+        taken.push(index);
+        Some(index)
+    };
+
+    // The most specific columns are matched first, so for example the name of the counterparty is
+    // not mistaken for the description.
+    let follow_number = find(
+        &["volgnr", "volgnummer", "reference", "referentie", "transaction id", "number", "id"],
+        None,
+    )?;
+    let account_iban = find(&["iban", "rekening", "account"], Some(false))?;
+    let external_account_name = find(
+        &["naam tegenpartij", "tegenpartij", "counterparty", "payee", "beneficiary", "naam", "name"],
+        None,
+    )?;
+    let description = find(
+        &["omschrijving", "description", "mededelingen", "memo", "details"],
+        None,
+    )?;
+    let amount = find(&["bedrag", "amount"], None)?;
+
+    // The value date is matched before the booking date, otherwise a column like 'rentedatum'
+    // could be taken for the booking date when the CSV has no plain 'datum' column.
+    let value_date = find(&["rentedatum", "valutadatum", "value date"], None);
+    let date = find(&["datum", "date", "boekdatum"], None)?;
+
+    let external_account_iban = find(&["iban", "rekening", "account"], Some(true));
+    let currency = find(&["munt", "currency", "valuta"], None);
+
+    let column = |index: usize| {
+        let is_unique = columns
+            .iter()
+            .filter(|column| **column == columns[index])
+            .count()
+            == 1;
+
+        match header.get(index) {
+            Some(name) if is_unique && !columns[index].is_empty() => {
+                CsvColumn::Name(name.trim().to_string())
+            }
+            _ => CsvColumn::Index(index as u32),
+        }
+    };
+
+    let dates: Vec<&str> = rows
+        .iter()
+        .filter_map(|row| row.get(date))
+        .map(str::trim)
+        .collect();
+
+    let date_format = DATE_FORMATS
+        .into_iter()
+        .find(|format| {
+            dates
+                .iter()
+                .all(|date| NaiveDate::parse_from_str(date, format).is_ok())
+        })
+        .unwrap_or(DATE_FORMATS[0]);
+
+    let parsed_dates: Vec<NaiveDate> = dates
+        .iter()
+        .filter_map(|date| NaiveDate::parse_from_str(date, date_format).ok())
+        .collect();
+
+    let ordering = match (parsed_dates.first(), parsed_dates.last()) {
+        (Some(first), Some(last)) if first > last => CsvImportOrdering::NewestFirst,
+        _ => CsvImportOrdering::NewestLast,
+    };
+
+    let amounts: Vec<&str> = rows
+        .iter()
+        .filter_map(|row| row.get(amount))
+        .map(str::trim)
+        .collect();
+
+    Some(CsvMapping {
+        date_mapping: DateMapping {
+            template: Some("$ 00:00:00 +0000".to_string()),
+            format: format!("{} %H:%M:%S %z", date_format),
+        },
+        amount_mapping: AmountMapping::Decimal,
+        amount_format: guess_amount_format(&amounts),
+        ordering,
+        account_iban: column(account_iban),
+        date: column(date),
+        follow_number: column(follow_number),
+        description: column(description),
+        amount: column(amount),
+        external_account_name: column(external_account_name),
+        currency: currency.map(column),
+        external_account_iban: external_account_iban.map(column),
+        value_date: value_date.map(column),
+        default_currency: None,
+        debit_credit: None,
+        credit_amount: None,
+    })
+}
+
+/// Guesses how the amounts are written. The separators can only be told apart when an amount has
+/// both of them, in which case the last one is taken as the decimal separator.
+fn guess_amount_format(amounts: &[&str]) -> AmountFormat {
+    let separators = amounts.iter().find_map(|amount| {
+        let decimal = amount.rfind(['.', ','])?;
+        let thousands = amount[..decimal].rfind(['.', ','])?;
+
+        Some((amount[decimal..].chars().next()?, amount[thousands..].chars().next()?))
+    });
+
+    let negative_style = if amounts
+        .iter()
+        .any(|amount| amount.starts_with('(') && amount.ends_with(')'))
+    {
+        NegativeStyle::Parentheses
+    } else if amounts.iter().any(|amount| amount.ends_with('-')) {
+        NegativeStyle::Trailing
+    } else {
+        NegativeStyle::Leading
+    };
+
+    AmountFormat {
+        decimal_separator: separators.map(|(decimal, _)| decimal),
+        thousands_separator: separators.map(|(_, thousands)| thousands),
+        negative_style,
+    }
+}
+
+/// Finds the column for the first keyword that matches. A column with exactly the name of a keyword
+/// is preferred over a column that only contains it. When `counterparty` is set, columns are only
+/// matched if they are or aren't about the counterparty.
+fn find_column(
+    columns: &[String],
+    taken: &[usize],
+    keywords: &[&str],
+    counterparty: Option<bool>,
+) -> Option<usize> {
+    let candidates: Vec<(usize, &String)> = columns
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !taken.contains(index))
+        .filter(|(_, column)| {
+            let is_counterparty = COUNTERPARTY_WORDS
+                .iter()
+                .any(|word| column.contains(word));
+
+            counterparty.is_none_or(|counterparty| counterparty == is_counterparty)
+        })
+        .collect();
+
+    let exact = keywords.iter().find_map(|keyword| {
+        candidates
+            .iter()
+            .find(|(_, column)| column == keyword)
+    });
+
+    let contains = || {
+        keywords.iter().find_map(|keyword| {
+            candidates
+                .iter()
+                .find(|(_, column)| keyword.len() > 2 && column.contains(keyword))
+        })
+    };
+
+    exact.or_else(contains).map(|(index, _)| *index)
+}
+
+#[cfg(test)]
+mod tests {
+    use csv::StringRecord;
+
+    use crate::models::csv::amount_format::NegativeStyle;
+    use crate::models::csv::csv_mapping::{CsvColumn, CsvImportOrdering};
+    use crate::routes::importing::guess_csv_mapping::{guess_csv_mapping, normalize_header};
+
+    #[test]
+    fn rabobank_export_is_recognized() {
+        let header = StringRecord::from(vec![
+            "IBAN/BBAN", "Munt", "BIC", "Volgnr", "Datum", "Rentedatum", "Bedrag", "Saldo na trn",
+            "Tegenrekening IBAN/BBAN", "Naam tegenpartij", "Omschrijving-1",
+        ]);
+        let rows = vec![
+            StringRecord::from(vec![
+                "NL12RABO0123456789", "EUR", "RABONL2U", "1", "2023-01-10", "2023-01-10", "-12,34",
+                "100,00", "NL99INGB0123456789", "Jumbo", "Groceries",
+            ]),
+            StringRecord::from(vec![
+                "NL12RABO0123456789", "EUR", "RABONL2U", "2", "2023-01-15", "2023-01-15", "-10,00",
+                "90,00", "NL99INGB0123456789", "Jumbo", "Groceries",
+            ]),
+        ];
+
+        let mapping = guess_csv_mapping(&header, &rows).unwrap();
+
+        assert_eq!(mapping.account_iban, name("IBAN/BBAN"));
+        assert_eq!(mapping.follow_number, name("Volgnr"));
+        assert_eq!(mapping.date, name("Datum"));
+        assert_eq!(mapping.amount, name("Bedrag"));
+        assert_eq!(mapping.external_account_name, name("Naam tegenpartij"));
+        assert_eq!(mapping.description, name("Omschrijving-1"));
+        assert_eq!(mapping.currency, Some(name("Munt")));
+        assert_eq!(mapping.external_account_iban, Some(name("Tegenrekening IBAN/BBAN")));
+        assert_eq!(mapping.value_date, Some(name("Rentedatum")));
+        assert_eq!(mapping.date_mapping.format, "%Y-%m-%d %H:%M:%S %z");
+        assert_eq!(mapping.ordering, CsvImportOrdering::NewestLast);
+    }
+
+    #[test]
+    fn date_format_and_ordering_are_guessed_from_rows() {
+        let header = StringRecord::from(vec![
+            "Date", "Name", "Account", "Reference", "Amount", "Description",
+        ]);
+        let rows = vec![
+            StringRecord::from(vec!["25/01/2023", "Work", "NL12", "2", "3000.00", "Salary"]),
+            StringRecord::from(vec!["13/01/2023", "Jumbo", "NL12", "1", "-12.34", "Groceries"]),
+        ];
+
+        let mapping = guess_csv_mapping(&header, &rows).unwrap();
+
+        assert_eq!(mapping.date, name("Date"));
+        assert_eq!(mapping.external_account_name, name("Name"));
+        assert_eq!(mapping.account_iban, name("Account"));
+        assert_eq!(mapping.follow_number, name("Reference"));
+        assert_eq!(mapping.currency, None);
+        assert_eq!(mapping.date_mapping.format, "%d/%m/%Y %H:%M:%S %z");
+        assert_eq!(mapping.ordering, CsvImportOrdering::NewestFirst);
+    }
+
+    #[test]
+    fn duplicate_column_names_are_mapped_by_index() {
+        let header = StringRecord::from(vec![
+            "Date", "Name", "Account", "Reference", "Amount", "Description", "Description",
+        ]);
+
+        let mapping = guess_csv_mapping(&header, &[]).unwrap();
+
+        assert_eq!(mapping.description, CsvColumn::Index(5));
+    }
+
+    #[test]
+    fn missing_columns_cannot_be_guessed() {
+        let header = StringRecord::from(vec!["Date", "Amount"]);
+
+        assert!(guess_csv_mapping(&header, &[]).is_none());
+    }
+
+    #[test]
+    fn amount_format_is_guessed_from_rows() {
+        let header = StringRecord::from(vec![
+            "Date", "Name", "Account", "Reference", "Amount", "Description",
+        ]);
+        let rows = vec![
+            StringRecord::from(vec!["13/01/2023", "Jumbo", "NL12", "1", "12,34-", "Groceries"]),
+            StringRecord::from(vec!["25/01/2023", "Work", "NL12", "2", "3.000,00", "Salary"]),
+        ];
+
+        let mapping = guess_csv_mapping(&header, &rows).unwrap();
+
+        assert_eq!(mapping.amount_format.decimal_separator, Some(','));
+        assert_eq!(mapping.amount_format.thousands_separator, Some('.'));
+        assert_eq!(mapping.amount_format.negative_style, NegativeStyle::Trailing);
+    }
+
+    #[test]
+    fn headers_are_normalized() {
+        let header = StringRecord::from(vec![" IBAN ", "Datum", " \"Naam\""]);
+
+        assert_eq!(normalize_header(&header), "iban,datum,naam");
+    }
+
+    fn name(name: &str) -> CsvColumn {
+        CsvColumn::Name(name.to_string())
+    }
+}
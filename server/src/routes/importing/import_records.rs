@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::error::Error::Sqlx;
+use crate::models::csv::csv_mapping::CsvImportOrdering;
+use crate::models::csv::csv_mapping::CsvImportOrdering::NewestFirst;
+use crate::models::dto::importing::import_row_dto::{ImportRowDto, ImportRowStatus};
+use crate::models::entities::bank_account::BankAccount;
+use crate::models::entities::import::Import;
+use crate::models::entities::planned_transaction::PlannedTransaction;
+use crate::models::entities::reconciliation::Reconciliation;
+use crate::models::entities::transaction::transaction_type::TransactionType;
+use crate::models::entities::transaction::Transaction;
+use crate::models::entities::user::User;
+use crate::prelude::*;
+use crate::routes::importing::map_csv_record::MappedCsvRecord;
+use crate::services::external_account_matcher::ExternalAccountMatcher;
+use crate::services::planned_transaction_service::find_match;
+use crate::services::rule_service::{RuleService, RuleSubject};
+use crate::services::transfer_service::TransferService;
+use crate::shared::DbPool;
+use crate::utils::iban::normalize_iban;
+
+/// Creates a new import with the given records, regardless of the format the records were read
+/// from. Records that have already been imported before are skipped based on their follow number.
+///
+/// Returns what happened to each of the records. With `dry_run` the whole import is done the same
+/// way, but the database transaction is rolled back at the end, and records that cannot be
+/// imported are reported instead of failing the import.
+pub async fn import_records(
+    pool: &DbPool,
+    user_id: &str,
+    filename: String,
+    records: Vec<MappedCsvRecord>,
+    ordering: &CsvImportOrdering,
+    dry_run: bool,
+) -> Result<Vec<ImportRowDto>> {
+    // Start a database transaction.
+    let mut db_transaction = pool.begin().await?;
+
+    // Get required maps used when importing
+    let mut bank_account_map = get_bank_accounts_map(pool, user_id).await?;
+    let external_account_matcher = ExternalAccountMatcher::load(pool, user_id).await?;
+    let external_account_iban_map = get_external_account_ibans_map(pool, user_id).await?;
+    let mut order_indicator = get_order_indicator(pool, user_id).await?;
+    let rule_service = RuleService::load(pool, user_id).await?;
+    let locked_until = Reconciliation::locked_until(pool, user_id).await?;
+    let base_currency = User::base_currency(pool, user_id).await?;
+    let mut planned = PlannedTransaction::find_all(pool, user_id).await?;
+
+    // Create an import record where all the transactions will be added to.
+    let import_uuid = Uuid::new_v4();
+    let import = Import {
+        id: import_uuid.to_string(),
+        user_id: user_id.to_string(),
+        imported_at: Utc::now(),
+        filename,
+    };
+
+    // Create the parent import in the database
+    import.create(&mut *db_transaction).await?;
+
+    // If the first record is the newest, the order indicator should count down, so the indicator
+    // is set to the highest value for the import (the number of transactions to import)
+    if NewestFirst == *ordering {
+        order_indicator += records.len() as i32;
+    }
+
+    let mut rows = vec![];
+
+    for mapped_record in records {
+        let mut row = ImportRowDto {
+            row: mapped_record.row,
+            status: ImportRowStatus::Insert,
+            follow_number: Some(mapped_record.follow_number.to_string()),
+            new_bank_account: false,
+            planned_transaction_id: None,
+            transfer_transaction_id: None,
+            column: None,
+            error: None,
+        };
+
+        let (bank_account_id, bank_account_currency) = match bank_account_map
+            .get(&*mapped_record.account_iban)
+        {
+            Some(bank_account) => bank_account.clone(),
+            None => {
+                row.new_bank_account = true;
+
+                let bank_account = BankAccount {
+                    id: Uuid::new_v4().to_string(),
+                    iban: mapped_record.account_iban.to_string(),
+                    user_id: user_id.to_string(),
+                    name: mapped_record.account_iban.to_string(),
+                    description: "A new bank account".to_string(),
+                    hex_color: "ffffff".to_string(),
+                    currency: mapped_record
+                        .currency
+                        .clone()
+                        .unwrap_or_else(|| base_currency.to_string()),
+                };
+
+                bank_account.create(&mut *db_transaction).await?;
+
+                bank_account_map.insert(
+                    mapped_record.account_iban.to_string(),
+                    (bank_account.id.to_string(), bank_account.currency.to_string()),
+                );
+
+                (bank_account.id, bank_account.currency)
+            }
+        };
+
+        if NewestFirst == *ordering {
+            order_indicator -= 1;
+        } else {
+            order_indicator += 1;
+        }
+
+        let mut transaction = Transaction {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            transaction_type: TransactionType::Transaction,
+            follow_number: mapped_record.follow_number,
+            original_description: mapped_record.description.to_string(),
+            description: mapped_record.description,
+            complete_amount: mapped_record.amount,
+            amount: mapped_record.amount,
+            date: mapped_record.date,
+            bank_account_id: Some(bank_account_id.to_string()),
+            category_id: None,
+            parent_transaction_id: None,
+            external_account_name: mapped_record.external_account_name.to_string(),
+            external_account_id: None,
+            external_account_name_id: None,
+            external_account_iban: mapped_record
+                .external_account_iban
+                .as_deref()
+                .map(normalize_iban)
+                .filter(|iban| !iban.is_empty()),
+            parent_import_id: Some(import_uuid.to_string()),
+            subcategory_id: None,
+            order_indicator,
+            related_move_transaction: None,
+            related_transfer_transaction: None,
+            currency: mapped_record.currency.unwrap_or(bank_account_currency),
+        };
+
+        // The IBAN of the other party is more stable than the name, so it's matched first.
+        let iban_match = transaction
+            .external_account_iban
+            .as_ref()
+            .and_then(|iban| external_account_iban_map.get(iban));
+
+        if let Some((external_id, category_id)) = iban_match {
+            transaction.external_account_id = Some(external_id.to_string());
+            transaction.category_id = category_id.to_owned();
+        } else if let Some(pattern) =
+            external_account_matcher.find(&mapped_record.external_account_name)
+        {
+            transaction.external_account_id = Some(pattern.external_account_id.to_string());
+            transaction.external_account_name_id = Some(pattern.name_id.to_string());
+            transaction.category_id = pattern.default_category_id.to_owned();
+        }
+
+        // Rules are applied after the external account mapping so the user is able to override
+        // the default category of an external account.
+        let outcome = rule_service.evaluate(&RuleSubject {
+            description: &transaction.original_description,
+            amount: transaction.amount,
+            external_account_iban: transaction.external_account_iban.as_deref(),
+            bank_account_id: &bank_account_id,
+            date: transaction.date,
+        });
+
+        if let Some((category_id, subcategory_id)) = outcome.category {
+            transaction.category_id = Some(category_id);
+            transaction.subcategory_id = subcategory_id;
+        }
+
+        if let Some(description) = outcome.description {
+            transaction.description = description;
+        }
+
+        if let Some(external_account_id) = outcome.external_account_id {
+            // The matched name entry belongs to the account the rule replaces.
+            if transaction.external_account_id.as_ref() != Some(&external_account_id) {
+                transaction.external_account_name_id = None;
+            }
+
+            transaction.external_account_id = Some(external_account_id);
+        }
+
+        // Because Postgres does an implicit rollback when a statement fails, a savepoint is created
+        // so if the insert fails like we expect, the savepoint is the one that is implicitly
+        // rolled back instead of the actual transaction.
+        sqlx::query!("SAVEPOINT T")
+            .execute(&mut *db_transaction)
+            .await?;
+
+        let result = transaction.create(&mut *db_transaction).await;
+
+        // If the result is Ok the transactions is guaranteed to be a new transaction. A new
+        // transaction inside a reconciled period would change the reconciled balance, so the
+        // import is rejected.
+        if result.is_ok() {
+            let locked = locked_until
+                .get(&bank_account_id)
+                .is_some_and(|until| transaction.date <= *until);
+
+            if locked && !dry_run {
+                return Err(Reconciliation::locked_error());
+            }
+
+            if locked {
+                row.status = ImportRowStatus::Error;
+                row.error = Some("The date is part of a reconciled balance".to_string());
+            } else if let Some(index) = find_match(&planned, &transaction) {
+                // The planned transaction is advanced right away, so a later row of the same
+                // import can fulfil its next occurrence.
+                planned[index]
+                    .link_and_advance(&mut *db_transaction, &transaction.id)
+                    .await?;
+
+                row.planned_transaction_id = Some(planned[index].id.to_string());
+            }
+
+            // When the money went to or came from another bank account of the user, the other leg
+            // of the transfer may already be imported, possibly earlier in this same import.
+            let transfer_account_id = transaction
+                .external_account_iban
+                .as_ref()
+                .and_then(|counter_iban| {
+                    bank_account_map
+                        .iter()
+                        .find(|(iban, _)| normalize_iban(iban) == *counter_iban)
+                })
+                .map(|(_, (id, _))| id.to_string())
+                .filter(|id| *id != bank_account_id);
+
+            if let Some(transfer_account_id) = transfer_account_id {
+                let counterpart = TransferService::find_counterpart(
+                    &mut db_transaction,
+                    &transaction,
+                    &transfer_account_id,
+                )
+                .await?;
+
+                if let Some(counterpart) = counterpart {
+                    TransferService::set_link(
+                        &mut db_transaction,
+                        user_id,
+                        &transaction.id,
+                        &counterpart,
+                    )
+                    .await?;
+
+                    row.transfer_transaction_id = Some(counterpart);
+                }
+            }
+
+            rows.push(row);
+            continue;
+        }
+
+        sqlx::query!("ROLLBACK TO T")
+            .execute(&mut *db_transaction)
+            .await?;
+
+        // If the database returned an Err, the transaction may be a duplicate, so that is checked
+        // here and if it is a duplicate, a link is created between the duplicate transaction and
+        // the import record.
+        let error = result.expect_err("Was Ok but also an error?");
+
+        let Sqlx(wrapped_error) = &error else {
+            return Err(error);
+        };
+
+        let Some(constraint) = wrapped_error.get_constraint() else {
+            return Err(error);
+        };
+
+        // If the Err was not caused by the unique constrained the error import fails.
+        if constraint != "unique_follow_number" {
+            return Err(error);
+        }
+
+        sqlx::query!(
+            r#"
+                INSERT INTO skipped_transactions
+                VALUES ($1, $2, $3);
+            "#,
+            import_uuid.to_string(),
+            user_id.to_string(),
+            transaction.follow_number
+        )
+        .execute(&mut *db_transaction)
+        .await?;
+
+        row.status = ImportRowStatus::Duplicate;
+        rows.push(row);
+    }
+
+    if dry_run {
+        db_transaction.rollback().await?;
+    } else {
+        db_transaction.commit().await?;
+    }
+
+    Ok(rows)
+}
+
+/// Returns the id and currency of the bank accounts of the user by their IBAN.
+async fn get_bank_accounts_map(
+    pool: &DbPool,
+    user_id: &str,
+) -> Result<HashMap<String, (String, String)>> {
+    let records = sqlx::query!(
+        r#"
+            SELECT id, iban, currency
+            FROM bank_accounts
+            WHERE user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut map = HashMap::new();
+
+    for record in records {
+        map.insert(record.iban, (record.id, record.currency));
+    }
+
+    Ok(map)
+}
+
+/// Returns the id and default category of the external accounts of the user by their IBANs.
+async fn get_external_account_ibans_map(
+    pool: &DbPool,
+    user_id: &str,
+) -> Result<HashMap<String, (String, Option<String>)>> {
+    let records = sqlx::query!(
+        r#"
+            SELECT external_account_ibans.iban, parent_external_account, e.default_category_id
+            FROM external_account_ibans
+            INNER JOIN external_accounts e ON e.id = external_account_ibans.parent_external_account
+            WHERE external_account_ibans.user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut map = HashMap::new();
+
+    for record in records {
+        map.insert(
+            record.iban,
+            (record.parent_external_account, record.default_category_id),
+        );
+    }
+
+    Ok(map)
+}
+
+async fn get_order_indicator(pool: &DbPool, user_id: &str) -> Result<i32> {
+    let record = sqlx::query!(
+        r#"
+            SELECT MAX(order_indicator) AS max_indicator
+            FROM transactions
+            WHERE user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(record.max_indicator.unwrap_or(0))
+}
@@ -1,71 +1,195 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, ParseError, Utc};
 use csv::StringRecord;
 
 use crate::error::import_error::ImportError;
-use crate::models::csv::csv_mapping::{AmountMapping, CsvMapping, DateMapping};
+use crate::models::csv::csv_mapping::{AmountMapping, CsvColumn, CsvMapping, DateMapping};
 use crate::prelude::*;
+use crate::utils::currency::{is_currency_code, minor_units, DEFAULT_CURRENCY};
 
 #[derive(Debug)]
 pub struct MappedCsvRecord {
+    /// Where the record was found in the file, used when reporting on the import. For CSV files
+    /// this is the line number and for statements the position of the entry in the statement.
+    pub row: u64,
+
     pub account_iban: String,
     pub date: DateTime<Utc>,
     pub follow_number: String,
     pub description: String,
     pub amount: i64,
     pub external_account_name: String,
+    pub currency: Option<String>,
+    pub external_account_iban: Option<String>,
+    pub value_date: Option<DateTime<Utc>>,
 }
 
-pub fn map_csv_record(record: StringRecord, mapping: &CsvMapping) -> Result<MappedCsvRecord> {
-    let follow_number = record
-        .get(mapping.follow_number as usize)
-        .ok_or(ImportError::missing_column("follow_number"))?
+/// Maps a record of the CSV to the fields of a transaction. Columns that are mapped by name are
+/// looked up in the header of the CSV. Values that cannot be read are reported together with the
+/// row and the field they belong to.
+pub fn map_csv_record(
+    record: StringRecord,
+    header: &StringRecord,
+    mapping: &CsvMapping,
+) -> Result<MappedCsvRecord> {
+    let row = record_row(&record);
+
+    let follow_number = get_column(&record, header, &mapping.follow_number, "follow_number")?
         .to_string();
 
-    let description = record
-        .get(mapping.description as usize)
-        .ok_or(ImportError::missing_column("description"))?
+    let description = get_column(&record, header, &mapping.description, "description")?
         .to_string();
 
-    let temp_amount = record
-        .get(mapping.amount as usize)
-        .ok_or(ImportError::missing_column("amount"))?
-        .replace('+', "")
-        .replace(',', ".")
-        .parse::<f64>()?;
+    let currency = get_optional_column(&record, header, mapping.currency.as_ref(), "currency")?
+        .map(str::to_string)
+        .or_else(|| mapping.default_currency.clone())
+        .map(|currency| currency.to_uppercase());
+
+    if let Some(currency) = &currency {
+        if !is_currency_code(currency) {
+            return Err(ImportError::invalid_value(row, "currency", "Not a currency code").into());
+        }
+    }
 
-    let amount: i64 = match mapping.amount_mapping {
-        AmountMapping::Cents => temp_amount as i64,
-        AmountMapping::Euro => (temp_amount * 100_f64) as i64,
+    // Without a currency the amount is assumed to have two decimals, like most currencies.
+    let decimals = match mapping.amount_mapping {
+        AmountMapping::Cents => 0,
+        AmountMapping::Decimal => minor_units(currency.as_deref().unwrap_or(DEFAULT_CURRENCY)),
     };
 
-    let date_string = record
-        .get(mapping.date as usize)
-        .ok_or(ImportError::missing_column("date"))?
-        .to_string();
+    let parse_amount = |value: &str| {
+        mapping
+            .amount_format
+            .parse(value, decimals)
+            .map_err(|error| ImportError::invalid_value(row, "amount", error.to_string()))
+    };
 
-    let date = map_datetime(&date_string, &mapping.date_mapping)?;
+    let mut amount = match &mapping.credit_amount {
+        Some(credit_column) => {
+            let debit = get_optional_column(&record, header, Some(&mapping.amount), "amount")?;
+            let credit =
+                get_optional_column(&record, header, Some(credit_column), "credit_amount")?;
 
-    let account_iban = record
-        .get(mapping.account_iban as usize)
-        .ok_or(ImportError::missing_column("iban"))?
-        .to_string();
+            if debit.is_none() && credit.is_none() {
+                return Err(ImportError::invalid_value(
+                    row,
+                    "amount",
+                    "Neither the debit nor the credit column has a value",
+                )
+                .into());
+            }
 
-    let external_account_name = record
-        .get(mapping.external_account_name as usize)
-        .ok_or("Column for external_account_name does not exist")?
-        .to_string();
+            let debit = debit.map(parse_amount).transpose()?.unwrap_or_default();
+            let credit = credit.map(parse_amount).transpose()?.unwrap_or_default();
+
+            credit.abs() - debit.abs()
+        }
+        None => parse_amount(get_column(&record, header, &mapping.amount, "amount")?)?,
+    };
+
+    if let Some(debit_credit) = &mapping.debit_credit {
+        let indicator = get_column(&record, header, &debit_credit.column, "debit_credit")?;
+
+        amount = if indicator.trim().eq_ignore_ascii_case(debit_credit.debit.trim()) {
+            -amount.abs()
+        } else {
+            amount.abs()
+        };
+    }
+
+    let date_string = get_column(&record, header, &mapping.date, "date")?.to_string();
+
+    let date = map_datetime(&date_string, &mapping.date_mapping)
+        .map_err(|error| ImportError::invalid_value(row, "date", error.to_string()))?;
+
+    let account_iban = get_column(&record, header, &mapping.account_iban, "iban")?.to_string();
+
+    let external_account_name = get_column(
+        &record,
+        header,
+        &mapping.external_account_name,
+        "external_account_name",
+    )?
+    .to_string();
+
+    let external_account_iban = get_optional_column(
+        &record,
+        header,
+        mapping.external_account_iban.as_ref(),
+        "external_account_iban",
+    )?
+    .map(str::to_string);
+
+    let value_date = get_optional_column(
+        &record,
+        header,
+        mapping.value_date.as_ref(),
+        "value_date",
+    )?
+    .map(|value| map_datetime(&value.to_string(), &mapping.date_mapping))
+    .transpose()
+    .map_err(|error| ImportError::invalid_value(row, "value_date", error.to_string()))?;
 
     Ok(MappedCsvRecord {
+        row,
         account_iban,
         date,
         follow_number,
         description,
         amount,
         external_account_name,
+        currency,
+        external_account_iban,
+        value_date,
     })
 }
 
-fn map_datetime(col_value: &String, date_mapping: &DateMapping) -> Result<DateTime<Utc>> {
+fn get_column<'a>(
+    record: &'a StringRecord,
+    header: &StringRecord,
+    column: &CsvColumn,
+    field: &str,
+) -> Result<&'a str> {
+    let index = column
+        .resolve(header)
+        .ok_or(ImportError::missing_column(field))?;
+
+    let value = record.get(index).ok_or(ImportError::invalid_value(
+        record_row(record),
+        field,
+        "The row does not have this column",
+    ))?;
+
+    Ok(value)
+}
+
+/// Returns the line number of the record in the CSV.
+pub fn record_row(record: &StringRecord) -> u64 {
+    record
+        .position()
+        .map(|position| position.line())
+        .unwrap_or_default()
+}
+
+/// Reads an optional column. Empty values are treated the same as an unmapped column.
+fn get_optional_column<'a>(
+    record: &'a StringRecord,
+    header: &StringRecord,
+    column: Option<&CsvColumn>,
+    field: &str,
+) -> Result<Option<&'a str>> {
+    let Some(column) = column else {
+        return Ok(None);
+    };
+
+    let value = get_column(record, header, column, field)?.trim();
+
+    Ok(Some(value).filter(|value| !value.is_empty()))
+}
+
+fn map_datetime(
+    col_value: &String,
+    date_mapping: &DateMapping,
+) -> std::result::Result<DateTime<Utc>, ParseError> {
     let mut working_value = col_value.to_string();
 
     if let Some(template) = &date_mapping.template {
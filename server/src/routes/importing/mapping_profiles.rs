@@ -0,0 +1,171 @@
+use rocket::serde::json::Json;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::models::csv::csv_mapping::CsvMapping;
+use crate::models::dto::importing::csv_mapping_profile_dto::CsvMappingProfileDto;
+use crate::models::dto::importing::csv_mapping_suggestion_dto::CsvMappingSuggestionDto;
+use crate::models::dto::importing::detect_csv_mapping_dto::DetectCsvMappingDto;
+use crate::models::dto::importing::new_csv_mapping_profile_dto::NewCsvMappingProfileDto;
+use crate::models::entities::csv_mapping_profile::CsvMappingProfile;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::routes::importing::guess_csv_mapping::{guess_csv_mapping, normalize_header, parse_header};
+use crate::shared::SharedPool;
+
+#[get("/profiles")]
+pub async fn get_all_csv_mapping_profiles(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<CsvMappingProfileDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all CSV mapping profiles for user '{}'", user);
+    let profiles = CsvMappingProfile::find_all(pool, &user.uuid).await?;
+
+    Ok(Json(profiles.into_iter().map(CsvMappingProfileDto::from).collect()))
+}
+
+#[post("/profiles", data = "<body>")]
+pub async fn create_csv_mapping_profile(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<NewCsvMappingProfileDto>,
+) -> Result<Json<CsvMappingProfileDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    let profile = CsvMappingProfile {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        name: body.name,
+        header: normalize_profile_header(body.header.as_deref())?,
+        mapping: body.mapping,
+    };
+
+    debug!("Creating new CSV mapping profile for user '{}'", user);
+    profile.create(inner_pool).await?;
+
+    debug!("Created CSV mapping profile '{}'", profile.id);
+    get_csv_mapping_profile_by_id(pool, user, &profile.id).await
+}
+
+#[get("/profiles/<id>")]
+pub async fn get_csv_mapping_profile_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<CsvMappingProfileDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying CSV mapping profile with id '{}'", id);
+    let profile = CsvMappingProfile::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(profile.into()))
+}
+
+#[put("/profiles/<id>", data = "<body>")]
+pub async fn update_csv_mapping_profile(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    body: Json<NewCsvMappingProfileDto>,
+) -> Result<Json<CsvMappingProfileDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+    let mapping = &body.mapping;
+
+    debug!("Executing CSV mapping profile guard for id '{}' with user '{}'", id, user);
+    CsvMappingProfile::guard_one(inner_pool, id, &user.uuid).await?;
+
+    debug!("Updating CSV mapping profile with id '{}'", id);
+    sqlx::query!(
+        r#"
+            UPDATE csv_mapping_profiles
+            SET name = $3, header = $4, date_template = $5, date_format = $6, amount_mapping = $7,
+                ordering = $8, columns = $9
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid,
+        body.name,
+        normalize_profile_header(body.header.as_deref())?,
+        mapping.date_mapping.template,
+        mapping.date_mapping.format,
+        <&str>::from(mapping.amount_mapping),
+        <&str>::from(mapping.ordering),
+        CsvMappingProfile::serialize_columns(mapping)?,
+    )
+    .execute(inner_pool)
+    .await?;
+
+    debug!("Updated CSV mapping profile '{}'", id);
+    get_csv_mapping_profile_by_id(pool, user, id).await
+}
+
+#[delete("/profiles/<id>")]
+pub async fn delete_csv_mapping_profile(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing CSV mapping profile guard for id '{}' with user '{}'", id, user);
+    CsvMappingProfile::guard_one(pool, id, &user.uuid).await?;
+
+    debug!("Deleting CSV mapping profile with id '{}'", id);
+    sqlx::query!(
+        r#"
+            DELETE FROM csv_mapping_profiles
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Suggests a mapping for a CSV based on its header row. When the user saved a profile for the
+/// same header, that profile is returned. Otherwise the mapping is guessed from the column names.
+#[post("/csv/detect", data = "<body>")]
+pub async fn detect_csv_mapping(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<DetectCsvMappingDto>,
+) -> Result<Json<CsvMappingSuggestionDto>> {
+    let pool = db_inner!(pool);
+
+    let header = parse_header(&body.header)?;
+
+    trace!("Finding CSV mapping profile for header");
+    let profile =
+        CsvMappingProfile::find_by_header(pool, &normalize_header(&header), &user.uuid).await?;
+
+    let mapping: Option<CsvMapping> = match &profile {
+        Some(profile) => Some(profile.mapping.clone()),
+        None => guess_csv_mapping(&header, &[]),
+    };
+
+    let unknown_columns = mapping
+        .as_ref()
+        .map(|mapping| mapping.unknown_columns(&header))
+        .unwrap_or_default();
+
+    Ok(Json(CsvMappingSuggestionDto {
+        profile: profile.map(CsvMappingProfileDto::from),
+        mapping,
+        unknown_columns,
+    }))
+}
+
+fn normalize_profile_header(header: Option<&str>) -> Result<Option<String>> {
+    let Some(header) = header else {
+        return Ok(None);
+    };
+
+    Ok(Some(normalize_header(&parse_header(header)?)))
+}
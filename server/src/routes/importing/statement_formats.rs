@@ -0,0 +1,89 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+use crate::error::import_error::ImportError;
+use crate::models::csv::amount_format::AmountFormat;
+use crate::models::dto::importing::import_statement_dto::StatementFormat;
+use crate::prelude::*;
+use crate::routes::importing::map_csv_record::MappedCsvRecord;
+use crate::utils::iban::normalize_iban;
+
+pub mod camt053;
+pub mod mt940;
+pub mod ofx;
+
+/// Reads the records from a statement in the given format.
+pub fn parse_statement(format: StatementFormat, content: &str) -> Result<Vec<MappedCsvRecord>> {
+    match format {
+        StatementFormat::Ofx => ofx::parse(content),
+        StatementFormat::Camt053 => camt053::parse(content),
+        StatementFormat::Mt940 => mt940::parse(content),
+    }
+}
+
+/// Builds the follow number of a transaction from its reference. Banks only keep references unique
+/// per account, while follow numbers have to be unique for the user, so the account is included.
+fn follow_number(account: &str, reference: &str) -> String {
+    format!("{}-{}", normalize_iban(account), reference)
+}
+
+/// Parses a decimal amount like '-12.30' or '12,3' into cents. The statement formats don't use
+/// thousands separators, so the default [AmountFormat] is used.
+fn parse_amount(value: &str) -> Result<i64> {
+    let amount = AmountFormat::default().parse(value, 2).map_err(|_| {
+        ImportError::invalid_statement(format!("'{}' is not a valid amount", value.trim()))
+    })?;
+
+    Ok(amount)
+}
+
+/// Parses a date in the given format to midnight UTC of that day, as the statement formats only
+/// reliably contain the booking date.
+fn parse_date(value: &str, format: &str) -> Result<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(value, format).map_err(|_| {
+        ImportError::invalid_statement(format!("'{}' is not a valid date", value))
+    })?;
+
+    Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("Midnight is a valid time")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::dto::importing::import_statement_dto::StatementFormat;
+    use crate::routes::importing::statement_formats::parse_amount;
+
+    #[test]
+    fn amounts_are_parsed_exactly() {
+        assert_eq!(parse_amount("12.34").unwrap(), 1234);
+        assert_eq!(parse_amount("-0,1").unwrap(), -10);
+        assert_eq!(parse_amount("+1000").unwrap(), 100000);
+        assert_eq!(parse_amount("19.990").unwrap(), 1999);
+        assert_eq!(parse_amount("1234,").unwrap(), 123400);
+    }
+
+    #[test]
+    fn invalid_amounts_are_rejected() {
+        assert!(parse_amount("").is_err());
+        assert!(parse_amount("1.234").is_err());
+        assert!(parse_amount("1.000,00").is_err());
+        assert!(parse_amount("12a").is_err());
+    }
+
+    #[test]
+    fn formats_are_detected() {
+        assert_eq!(
+            StatementFormat::detect("OFXHEADER:100\nDATA:OFXSGML"),
+            Some(StatementFormat::Ofx)
+        );
+        assert_eq!(
+            StatementFormat::detect(
+                r#"<?xml version="1.0"?><Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">"#
+            ),
+            Some(StatementFormat::Camt053)
+        );
+        assert_eq!(
+            StatementFormat::detect(":20:STATEMENT\n:25:NL12RABO0123456789\n:61:230115D12,34NTRFNONREF"),
+            Some(StatementFormat::Mt940)
+        );
+        assert_eq!(StatementFormat::detect("iban,date,amount"), None);
+    }
+}
@@ -0,0 +1,201 @@
+use roxmltree::{Document, Node};
+
+use crate::error::import_error::ImportError;
+use crate::prelude::*;
+use crate::routes::importing::map_csv_record::MappedCsvRecord;
+use crate::routes::importing::statement_formats::{follow_number, parse_amount, parse_date};
+
+/// Reads the entries from a CAMT.053 statement. Elements are matched on their local name only, so
+/// the different versions of the camt.053 namespace are all supported.
+pub fn parse(content: &str) -> Result<Vec<MappedCsvRecord>> {
+    let document = Document::parse(content.trim_start_matches('\u{feff}'))
+        .map_err(|error| ImportError::invalid_statement(error.to_string()))?;
+
+    let mut records = vec![];
+
+    for statement in descendants(document.root(), "Stmt") {
+        let account = child(statement, "Acct")
+            .and_then(|account| child(account, "Id"))
+            .and_then(|id| {
+                text(child(id, "IBAN")).or_else(|| text(child(id, "Othr").and_then(|other| child(other, "Id"))))
+            })
+            .ok_or(ImportError::invalid_statement("A statement does not contain an account"))?;
+
+        for entry in statement.children().filter(|node| has_name(node, "Ntry")) {
+            records.push(parse_entry(entry, &account, records.len() as u64 + 1)?);
+        }
+    }
+
+    Ok(records)
+}
+
+fn parse_entry(entry: Node, account: &str, row: u64) -> Result<MappedCsvRecord> {
+    let amount_node = child(entry, "Amt");
+    let amount = text(amount_node)
+        .ok_or(ImportError::invalid_statement("An entry does not have an amount"))?;
+    let currency = amount_node
+        .and_then(|node| node.attribute("Ccy"))
+        .map(str::to_string);
+
+    let is_debit = text(child(entry, "CdtDbtInd")).as_deref() == Some("DBIT");
+
+    let amount = parse_amount(&amount)?.abs();
+    let amount = if is_debit { -amount } else { amount };
+
+    // Both a date and a datetime are allowed here, but only the date part is used.
+    let date = child(entry, "BookgDt")
+        .and_then(|date| text(child(date, "Dt")).or_else(|| text(child(date, "DtTm"))))
+        .ok_or(ImportError::invalid_statement("An entry does not have a booking date"))?;
+
+    let details = descendants(entry, "TxDtls").next();
+
+    let reference = text(child(entry, "AcctSvcrRef"))
+        .or_else(|| text(child(entry, "NtryRef")))
+        .or_else(|| {
+            details
+                .and_then(|details| child(details, "Refs"))
+                .and_then(|refs| text(child(refs, "EndToEndId")))
+                .filter(|reference| reference != "NOTPROVIDED")
+        })
+        .ok_or(ImportError::invalid_statement("An entry does not have a reference"))?;
+
+    let description = details
+        .and_then(|details| child(details, "RmtInf"))
+        .map(|info| {
+            info.children()
+                .filter(|node| has_name(node, "Ustrd"))
+                .filter_map(|node| node.text())
+                .map(str::trim)
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .filter(|description| !description.is_empty())
+        .or_else(|| text(child(entry, "AddtlNtryInf")))
+        .unwrap_or_default();
+
+    // The other party is the creditor when money is sent and the debtor when money is received.
+    let party = if is_debit { "Cdtr" } else { "Dbtr" };
+    let parties = details.and_then(|details| child(details, "RltdPties"));
+    let external_account_name = parties
+        .and_then(|parties| child(parties, party))
+        .and_then(|party| descendants(party, "Nm").next())
+        .and_then(|name| text(Some(name)))
+        .unwrap_or_default();
+    let external_account_iban = parties
+        .and_then(|parties| child(parties, &format!("{}Acct", party)))
+        .and_then(|account| child(account, "Id"))
+        .and_then(|id| text(child(id, "IBAN")));
+
+    let value_date = child(entry, "ValDt")
+        .and_then(|date| text(child(date, "Dt")).or_else(|| text(child(date, "DtTm"))))
+        .map(|date| parse_date(date.get(..10).unwrap_or(&date), "%Y-%m-%d"))
+        .transpose()?;
+
+    Ok(MappedCsvRecord {
+        row,
+        account_iban: account.to_string(),
+        date: parse_date(date.get(..10).unwrap_or(&date), "%Y-%m-%d")?,
+        follow_number: follow_number(account, &reference),
+        description,
+        amount,
+        external_account_name,
+        currency,
+        external_account_iban,
+        value_date,
+    })
+}
+
+fn has_name(node: &Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| has_name(child, name))
+}
+
+fn descendants<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.descendants()
+        .filter(move |descendant| has_name(descendant, name))
+}
+
+fn text(node: Option<Node>) -> Option<String> {
+    node.and_then(|node| node.text())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::importing::statement_formats::camt053::parse;
+
+    const STATEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+            <BkToCstmrStmt>
+                <Stmt>
+                    <Id>statement-1</Id>
+                    <Acct><Id><IBAN>NL12RABO0123456789</IBAN></Id></Acct>
+                    <Ntry>
+                        <Amt Ccy="EUR">12.34</Amt>
+                        <CdtDbtInd>DBIT</CdtDbtInd>
+                        <BookgDt><Dt>2023-01-15</Dt></BookgDt>
+                        <ValDt><Dt>2023-01-14</Dt></ValDt>
+                        <AcctSvcrRef>2023011501</AcctSvcrRef>
+                        <NtryDtls><TxDtls>
+                            <RltdPties>
+                                <Cdtr><Nm>Jumbo</Nm></Cdtr>
+                                <CdtrAcct><Id><IBAN>NL99INGB0123456789</IBAN></Id></CdtrAcct>
+                            </RltdPties>
+                            <RmtInf><Ustrd>Groceries</Ustrd><Ustrd>week 3</Ustrd></RmtInf>
+                        </TxDtls></NtryDtls>
+                    </Ntry>
+                    <Ntry>
+                        <Amt Ccy="EUR">3000</Amt>
+                        <CdtDbtInd>CRDT</CdtDbtInd>
+                        <BookgDt><DtTm>2023-01-20T10:00:00+01:00</DtTm></BookgDt>
+                        <NtryDtls><TxDtls>
+                            <Refs><EndToEndId>salary-january</EndToEndId></Refs>
+                            <RltdPties><Dbtr><Pty><Nm>Work</Nm></Pty></Dbtr></RltdPties>
+                        </TxDtls></NtryDtls>
+                        <AddtlNtryInf>Salary</AddtlNtryInf>
+                    </Ntry>
+                </Stmt>
+            </BkToCstmrStmt>
+        </Document>"#;
+
+    #[test]
+    fn entries_are_parsed() {
+        let records = parse(STATEMENT).unwrap();
+
+        assert_eq!(records.len(), 2);
+
+        let groceries = records.get(0).unwrap();
+        assert_eq!(groceries.account_iban, "NL12RABO0123456789");
+        assert_eq!(groceries.date.to_rfc3339(), "2023-01-15T00:00:00+00:00");
+        assert_eq!(groceries.follow_number, "NL12RABO0123456789-2023011501");
+        assert_eq!(groceries.description, "Groceries week 3");
+        assert_eq!(groceries.amount, -1234);
+        assert_eq!(groceries.external_account_name, "Jumbo");
+        assert_eq!(groceries.currency.as_deref(), Some("EUR"));
+        assert_eq!(groceries.external_account_iban.as_deref(), Some("NL99INGB0123456789"));
+        assert_eq!(
+            groceries.value_date.unwrap().to_rfc3339(),
+            "2023-01-14T00:00:00+00:00"
+        );
+
+        let salary = records.get(1).unwrap();
+        assert_eq!(salary.date.to_rfc3339(), "2023-01-20T00:00:00+00:00");
+        assert_eq!(salary.follow_number, "NL12RABO0123456789-salary-january");
+        assert_eq!(salary.description, "Salary");
+        assert_eq!(salary.amount, 300000);
+        assert_eq!(salary.external_account_name, "Work");
+        assert_eq!(salary.external_account_iban, None);
+    }
+
+    #[test]
+    fn invalid_xml_is_rejected() {
+        assert!(parse("<Document><Stmt>").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::error::import_error::ImportError;
+use crate::prelude::*;
+use crate::routes::importing::map_csv_record::MappedCsvRecord;
+use crate::routes::importing::statement_formats::{follow_number, parse_amount, parse_date};
+
+/// Reads the statement lines from an MT940 file. A file can contain multiple statements, each
+/// starting with a `:20:` field.
+pub fn parse(content: &str) -> Result<Vec<MappedCsvRecord>> {
+    let mut statement_reference = String::new();
+    let mut account_iban: Option<String> = None;
+    let mut statement_index = 0;
+    let mut records: Vec<MappedCsvRecord> = vec![];
+
+    for (tag, value) in fields(content) {
+        match tag.as_str() {
+            "20" => {
+                statement_reference = value;
+                statement_index = 0;
+            }
+            "25" => account_iban = Some(parse_account(&value)),
+            "61" => {
+                let account_iban = account_iban.clone().ok_or(ImportError::invalid_statement(
+                    "A statement line was found before the account",
+                ))?;
+
+                let line = parse_statement_line(&value)?;
+
+                // Not every bank provides a reference, so in that case one is made up from the
+                // position within the statement, which is stable when the same statement is
+                // imported again, also as part of a file with other statements.
+                let reference = line.reference.unwrap_or_else(|| {
+                    format!("{}-{}-{}", statement_reference, line.date, statement_index)
+                });
+
+                let follow_number = follow_number(&account_iban, &reference);
+                statement_index += 1;
+
+                records.push(MappedCsvRecord {
+                    row: records.len() as u64 + 1,
+                    account_iban,
+                    date: parse_date(&line.date, "%y%m%d")?,
+                    follow_number,
+                    description: String::new(),
+                    amount: line.amount,
+                    external_account_name: String::new(),
+                    currency: None,
+                    external_account_iban: None,
+                    value_date: None,
+                });
+            }
+            "86" => {
+                // The information belongs to the statement line right before it.
+                if let Some(record) = records.last_mut() {
+                    let (description, name) = parse_information(&value);
+                    record.description = description;
+                    record.external_account_name = name;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+/// Splits the content into its fields. A field starts with a tag like `:61:` and continues on the
+/// following lines until the next tag.
+fn fields(content: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = vec![];
+
+    for line in content.lines() {
+        let line = line.trim_end();
+
+        if let Some((tag, value)) = line.strip_prefix(':').and_then(|line| line.split_once(':')) {
+            fields.push((tag.to_string(), value.to_string()));
+            continue;
+        }
+
+        // The end of a message and the SWIFT headers around it are not part of any field.
+        if line == "-" || line.starts_with('{') || line.starts_with("-}") {
+            continue;
+        }
+
+        if let Some((_, value)) = fields.last_mut() {
+            value.push_str(line);
+        }
+    }
+
+    fields
+}
+
+/// The account can be followed by a currency or be prefixed by the BIC of the bank.
+fn parse_account(value: &str) -> String {
+    let account = value.rsplit('/').next().unwrap_or(value).trim();
+
+    let has_currency = account.len() > 3
+        && account[account.len() - 3..]
+            .chars()
+            .all(|char| char.is_ascii_uppercase())
+        && account[..account.len() - 3]
+            .chars()
+            .last()
+            .is_some_and(|char| char.is_ascii_digit() || char.is_whitespace());
+
+    if has_currency {
+        return account[..account.len() - 3].trim().to_string();
+    }
+
+    account.to_string()
+}
+
+#[derive(Debug)]
+struct StatementLine {
+    date: String,
+    amount: i64,
+    reference: Option<String>,
+}
+
+/// Parses a `:61:` field, which looks like `2301150115D12,34NTRFREFERENCE//BANKREFERENCE`: the
+/// value date, an optional entry date, the debit/credit mark, an optional funds code, the amount,
+/// the transaction type and the references.
+fn parse_statement_line(value: &str) -> Result<StatementLine> {
+    let invalid = || ImportError::invalid_statement(format!("Invalid statement line '{}'", value));
+
+    if !value.is_ascii() || value.len() < 7 {
+        return Err(invalid().into());
+    }
+
+    let date = value[..6].to_string();
+    let mut rest = &value[6..];
+
+    if rest.len() >= 4 && rest[..4].chars().all(|char| char.is_ascii_digit()) {
+        rest = &rest[4..];
+    }
+
+    // A reversal of a credit is a debit and the other way around.
+    let (negative, rest) = if let Some(rest) = rest.strip_prefix("RC") {
+        (true, rest)
+    } else if let Some(rest) = rest.strip_prefix("RD") {
+        (false, rest)
+    } else if let Some(rest) = rest.strip_prefix('C') {
+        (false, rest)
+    } else if let Some(rest) = rest.strip_prefix('D') {
+        (true, rest)
+    } else {
+        return Err(invalid().into());
+    };
+
+    let rest = match rest.chars().next() {
+        Some(char) if char.is_ascii_alphabetic() => &rest[1..],
+        _ => rest,
+    };
+
+    let amount_end = rest
+        .find(|char: char| !char.is_ascii_digit() && char != ',')
+        .unwrap_or(rest.len());
+
+    let amount = parse_amount(&rest[..amount_end])?;
+
+    // The transaction type is always four characters, like 'NTRF'.
+    let references = rest.get(amount_end + 4..).unwrap_or("");
+    let (customer_reference, bank_reference) = references
+        .split_once("//")
+        .unwrap_or((references, ""));
+
+    let reference = [customer_reference, bank_reference]
+        .into_iter()
+        .map(str::trim)
+        .find(|reference| !reference.is_empty() && *reference != "NONREF")
+        .map(str::to_string);
+
+    Ok(StatementLine {
+        date,
+        amount: if negative { -amount } else { amount },
+        reference,
+    })
+}
+
+/// Returns the description and the name of the other party from a `:86:` field. Structured
+/// information uses codes like `/NAME/` and `/REMI/`, otherwise the whole field is the
+/// description.
+fn parse_information(value: &str) -> (String, String) {
+    let subfield = |code: &str| {
+        let start = value.find(&format!("/{}/", code))? + code.len() + 2;
+        let rest = &value[start..];
+        let rest = rest.strip_prefix("USTD//").unwrap_or(rest);
+
+        Some(rest.split('/').next().unwrap_or(rest).trim().to_string())
+    };
+
+    let name = subfield("NAME").unwrap_or_default();
+    let description = subfield("REMI").unwrap_or_else(|| value.trim().to_string());
+
+    (description, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::importing::statement_formats::mt940::{parse, parse_account};
+
+    #[test]
+    fn statement_lines_are_parsed() {
+        let content = [
+            ":20:STATEMENT-1",
+            ":25:NL12RABO0123456789 EUR",
+            ":28C:1",
+            ":60F:C230101EUR1000,00",
+            ":61:2301150115D12,34NTRF2023011501//BANK-1",
+            ":86:/NAME/Jumbo/REMI/USTD//Groceries",
+            " week 3/",
+            ":61:230120C3000,NTRFNONREF",
+            ":86:Salary january",
+            ":61:230125RD5,00NTRFNONREF",
+            ":62F:C230131EUR3992,66",
+            "-",
+        ]
+        .join("\r\n");
+
+        let records = parse(&content).unwrap();
+
+        assert_eq!(records.len(), 3);
+
+        let groceries = records.get(0).unwrap();
+        assert_eq!(groceries.account_iban, "NL12RABO0123456789");
+        assert_eq!(groceries.date.to_rfc3339(), "2023-01-15T00:00:00+00:00");
+        assert_eq!(groceries.follow_number, "NL12RABO0123456789-2023011501");
+        assert_eq!(groceries.description, "Groceries week 3");
+        assert_eq!(groceries.amount, -1234);
+        assert_eq!(groceries.external_account_name, "Jumbo");
+
+        let salary = records.get(1).unwrap();
+        assert_eq!(salary.follow_number, "NL12RABO0123456789-STATEMENT-1-230120-1");
+        assert_eq!(salary.description, "Salary january");
+        assert_eq!(salary.amount, 300000);
+        assert_eq!(salary.external_account_name, "");
+
+        // A reversed debit adds the money back.
+        assert_eq!(records.get(2).unwrap().amount, 500);
+    }
+
+    #[test]
+    fn made_up_references_only_depend_on_the_statement() {
+        let content = [
+            ":20:STATEMENT-1",
+            ":25:NL12RABO0123456789",
+            ":61:230115D1,00NTRFNONREF",
+            ":20:STATEMENT-2",
+            ":25:NL12RABO0123456789",
+            ":61:230116D2,00NTRFNONREF",
+            ":61:230117D3,00NTRFNONREF",
+        ]
+        .join("\n");
+
+        let records = parse(&content).unwrap();
+        let follow_numbers: Vec<&str> = records
+            .iter()
+            .map(|record| record.follow_number.as_str())
+            .collect();
+
+        assert_eq!(
+            follow_numbers,
+            vec![
+                "NL12RABO0123456789-STATEMENT-1-230115-0",
+                "NL12RABO0123456789-STATEMENT-2-230116-0",
+                "NL12RABO0123456789-STATEMENT-2-230117-1",
+            ]
+        );
+    }
+
+    #[test]
+    fn accounts_are_cleaned_up() {
+        assert_eq!(parse_account("NL12RABO0123456789EUR"), "NL12RABO0123456789");
+        assert_eq!(parse_account("INGBNL2A/NL12INGB0123456789"), "NL12INGB0123456789");
+        assert_eq!(parse_account("123456789"), "123456789");
+    }
+
+    #[test]
+    fn invalid_statement_lines_are_rejected() {
+        let content = ":20:STATEMENT-1\n:25:NL12RABO0123456789\n:61:230115X12,34NTRFNONREF";
+
+        assert!(parse(content).is_err());
+    }
+
+    #[test]
+    fn statement_lines_require_an_account() {
+        let content = ":20:STATEMENT-1\n:61:230115D12,34NTRFNONREF";
+
+        assert!(parse(content).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::import_error::ImportError;
+use crate::prelude::*;
+use crate::routes::importing::map_csv_record::MappedCsvRecord;
+use crate::routes::importing::statement_formats::{follow_number, parse_amount, parse_date};
+
+/// Reads the transactions from an OFX statement. OFX 1.x is SGML where closing tags are optional,
+/// so instead of using an XML parser, the value of a tag is read up until the next tag starts. This
+/// works for both the SGML and the XML variant.
+pub fn parse(content: &str) -> Result<Vec<MappedCsvRecord>> {
+    let account_iban = tag_value(content, "ACCTID")
+        .ok_or(ImportError::invalid_statement("The statement does not contain an account"))?;
+
+    let mut records = vec![];
+
+    for (index, block) in content.split("<STMTTRN>").skip(1).enumerate() {
+        let block = block.split("</STMTTRN>").next().unwrap_or(block);
+
+        let reference = tag_value(block, "FITID").ok_or(ImportError::invalid_statement(
+            "A transaction does not have a FITID",
+        ))?;
+
+        let amount = tag_value(block, "TRNAMT").ok_or(ImportError::invalid_statement(
+            "A transaction does not have an amount",
+        ))?;
+
+        // Dates look like '20230115120000.000[-5:EST]', but only the date itself is used.
+        let date = tag_value(block, "DTPOSTED").ok_or(ImportError::invalid_statement(
+            "A transaction does not have a posted date",
+        ))?;
+
+        let name = tag_value(block, "NAME").unwrap_or_default();
+        let description = tag_value(block, "MEMO").unwrap_or_else(|| name.to_string());
+
+        records.push(MappedCsvRecord {
+            row: index as u64 + 1,
+            account_iban: account_iban.to_string(),
+            date: parse_date(date.get(..8).unwrap_or(&date), "%Y%m%d")?,
+            follow_number: follow_number(&account_iban, &reference),
+            description,
+            amount: parse_amount(&amount)?,
+            external_account_name: name,
+            currency: None,
+            external_account_iban: None,
+            value_date: None,
+        });
+    }
+
+    Ok(records)
+}
+
+/// Returns the value of the first occurrence of the given tag.
+fn tag_value(content: &str, tag: &str) -> Option<String> {
+    let start = content.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let value = &content[start..];
+    let end = value.find('<').unwrap_or(value.len());
+
+    Some(
+        value[..end]
+            .trim()
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::importing::statement_formats::ofx::parse;
+
+    #[test]
+    fn sgml_statements_are_parsed() {
+        let content = [
+            "OFXHEADER:100",
+            "DATA:OFXSGML",
+            "",
+            "<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS>",
+            "<BANKACCTFROM><BANKID>RABONL2U<ACCTID>NL12RABO0123456789<ACCTTYPE>CHECKING</BANKACCTFROM>",
+            "<BANKTRANLIST>",
+            "<STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20230115120000.000[-5:EST]<TRNAMT>-12.34<FITID>2023011501<NAME>Jumbo<MEMO>Groceries &amp; more",
+            "<STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20230120<TRNAMT>3000.00<FITID>2023012001<NAME>Work",
+            "</BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>",
+        ]
+        .join("\n");
+
+        let records = parse(&content).unwrap();
+
+        assert_eq!(records.len(), 2);
+
+        let groceries = records.get(0).unwrap();
+        assert_eq!(groceries.account_iban, "NL12RABO0123456789");
+        assert_eq!(groceries.date.to_rfc3339(), "2023-01-15T00:00:00+00:00");
+        assert_eq!(groceries.follow_number, "NL12RABO0123456789-2023011501");
+        assert_eq!(groceries.description, "Groceries & more");
+        assert_eq!(groceries.amount, -1234);
+        assert_eq!(groceries.external_account_name, "Jumbo");
+
+        // Without a memo the name is used as the description.
+        let salary = records.get(1).unwrap();
+        assert_eq!(salary.description, "Work");
+        assert_eq!(salary.amount, 300000);
+    }
+
+    #[test]
+    fn xml_statements_are_parsed() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <?OFX OFXHEADER="200" VERSION="220"?>
+            <OFX>
+                <BANKACCTFROM><ACCTID>NL12RABO0123456789</ACCTID></BANKACCTFROM>
+                <STMTTRN>
+                    <DTPOSTED>20230115</DTPOSTED>
+                    <TRNAMT>-5</TRNAMT>
+                    <FITID>abc</FITID>
+                    <NAME>Shop</NAME>
+                </STMTTRN>
+            </OFX>"#;
+
+        let records = parse(content).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records.get(0).unwrap().follow_number, "NL12RABO0123456789-abc");
+        assert_eq!(records.get(0).unwrap().amount, -500);
+    }
+
+    #[test]
+    fn transactions_without_id_are_rejected() {
+        let content = "<OFX><ACCTID>NL12<STMTTRN><DTPOSTED>20230115<TRNAMT>-5</STMTTRN></OFX>";
+
+        assert!(parse(content).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use rocket::serde::json::Json;
+
+use crate::db_inner;
+use crate::error::import_error::ImportError;
+use crate::models::csv::csv_mapping::CsvImportOrdering;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::importing::import_report_dto::ImportReportDto;
+use crate::models::dto::importing::import_statement_dto::{ImportStatementDto, StatementFormat};
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::routes::importing::import_records::import_records;
+use crate::routes::importing::statement_formats::parse_statement;
+use crate::shared::SharedPool;
+
+/// Imports an OFX, CAMT.053 or MT940 statement. When the format is not specified, it is detected
+/// from the content of the statement. With `dryRun`, the report shows what the import would do
+/// without saving anything.
+#[post("/statement?<query..>", data = "<body>")]
+pub async fn import_statement(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<ImportStatementDto>,
+    query: ImportQueryDto,
+) -> Result<Json<ImportReportDto>> {
+    let pool = db_inner!(pool);
+    let body = body.0;
+
+    let format = body
+        .format
+        .or_else(|| StatementFormat::detect(&body.content))
+        .ok_or(ImportError::UnknownFormat)?;
+
+    debug!("Importing {:?} statement for user '{}'", format, user);
+    let mut records = parse_statement(format, &body.content)?;
+
+    // Statements are not guaranteed to list the oldest transaction first, so the records are
+    // sorted to get the right order indicators.
+    records.sort_by_key(|record| record.date);
+
+    let rows = import_records(
+        pool,
+        &user.uuid,
+        body.filename,
+        records,
+        &CsvImportOrdering::NewestLast,
+        query.dry_run,
+    )
+    .await?;
+
+    Ok(Json(ImportReportDto::new(query.dry_run, rows)))
+}
@@ -8,5 +8,13 @@ pub mod transactions;
 pub mod aggregates;
 pub mod bank_accounts;
 pub mod blobs;
+pub mod budgets;
 pub mod corrections;
+pub mod exchange_rates;
+pub mod forecast;
+pub mod planned_transactions;
+pub mod reconciliations;
+pub mod recurring;
+pub mod rules;
+pub mod tags;
 pub mod users;
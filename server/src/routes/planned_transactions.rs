@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+use rocket::serde::json::Json;
+use rocket::Route;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::planned_transactions::new_planned_transaction_dto::NewPlannedTransactionDto;
+use crate::models::dto::planned_transactions::planned_transaction_dto::PlannedTransactionDto;
+use crate::models::entities::bank_account::BankAccount;
+use crate::models::entities::category::Category;
+use crate::models::entities::external_account::ExternalAccount;
+use crate::models::entities::planned_transaction::PlannedTransaction;
+use crate::models::entities::subcategory::Subcategory;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::shared::{DbPool, SharedPool};
+use crate::utils::datetime::{parse_rfc3339, to_utc};
+
+pub fn create_planned_transaction_routes() -> Vec<Route> {
+    routes![
+        get_all_planned_transactions,
+        create_new_planned_transaction,
+        get_planned_transaction_by_id,
+        update_planned_transaction,
+        delete_planned_transaction,
+    ]
+}
+
+#[get("/")]
+pub async fn get_all_planned_transactions(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<PlannedTransactionDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all planned transactions for user '{}'", user);
+    let planned = PlannedTransaction::find_all(pool, &user.uuid).await?;
+
+    Ok(Json(planned.into_iter().map(PlannedTransactionDto::from).collect()))
+}
+
+#[post("/", data = "<body>")]
+pub async fn create_new_planned_transaction(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<NewPlannedTransactionDto<'_>>,
+) -> Result<Json<PlannedTransactionDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    guard_planned_transaction_references(inner_pool, &user, &body).await?;
+    let (date, ends_at) = parse_schedule(&body)?;
+
+    let planned = PlannedTransaction {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        bank_account_id: body.bank_account_id.to_string(),
+        category_id: body.category_id.map(|v| v.to_string()),
+        subcategory_id: body.subcategory_id.map(|v| v.to_string()),
+        external_account_id: body.external_account_id.map(|v| v.to_string()),
+        description: body.description.to_string(),
+        amount: body.amount,
+        date,
+        recurrence: body.recurrence,
+        ends_at,
+        completed: false,
+        linked_transaction_ids: vec![],
+    };
+
+    debug!("Creating new planned transaction for user '{}'", user);
+    planned.create(inner_pool).await?;
+
+    debug!("Created planned transaction '{}'", planned.id);
+    get_planned_transaction_by_id(pool, user, &planned.id).await
+}
+
+#[get("/<id>")]
+pub async fn get_planned_transaction_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<PlannedTransactionDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying planned transaction with id '{}'", id);
+    let planned = PlannedTransaction::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(planned.into()))
+}
+
+/// Replaces the planned transaction. The date becomes the next occurrence, so it is opened again
+/// when it was completed. Transactions that were already linked stay linked.
+#[put("/<id>", data = "<body>")]
+pub async fn update_planned_transaction(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    body: Json<NewPlannedTransactionDto<'_>>,
+) -> Result<Json<PlannedTransactionDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    debug!("Executing planned transaction guard for id '{}' with user '{}'", id, user);
+    PlannedTransaction::guard_one(inner_pool, id, &user.uuid).await?;
+
+    guard_planned_transaction_references(inner_pool, &user, &body).await?;
+    let (date, ends_at) = parse_schedule(&body)?;
+
+    debug!("Updating planned transaction with id '{}'", id);
+    sqlx::query!(
+        r#"
+            UPDATE planned_transactions
+            SET bank_account_id = $3, category_id = $4, subcategory_id = $5,
+                external_account_id = $6, description = $7, amount = $8, date = $9,
+                recurrence = $10, ends_at = $11, completed = false
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid,
+        body.bank_account_id,
+        body.category_id,
+        body.subcategory_id,
+        body.external_account_id,
+        body.description,
+        body.amount,
+        OffsetDateTime::from_unix_timestamp(date.timestamp())?,
+        body.recurrence.map(<&str>::from),
+        ends_at
+            .map(|ends_at| OffsetDateTime::from_unix_timestamp(ends_at.timestamp()))
+            .transpose()?,
+    )
+    .execute(inner_pool)
+    .await?;
+
+    debug!("Updated planned transaction '{}'", id);
+    get_planned_transaction_by_id(pool, user, id).await
+}
+
+#[delete("/<id>")]
+pub async fn delete_planned_transaction(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing planned transaction guard for id '{}' with user '{}'", id, user);
+    PlannedTransaction::guard_one(pool, id, &user.uuid).await?;
+
+    debug!("Deleting planned transaction with id '{}'", id);
+    sqlx::query!(
+        r#"
+            DELETE FROM planned_transactions
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn guard_planned_transaction_references(
+    pool: &DbPool,
+    user: &JwtUserPayload,
+    body: &NewPlannedTransactionDto<'_>,
+) -> Result<()> {
+    debug!("Executing bank account guard for id '{}' with user '{}'", body.bank_account_id, user);
+    BankAccount::guard_one(pool, body.bank_account_id, &user.uuid).await?;
+
+    if let Some(category_id) = body.category_id {
+        debug!("Executing category guard for id '{}' with user '{}'", category_id, user);
+        Category::guard_one(pool, category_id, &user.uuid).await?;
+    }
+
+    if let Some(subcategory_id) = body.subcategory_id {
+        let Some(category_id) = body.category_id else {
+            return Err(HttpError::new(400)
+                .message("A subcategory can only be set together with its category")
+                .into());
+        };
+
+        debug!("Executing subcategory guard for id '{}' with user '{}'", subcategory_id, user);
+        Subcategory::guard_one(pool, subcategory_id, category_id, &user.uuid).await?;
+    }
+
+    if let Some(external_account_id) = body.external_account_id {
+        debug!(
+            "Executing external account guard for id '{}' with user '{}'",
+            external_account_id, user
+        );
+        ExternalAccount::guard_one(pool, &external_account_id.to_string(), &user.uuid).await?;
+    }
+
+    Ok(())
+}
+
+fn parse_schedule(
+    body: &NewPlannedTransactionDto,
+) -> Result<(DateTime<Utc>, Option<DateTime<Utc>>)> {
+    let date = to_utc(parse_rfc3339(body.date)?);
+
+    let ends_at = body
+        .ends_at
+        .map(|ends_at| parse_rfc3339(ends_at).map(to_utc))
+        .transpose()?;
+
+    if ends_at.is_some_and(|ends_at| ends_at < date) {
+        return Err(HttpError::new(400)
+            .message("The end of a planned transaction cannot be before its first date")
+            .into());
+    }
+
+    Ok((date, ends_at))
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use rocket::serde::json::Json;
+use rocket::Route;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::reconciliations::new_reconciliation_dto::NewReconciliationDto;
+use crate::models::dto::reconciliations::reconciliation_dto::ReconciliationDto;
+use crate::models::dto::transactions::new_correction_dto::NewCorrectionDto;
+use crate::models::dto::transactions::transaction_dto::TransactionDto;
+use crate::models::entities::bank_account::BankAccount;
+use crate::models::entities::reconciliation::Reconciliation;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::queries::transactions_query::TransactionQuery;
+use crate::services::balance_service::BalanceService;
+use crate::services::correction_service::CorrectionService;
+use crate::shared::{DbPool, SharedPool};
+use crate::utils::datetime::{parse_rfc3339, to_utc};
+
+pub fn create_reconciliation_routes() -> Vec<Route> {
+    routes![
+        get_all_reconciliations,
+        create_reconciliation,
+        get_reconciliation_by_id,
+        create_reconciliation_correction,
+        delete_reconciliation,
+    ]
+}
+
+#[get("/")]
+pub async fn get_all_reconciliations(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<ReconciliationDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all reconciliations for user '{}'", user);
+    let reconciliations = Reconciliation::find_all(pool, &user.uuid).await?;
+
+    let mut dtos = vec![];
+
+    for reconciliation in reconciliations {
+        dtos.push(map_reconciliation(pool, reconciliation).await?);
+    }
+
+    Ok(Json(dtos))
+}
+
+/// Records the balance the bank reports at the given date and compares it against the balance in
+/// the application. From then on, the transactions of the bank account up to that date are locked.
+#[post("/", data = "<body>")]
+pub async fn create_reconciliation(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<NewReconciliationDto<'_>>,
+) -> Result<Json<ReconciliationDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    debug!(
+        "Executing bank account guard for id '{}' with user '{}'",
+        body.bank_account_id, user
+    );
+    BankAccount::guard_one(inner_pool, body.bank_account_id, &user.uuid).await?;
+
+    let reconciliation = Reconciliation {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        bank_account_id: body.bank_account_id.to_string(),
+        date: to_utc(parse_rfc3339(body.date)?),
+        balance: body.balance,
+    };
+
+    debug!("Creating new reconciliation for user '{}'", user);
+    reconciliation.create(inner_pool).await?;
+
+    debug!("Created reconciliation '{}'", reconciliation.id);
+    get_reconciliation_by_id(pool, user, &reconciliation.id).await
+}
+
+#[get("/<id>")]
+pub async fn get_reconciliation_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<ReconciliationDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying reconciliation with id '{}'", id);
+    let reconciliation = Reconciliation::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(map_reconciliation(pool, reconciliation).await?))
+}
+
+/// Creates a correction at the date of the reconciliation for the difference between the statement
+/// balance and the balance in the application, so both balances match afterwards.
+#[post("/<id>/correction")]
+pub async fn create_reconciliation_correction(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<TransactionDto>> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Querying reconciliation with id '{}'", id);
+    let reconciliation = Reconciliation::find_one(inner_pool, id, &user.uuid).await?;
+    let reconciliation = map_reconciliation(inner_pool, reconciliation).await?;
+
+    if reconciliation.difference == 0 {
+        return HttpError::new(400)
+            .message("The balance of the reconciliation already matches the statement")
+            .into();
+    }
+
+    let body = NewCorrectionDto {
+        amount: reconciliation.difference,
+        description: "Reconciliation".to_string(),
+        bank_account_id: reconciliation.bank_account_id,
+        category_id: None,
+        subcategory_id: None,
+    };
+
+    let date = DateTime::parse_from_rfc3339(&reconciliation.date)?.with_timezone(&Utc);
+    let correction_id =
+        CorrectionService::create_correction(inner_pool, &user.uuid, body, date).await?;
+
+    let transaction = TransactionQuery::new(&user.uuid)
+        .where_id(correction_id)
+        .fetch_one(inner_pool)
+        .await?;
+
+    debug!("Created correction '{}' for reconciliation '{}'", transaction.id, id);
+    Ok(Json(transaction))
+}
+
+/// Deleting a reconciliation unlocks the transactions it covered, unless they are also covered by
+/// a later reconciliation.
+#[delete("/<id>")]
+pub async fn delete_reconciliation(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing reconciliation guard for id '{}' with user '{}'", id, user);
+    Reconciliation::guard_one(pool, id, &user.uuid).await?;
+
+    debug!("Deleting reconciliation with id '{}'", id);
+    sqlx::query!(
+        r#"
+            DELETE FROM reconciliations
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn map_reconciliation(
+    pool: &DbPool,
+    reconciliation: Reconciliation,
+) -> Result<ReconciliationDto> {
+    let computed_balance = BalanceService::balance_at(
+        pool,
+        &reconciliation.user_id,
+        &reconciliation.bank_account_id,
+        OffsetDateTime::from_unix_timestamp(reconciliation.date.timestamp())?,
+    )
+    .await?;
+
+    Ok(ReconciliationDto {
+        id: reconciliation.id,
+        bank_account_id: reconciliation.bank_account_id,
+        date: reconciliation.date.to_rfc3339(),
+        statement_balance: reconciliation.balance,
+        computed_balance,
+        difference: reconciliation.balance - computed_balance,
+    })
+}
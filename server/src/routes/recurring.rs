@@ -0,0 +1,148 @@
+use chrono::{Duration, Utc};
+use rocket::serde::json::Json;
+use rocket::Route;
+
+use crate::db_inner;
+use crate::models::dto::recurring::recurring_series_dto::RecurringSeriesDto;
+use crate::models::dto::recurring::upcoming_transaction_dto::UpcomingTransactionDto;
+use crate::models::dto::recurring::update_recurring_series_dto::UpdateRecurringSeriesDto;
+use crate::models::entities::recurring_series::{RecurringSeries, RecurringStatus};
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::recurring_service::RecurringService;
+use crate::shared::SharedPool;
+use crate::utils::datetime::{parse_rfc3339, to_utc};
+
+pub fn create_recurring_routes() -> Vec<Route> {
+    routes![
+        get_all_recurring_series,
+        detect_recurring_series,
+        get_upcoming_transactions,
+        get_recurring_series_by_id,
+        update_recurring_series,
+    ]
+}
+
+#[get("/")]
+pub async fn get_all_recurring_series(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<RecurringSeriesDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all recurring series for user '{}'", user);
+    let series = RecurringSeries::find_all(pool, &user.uuid).await?;
+
+    Ok(Json(series.into_iter().map(RecurringSeriesDto::from).collect()))
+}
+
+/// Scans the transactions for recurring series right away, instead of waiting for the scheduler.
+#[post("/detect")]
+pub async fn detect_recurring_series(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<RecurringSeriesDto>>> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Detecting recurring series for user '{}'", user);
+    RecurringService::recompute(inner_pool, &user.uuid, Utc::now()).await?;
+
+    get_all_recurring_series(pool, user).await
+}
+
+/// Returns the transactions the series that are not dismissed are expected to have until the
+/// given date, which defaults to a month from now. When the next transaction of a series is
+/// overdue, it is included as well, but the ones after it that are overdue are not.
+#[get("/upcoming?<until>")]
+pub async fn get_upcoming_transactions(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    until: Option<&str>,
+) -> Result<Json<Vec<UpcomingTransactionDto>>> {
+    let pool = db_inner!(pool);
+
+    let now = Utc::now();
+    let until = match until {
+        Some(until) => to_utc(parse_rfc3339(until)?),
+        None => now + Duration::days(30),
+    };
+
+    debug!("Querying all recurring series for user '{}'", user);
+    let series = RecurringSeries::find_all(pool, &user.uuid).await?;
+
+    let mut upcoming = vec![];
+
+    for series in series {
+        if series.status == RecurringStatus::Dismissed {
+            continue;
+        }
+
+        let mut date = series.next_date;
+
+        while date <= until {
+            if date < now && date != series.next_date {
+                date = series.recurrence.next(date);
+                continue;
+            }
+
+            upcoming.push(UpcomingTransactionDto {
+                series_id: series.id.to_string(),
+                external_account_name: series.external_account_name.to_string(),
+                date: date.to_rfc3339(),
+                amount: series.amount,
+                currency: series.currency.to_string(),
+            });
+
+            date = series.recurrence.next(date);
+        }
+    }
+
+    upcoming.sort_by(|a, b| a.date.cmp(&b.date));
+
+    Ok(Json(upcoming))
+}
+
+#[get("/<id>")]
+pub async fn get_recurring_series_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<RecurringSeriesDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying recurring series with id '{}'", id);
+    let series = RecurringSeries::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(series.into()))
+}
+
+/// Confirms or dismisses the series. Both are kept when the series is detected again, and a
+/// confirmed series is also kept when it is not detected anymore.
+#[patch("/<id>", data = "<body>")]
+pub async fn update_recurring_series(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    body: Json<UpdateRecurringSeriesDto>,
+) -> Result<Json<RecurringSeriesDto>> {
+    let inner_pool = db_inner!(pool);
+
+    debug!("Executing recurring series guard for id '{}' with user '{}'", id, user);
+    RecurringSeries::guard_one(inner_pool, id, &user.uuid).await?;
+
+    debug!("Updating status of recurring series '{}'", id);
+    sqlx::query!(
+        r#"
+            UPDATE recurring_series
+            SET status = $3
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid,
+        <&str>::from(body.status),
+    )
+    .execute(inner_pool)
+    .await?;
+
+    get_recurring_series_by_id(pool, user, id).await
+}
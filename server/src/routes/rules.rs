@@ -0,0 +1,250 @@
+use regex::Regex;
+use rocket::serde::json::Json;
+use rocket::Route;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::rules::new_rule_dto::NewRuleDto;
+use crate::models::dto::rules::rule_dto::RuleDto;
+use crate::models::entities::bank_account::BankAccount;
+use crate::models::entities::category::Category;
+use crate::models::entities::external_account::ExternalAccount;
+use crate::models::entities::rule::Rule;
+use crate::models::entities::subcategory::Subcategory;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::routes::rules::applying::{apply_rules, preview_rules};
+use crate::shared::{DbPool, SharedPool};
+
+pub mod applying;
+
+pub fn create_rule_routes() -> Vec<Route> {
+    routes![
+        get_all_rules,
+        create_new_rule,
+        get_rule_by_id,
+        update_rule,
+        delete_rule,
+        preview_rules,
+        apply_rules,
+    ]
+}
+
+#[get("/")]
+pub async fn get_all_rules(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+) -> Result<Json<Vec<RuleDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all rules for user '{}'", user);
+    let rules = sqlx::query_as!(
+        Rule,
+        r#"
+            SELECT *
+            FROM rules
+            WHERE user_id = $1
+            ORDER BY priority, name;
+        "#,
+        user.uuid
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Json(rules.into_iter().map(RuleDto::from).collect()))
+}
+
+#[post("/", data = "<body>")]
+pub async fn create_new_rule(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<NewRuleDto>,
+) -> Result<Json<RuleDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    validate_rule(inner_pool, &user, &body).await?;
+
+    let rule = Rule {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        name: body.name,
+        priority: body.priority,
+        description_contains: body.description_contains,
+        description_regex: body.description_regex,
+        min_amount: body.min_amount,
+        max_amount: body.max_amount,
+        iban: body.iban,
+        bank_account_id: body.bank_account_id,
+        weekday: body.weekday,
+        set_category_id: body.set_category_id,
+        set_subcategory_id: body.set_subcategory_id,
+        set_description: body.set_description,
+        set_external_account_id: body.set_external_account_id,
+    };
+
+    debug!("Creating new rule for user '{}'", user);
+    rule.create(inner_pool).await?;
+
+    debug!("Created rule '{}'", rule.id);
+    get_rule_by_id(pool, user, &rule.id).await
+}
+
+#[get("/<id>")]
+pub async fn get_rule_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<RuleDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying rule with id '{}'", id);
+    let rule = sqlx::query_as!(
+        Rule,
+        r#"
+            SELECT *
+            FROM rules
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Json(rule.into()))
+}
+
+#[put("/<id>", data = "<body>")]
+pub async fn update_rule(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    body: Json<NewRuleDto>,
+) -> Result<Json<RuleDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    debug!("Executing rule guard for id '{}' with user '{}'", id, user);
+    Rule::guard_one(inner_pool, id, &user.uuid).await?;
+
+    validate_rule(inner_pool, &user, &body).await?;
+
+    debug!("Updating rule with id '{}'", id);
+    sqlx::query!(
+        r#"
+            UPDATE rules
+            SET name = $3, priority = $4, description_contains = $5, description_regex = $6,
+                min_amount = $7, max_amount = $8, iban = $9, bank_account_id = $10, weekday = $11,
+                set_category_id = $12, set_subcategory_id = $13, set_description = $14,
+                set_external_account_id = $15
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid,
+        body.name,
+        body.priority,
+        body.description_contains,
+        body.description_regex,
+        body.min_amount,
+        body.max_amount,
+        body.iban,
+        body.bank_account_id,
+        body.weekday,
+        body.set_category_id,
+        body.set_subcategory_id,
+        body.set_description,
+        body.set_external_account_id,
+    )
+    .execute(inner_pool)
+    .await?;
+
+    debug!("Updated rule '{}'", id);
+    get_rule_by_id(pool, user, id).await
+}
+
+#[delete("/<id>")]
+pub async fn delete_rule(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing rule guard for id '{}' with user '{}'", id, user);
+    Rule::guard_one(pool, id, &user.uuid).await?;
+
+    debug!("Deleting rule with id '{}'", id);
+    sqlx::query!(
+        r#"
+            DELETE FROM rules
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Checks that the rule is able to do something and that everything it references is owned by the
+/// user.
+async fn validate_rule(pool: &DbPool, user: &JwtUserPayload, body: &NewRuleDto) -> Result<()> {
+    if body.set_category_id.is_none()
+        && body.set_description.is_none()
+        && body.set_external_account_id.is_none()
+    {
+        return HttpError::new(400)
+            .message("A rule must perform at least one action")
+            .into();
+    }
+
+    if let Some(pattern) = &body.description_regex {
+        if Regex::new(pattern).is_err() {
+            return HttpError::new(400)
+                .message("The description regex is not a valid regex")
+                .into();
+        }
+    }
+
+    if let Some(weekday) = body.weekday {
+        if !(0..=6).contains(&weekday) {
+            return HttpError::new(400)
+                .message("The weekday must be between 0 (Monday) and 6 (Sunday)")
+                .into();
+        }
+    }
+
+    if let Some(bank_account_id) = &body.bank_account_id {
+        debug!("Executing bank account guard for id '{}' with user '{}'", bank_account_id, user);
+        BankAccount::guard_one(pool, bank_account_id, &user.uuid).await?;
+    }
+
+    match (&body.set_category_id, &body.set_subcategory_id) {
+        (None, Some(_)) => {
+            return HttpError::new(400)
+                .message("A subcategory can only be set together with a category")
+                .into();
+        }
+        (Some(category_id), subcategory_id) => {
+            debug!("Executing category guard for id '{}' with user '{}'", category_id, user);
+            Category::guard_one(pool, category_id, &user.uuid).await?;
+
+            if let Some(subcategory_id) = subcategory_id {
+                debug!("Executing subcategory guard for id '{}' with user '{}'", subcategory_id, user);
+                Subcategory::guard_one(pool, subcategory_id, category_id, &user.uuid).await?;
+            }
+        }
+        (None, None) => {}
+    }
+
+    if let Some(external_account_id) = &body.set_external_account_id {
+        debug!("Executing external account guard for id '{}' with user '{}'", external_account_id, user);
+        ExternalAccount::guard_one(pool, external_account_id, &user.uuid).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use rocket::serde::json::Json;
+use sqlx::PgConnection;
+
+use crate::db_inner;
+use crate::models::dto::rules::apply_rules_dto::ApplyRulesDto;
+use crate::models::dto::rules::rule_change_dto::{RuleChangeDto, RuleChangeValuesDto};
+use crate::models::entities::transaction::transaction_type::TransactionType;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::queries::transactions_query::TransactionQuery;
+use crate::services::rule_service::{RuleService, RuleSubject};
+use crate::shared::SharedPool;
+use crate::utils::datetime::parse_rfc3339;
+
+/// Returns the changes that would be made when the rules are applied to the selected transactions,
+/// without actually changing anything.
+#[post("/preview", data = "<body>")]
+pub async fn preview_rules(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<ApplyRulesDto<'_>>,
+) -> Result<Json<Vec<RuleChangeDto>>> {
+    let pool = db_inner!(pool);
+    let mut connection = pool.acquire().await?;
+
+    let changes = resolve_rule_changes(&mut connection, &user, &body.0).await?;
+
+    Ok(Json(changes))
+}
+
+/// Applies the rules to the selected transactions and returns the changes that were made.
+#[post("/apply", data = "<body>")]
+pub async fn apply_rules(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<ApplyRulesDto<'_>>,
+) -> Result<Json<Vec<RuleChangeDto>>> {
+    let pool = db_inner!(pool);
+    let mut db_transaction = pool.begin().await?;
+
+    // The changes are resolved inside the database transaction, so they are based on the same
+    // state of the transactions they are applied to.
+    let changes = resolve_rule_changes(&mut db_transaction, &user, &body.0).await?;
+
+    debug!("Applying {} rule changes for user '{}'", changes.len(), user);
+    for change in &changes {
+        sqlx::query!(
+            r#"
+                UPDATE transactions
+                SET category_id = $3,
+                    subcategory_id = $4,
+                    description = $5,
+                    -- The name entry belongs to the external account the rule replaces.
+                    external_account_name_id = CASE
+                        WHEN $6 IS DISTINCT FROM external_account_id THEN NULL
+                        ELSE external_account_name_id
+                    END,
+                    external_account_id = $6
+                WHERE id = $1 AND user_id = $2;
+            "#,
+            change.transaction_id,
+            user.uuid,
+            change.after.category_id,
+            change.after.subcategory_id,
+            change.after.description,
+            change.after.external_account_id,
+        )
+        .execute(&mut *db_transaction)
+        .await?;
+    }
+
+    db_transaction.commit().await?;
+
+    Ok(Json(changes))
+}
+
+/// Runs the rules of the user against the selected transactions and returns only the transactions
+/// that would actually change.
+async fn resolve_rule_changes(
+    connection: &mut PgConnection,
+    user: &JwtUserPayload,
+    body: &ApplyRulesDto<'_>,
+) -> Result<Vec<RuleChangeDto>> {
+    let rule_service = RuleService::load(&mut *connection, &user.uuid).await?;
+
+    let mut query = TransactionQuery::new(&user.uuid).where_type(TransactionType::Transaction);
+
+    if let Some(from) = body.from {
+        query = query.where_date_from(parse_rfc3339(from)?);
+    }
+
+    if let Some(to) = body.to {
+        query = query.where_date_to(parse_rfc3339(to)?);
+    }
+
+    if let Some(bank_account_id) = body.bank_account_id {
+        query = query.where_bank_account(bank_account_id);
+    }
+
+    if body.only_uncategorized {
+        query = query.where_uncategorized();
+    }
+
+    trace!("Querying transactions to run the rules against");
+    let transactions = query.order().fetch_all(&mut *connection).await?;
+
+    let mut changes = vec![];
+
+    for transaction in transactions {
+        let bank_account = transaction.bank_account.as_ref();
+        let date = DateTime::parse_from_rfc3339(&transaction.date)?.with_timezone(&Utc);
+
+        let outcome = rule_service.evaluate(&RuleSubject {
+            description: &transaction.original_description,
+            amount: transaction.amount,
+            external_account_iban: transaction.external_account_iban.as_deref(),
+            bank_account_id: bank_account.map_or("", |bank_account| &bank_account.id),
+            date,
+        });
+
+        let before = RuleChangeValuesDto {
+            category_id: transaction.category.map(|category| category.id),
+            subcategory_id: transaction.subcategory.map(|subcategory| subcategory.id),
+            description: transaction.description,
+            external_account_id: transaction.external_account.map(|account| account.id),
+        };
+
+        let mut after = before.clone();
+
+        if let Some((category_id, subcategory_id)) = outcome.category {
+            after.category_id = Some(category_id);
+            after.subcategory_id = subcategory_id;
+        }
+
+        if let Some(description) = outcome.description {
+            after.description = description;
+        }
+
+        if let Some(external_account_id) = outcome.external_account_id {
+            after.external_account_id = Some(external_account_id);
+        }
+
+        if before == after {
+            continue;
+        }
+
+        changes.push(RuleChangeDto {
+            transaction_id: transaction.id,
+            original_description: transaction.original_description,
+            before,
+            after,
+        });
+    }
+
+    Ok(changes)
+}
@@ -0,0 +1,181 @@
+use rocket::serde::json::Json;
+use rocket::Route;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::models::dto::pagination::pagination_response_dto::PaginationResponseDto;
+use crate::models::dto::tags::new_tag_dto::NewTagDto;
+use crate::models::dto::tags::tag_dto::TagDto;
+use crate::models::dto::transactions::transaction_dto::TransactionDto;
+use crate::models::entities::tag::Tag;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::queries::transactions_query::TransactionQuery;
+use crate::shared::SharedPool;
+
+pub fn create_tag_routes() -> Vec<Route> {
+    routes![
+        get_all_tags,
+        create_new_tag,
+        get_tag_by_id,
+        update_tag,
+        delete_tag,
+        get_tag_transactions,
+    ]
+}
+
+#[get("/")]
+pub async fn get_all_tags(pool: &SharedPool, user: JwtUserPayload) -> Result<Json<Vec<TagDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying all tags for user '{}'", user);
+    let tags = Tag::find_all(pool, &user.uuid).await?;
+
+    Ok(Json(tags.into_iter().map(TagDto::from).collect()))
+}
+
+#[post("/", data = "<body>")]
+pub async fn create_new_tag(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<NewTagDto<'_>>,
+) -> Result<Json<TagDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    let tag = Tag {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        name: tag_name(&body)?,
+        hex_color: body.hex_color.to_string(),
+    };
+
+    debug!("Creating new tag for user '{}'", user);
+    tag.create(inner_pool).await.map_err(duplicate_name_error)?;
+
+    debug!("Created tag '{}'", tag.id);
+    get_tag_by_id(pool, user, &tag.id).await
+}
+
+#[get("/<id>")]
+pub async fn get_tag_by_id(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+) -> Result<Json<TagDto>> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying tag with id '{}'", id);
+    let tag = Tag::find_one(pool, id, &user.uuid).await?;
+
+    Ok(Json(tag.into()))
+}
+
+#[put("/<id>", data = "<body>")]
+pub async fn update_tag(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: &str,
+    body: Json<NewTagDto<'_>>,
+) -> Result<Json<TagDto>> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    debug!("Executing tag guard for id '{}' with user '{}'", id, user);
+    Tag::guard_one(inner_pool, id, &user.uuid).await?;
+
+    debug!("Updating tag with id '{}'", id);
+    sqlx::query!(
+        r#"
+            UPDATE tags
+            SET name = $3, hex_color = $4
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid,
+        tag_name(&body)?,
+        body.hex_color,
+    )
+    .execute(inner_pool)
+    .await
+    .map_err(|error| duplicate_name_error(error.into()))?;
+
+    debug!("Updated tag '{}'", id);
+    get_tag_by_id(pool, user, id).await
+}
+
+/// Deletes the tag and removes it from all transactions.
+#[delete("/<id>")]
+pub async fn delete_tag(pool: &SharedPool, user: JwtUserPayload, id: &str) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing tag guard for id '{}' with user '{}'", id, user);
+    Tag::guard_one(pool, id, &user.uuid).await?;
+
+    debug!("Deleting tag with id '{}'", id);
+    sqlx::query!(
+        r#"
+            DELETE FROM tags
+            WHERE id = $1 AND user_id = $2;
+        "#,
+        id,
+        user.uuid
+    )
+    .execute(pool)
+    .await?;
+
+    debug!("Deleted tag '{}'", id);
+    Ok(())
+}
+
+#[get("/<id>/transactions?<pagination..>")]
+pub async fn get_tag_transactions(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    id: String,
+    pagination: PaginationQueryDto,
+) -> Result<Json<PaginationResponseDto<TransactionDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing tag guard for id '{}' with user '{}'", id, user);
+    Tag::guard_one(pool, &id, &user.uuid).await?;
+
+    let transactions = TransactionQuery::new(&user.uuid)
+        .where_tag(&id)
+        .order()
+        .paginate(&pagination)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(Json(PaginationResponseDto::from_query(
+        pagination,
+        transactions,
+    )))
+}
+
+fn tag_name(body: &NewTagDto) -> Result<String> {
+    let name = body.name.trim();
+
+    if name.is_empty() {
+        return HttpError::new(400)
+            .message("The name of a tag cannot be empty")
+            .into();
+    }
+
+    Ok(name.to_string())
+}
+
+/// Tag names are unique per user, which is reported as a conflict instead of a database error.
+fn duplicate_name_error(error: Error) -> Error {
+    let Error::Sqlx(wrapped_error) = &error else {
+        return error;
+    };
+
+    if wrapped_error.get_constraint() != Some("unique_tag_name") {
+        return error;
+    }
+
+    Error::HttpError(HttpError::new(409).message("A tag with this name already exists"))
+}
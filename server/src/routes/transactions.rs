@@ -1,22 +1,34 @@
 use rocket::Route;
 
+use crate::routes::transactions::attachments::*;
 use crate::routes::transactions::splits::*;
 use crate::routes::transactions::transaction_management::*;
+use crate::routes::transactions::transfers::*;
 
+pub mod attachments;
 pub mod splits;
 pub mod transaction_management;
+pub mod transfers;
 
 pub fn create_transaction_routes() -> Vec<Route> {
     routes![
         get_all_transactions,
+        search_transactions,
         get_single_transaction,
         change_category_for_transaction,
         update_transaction_details,
         update_transaction,
         bulk_update_transaction_categories,
+        bulk_update_transaction_tags,
         get_splits,
         create_split,
         update_split,
         delete_split,
+        get_attachments,
+        create_attachment,
+        download_attachment,
+        delete_attachment,
+        link_transfer,
+        unlink_transfer,
     ]
 }
@@ -0,0 +1,152 @@
+use chrono::Utc;
+use rocket::serde::json::Json;
+use uuid::Uuid;
+
+use crate::db_inner;
+use crate::error::http_error::HttpError;
+use crate::models::dto::blobs::blob_content::BlobContent;
+use crate::models::dto::transactions::attachment_dto::AttachmentDto;
+use crate::models::dto::transactions::new_attachment_dto::NewAttachmentDto;
+use crate::models::entities::transaction::Transaction;
+use crate::models::entities::transaction_attachment::TransactionAttachment;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::blob_service::{BlobService, BlobUse};
+use crate::shared::{SharedBlobService, SharedPool};
+
+#[get("/<transaction_id>/attachments")]
+pub async fn get_attachments(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    transaction_id: &str,
+) -> Result<Json<Vec<AttachmentDto>>> {
+    let pool = db_inner!(pool);
+
+    debug!("Executing transaction guard for id '{}' with user '{}'", transaction_id, user);
+    Transaction::guard_one(pool, transaction_id, &user.uuid).await?;
+
+    let attachments = TransactionAttachment::find_all(pool, transaction_id, &user.uuid).await?;
+
+    Ok(Json(attachments.into_iter().map(AttachmentDto::from).collect()))
+}
+
+/// Confirms the uploaded blob and attaches it to the transaction. Only PDF, JPEG and PNG files can
+/// be attached, and a file can only be attached once.
+#[post("/<transaction_id>/attachments", data = "<body>")]
+pub async fn create_attachment(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    blob_service: &SharedBlobService,
+    transaction_id: &str,
+    body: Json<NewAttachmentDto<'_>>,
+) -> Result<Json<AttachmentDto>> {
+    let pool = db_inner!(pool);
+    let body = body.0;
+
+    let filename = body.filename.trim();
+
+    if filename.is_empty() {
+        return HttpError::new(400)
+            .message("The filename of an attachment cannot be empty")
+            .into();
+    }
+
+    debug!("Executing transaction guard for id '{}' with user '{}'", transaction_id, user);
+    Transaction::guard_one(pool, transaction_id, &user.uuid).await?;
+
+    let mime_type =
+        BlobService::check_token_for_use(&user.uuid, pool, body.token, BlobUse::Attachment)
+            .await?;
+
+    let attachment = TransactionAttachment {
+        id: Uuid::new_v4().to_string(),
+        user_id: user.uuid.to_string(),
+        transaction_id: transaction_id.to_string(),
+        blob_token: body.token.to_string(),
+        filename: filename.to_string(),
+        mime_type,
+        created_at: Utc::now(),
+    };
+
+    // The blob is only confirmed once the attachment is inserted, so a failed insert does not
+    // leave a confirmed blob behind that nothing uses.
+    let mut db_transaction = pool.begin().await?;
+
+    debug!("Creating attachment for transaction '{}'", transaction_id);
+    attachment
+        .create(&mut *db_transaction)
+        .await
+        .map_err(duplicate_attachment_error)?;
+
+    blob_service
+        .read()
+        .await
+        .confirm_token(&user.uuid, pool, &attachment.blob_token)
+        .await?;
+
+    db_transaction.commit().await?;
+
+    let attachment =
+        TransactionAttachment::find_one(pool, &attachment.id, transaction_id, &user.uuid).await?;
+
+    Ok(Json(attachment.into()))
+}
+
+/// Streams the file of the attachment with the content type it was uploaded with.
+#[get("/<transaction_id>/attachments/<id>")]
+pub async fn download_attachment(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    blob_service: &SharedBlobService,
+    transaction_id: &str,
+    id: &str,
+) -> Result<BlobContent> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying attachment with id '{}'", id);
+    let attachment = TransactionAttachment::find_one(pool, id, transaction_id, &user.uuid).await?;
+
+    blob_service
+        .read()
+        .await
+        .get_blob_with_mimetype(attachment.blob_token, BlobUse::Attachment.mimetypes())
+        .await
+}
+
+#[delete("/<transaction_id>/attachments/<id>")]
+pub async fn delete_attachment(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    blob_service: &SharedBlobService,
+    transaction_id: &str,
+    id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    debug!("Querying attachment with id '{}'", id);
+    let attachment = TransactionAttachment::find_one(pool, id, transaction_id, &user.uuid).await?;
+
+    // Deleting the blob also deletes the attachment that references it.
+    blob_service
+        .read()
+        .await
+        .delete(&user.uuid, pool, attachment.blob_token)
+        .await?;
+
+    debug!("Deleted attachment '{}'", id);
+    Ok(())
+}
+
+/// A blob can only be attached once, which is reported as a bad request instead of a database
+/// error.
+fn duplicate_attachment_error(error: Error) -> Error {
+    let Error::Sqlx(wrapped_error) = &error else {
+        return error;
+    };
+
+    if wrapped_error.get_constraint() != Some("unique_attachment_blob") {
+        return error;
+    }
+
+    Error::HttpError(HttpError::new(400).message("The file is already attached to a transaction"))
+}
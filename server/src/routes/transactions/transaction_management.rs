@@ -5,11 +5,14 @@ use crate::error::http_error::HttpError;
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
 use crate::models::dto::pagination::pagination_response_dto::PaginationResponseDto;
 use crate::models::dto::transactions::bulk_update_transaction_categories_dto::BulkUpdateTransactionCategoriesDto;
+use crate::models::dto::transactions::bulk_update_transaction_tags_dto::BulkUpdateTransactionTagsDto;
 use crate::models::dto::transactions::transaction_dto::TransactionDto;
+use crate::models::dto::transactions::transaction_search_query_dto::TransactionSearchQueryDto;
 use crate::models::dto::transactions::transaction_set_category_dto::TransactionSetCategoryDto;
 use crate::models::dto::transactions::update_transaction_details_dto::UpdateTransactionDetailsDto;
 use crate::models::dto::transactions::update_transaction_dto::UpdateTransactionDto;
 use crate::models::entities::category::Category;
+use crate::models::entities::tag::Tag;
 use crate::models::entities::transaction::transaction_type::TransactionType;
 
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
@@ -19,6 +22,7 @@ use crate::routes::categories::get_category_by_id;
 use crate::routes::categories::subcategories::get_subcategory_by_id;
 use crate::services::split_service::SplitService;
 use crate::shared::SharedPool;
+use crate::utils::datetime::parse_rfc3339;
 
 #[get("/?<pagination..>")]
 pub async fn get_all_transactions(
@@ -41,6 +45,86 @@ pub async fn get_all_transactions(
     )))
 }
 
+/// Returns the transactions that match all of the given filters, newest first.
+#[get("/search?<query..>")]
+pub async fn search_transactions(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    query: TransactionSearchQueryDto,
+) -> Result<Json<PaginationResponseDto<TransactionDto>>> {
+    let pool = db_inner!(pool);
+    let pagination = query.pagination();
+
+    if query.uncategorized && (query.category_id.is_some() || query.subcategory_id.is_some()) {
+        return Err(HttpError::new(400)
+            .message("Uncategorized transactions cannot be filtered on a category")
+            .into());
+    }
+
+    let mut search = TransactionQuery::new(&user.uuid);
+
+    search = match query.transaction_type {
+        Some(transaction_type) => search.where_type(transaction_type),
+        None => search.where_type_not(TransactionType::Split),
+    };
+
+    if let Some(text) = &query.q {
+        search = search.where_text(text);
+    }
+
+    if let Some(from) = &query.from {
+        search = search.where_date_from(parse_rfc3339(from)?);
+    }
+
+    if let Some(to) = &query.to {
+        search = search.where_date_to(parse_rfc3339(to)?);
+    }
+
+    if let Some(min_amount) = query.min_amount {
+        search = search.where_amount_from(min_amount);
+    }
+
+    if let Some(max_amount) = query.max_amount {
+        search = search.where_amount_to(max_amount);
+    }
+
+    if let Some(category_id) = query.category_id {
+        search = search.where_category(category_id);
+    }
+
+    if let Some(subcategory_id) = query.subcategory_id {
+        search = search.where_subcategory(subcategory_id);
+    }
+
+    if query.uncategorized {
+        search = search.where_uncategorized();
+    }
+
+    if let Some(bank_account_id) = query.bank_account_id {
+        search = search.where_bank_account(bank_account_id);
+    }
+
+    if let Some(tag_id) = query.tag_id {
+        search = search.where_tag(tag_id);
+    }
+
+    if let Some(import_id) = query.import_id {
+        search = search.where_import(import_id);
+    }
+
+    debug!("Searching transactions for user '{}'", user);
+    let transactions = search
+        .order()
+        .paginate(&pagination)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(Json(PaginationResponseDto::from_query(
+        pagination,
+        transactions,
+    )))
+}
+
 #[get("/<id>")]
 pub async fn get_single_transaction(
     pool: &SharedPool,
@@ -278,3 +362,83 @@ pub async fn bulk_update_transaction_categories(
 
     Ok(())
 }
+
+/// Adds and removes tags on multiple transactions at once. Splits can be tagged separately from
+/// their parent transaction.
+#[patch("/bulk-update-tags", data = "<body>")]
+pub async fn bulk_update_transaction_tags(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<BulkUpdateTransactionTagsDto>,
+) -> Result<()> {
+    let inner_pool = db_inner!(pool);
+    let body = body.0;
+
+    if body.transactions.is_empty() {
+        return HttpError::new(400)
+            .message("List of transaction ids cannot be empty")
+            .into();
+    }
+
+    if body.add.is_empty() && body.remove.is_empty() {
+        return HttpError::new(400)
+            .message("There are no tags to add or remove")
+            .into();
+    }
+
+    let record = sqlx::query!(
+        r#"
+            SELECT COUNT(*)
+            FROM transactions
+            WHERE user_id = $1 AND id = ANY($2);
+        "#,
+        user.uuid,
+        &body.transactions[..]
+    )
+    .fetch_one(inner_pool)
+    .await?;
+
+    if record.count.unwrap_or(0) as usize != body.transactions.len() {
+        return HttpError::new(404)
+            .message("Not all transactions exist")
+            .into();
+    }
+
+    debug!("Executing tag guard for tags {:?} with user '{}'", body.add, user);
+    Tag::guard_all(inner_pool, &body.add, &user.uuid).await?;
+
+    debug!("Executing tag guard for tags {:?} with user '{}'", body.remove, user);
+    Tag::guard_all(inner_pool, &body.remove, &user.uuid).await?;
+
+    let mut db_transaction = inner_pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO transaction_tags
+            SELECT transaction_id, tag_id, $1
+            FROM UNNEST($2::varchar[]) transaction_id, UNNEST($3::varchar[]) tag_id
+            ON CONFLICT DO NOTHING;
+        "#,
+        user.uuid,
+        &body.transactions[..],
+        &body.add[..],
+    )
+    .execute(&mut *db_transaction)
+    .await?;
+
+    sqlx::query!(
+        r#"
+            DELETE FROM transaction_tags
+            WHERE user_id = $1 AND transaction_id = ANY($2) AND tag_id = ANY($3);
+        "#,
+        user.uuid,
+        &body.transactions[..],
+        &body.remove[..],
+    )
+    .execute(&mut *db_transaction)
+    .await?;
+
+    db_transaction.commit().await?;
+
+    Ok(())
+}
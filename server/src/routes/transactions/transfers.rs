@@ -0,0 +1,46 @@
+use crate::db_inner;
+use crate::models::entities::transaction::Transaction;
+use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::prelude::*;
+use crate::services::transfer_service::TransferService;
+use crate::shared::SharedPool;
+
+/// Links two transactions on different bank accounts of the user as the outgoing and incoming leg
+/// of a single transfer, which leaves both out of the income and expenses.
+#[post("/<transaction_id>/transfer/<other_id>")]
+pub async fn link_transfer(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    transaction_id: &str,
+    other_id: &str,
+) -> Result<()> {
+    let mut db_transaction = db_inner!(pool).begin().await?;
+
+    TransferService::link(&mut db_transaction, &user.uuid, transaction_id, other_id).await?;
+
+    db_transaction.commit().await?;
+
+    debug!("Linked transfer between '{}' and '{}'", transaction_id, other_id);
+    Ok(())
+}
+
+/// Unlinks both legs of the transfer, so they count as income and expenses again.
+#[delete("/<transaction_id>/transfer")]
+pub async fn unlink_transfer(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    transaction_id: &str,
+) -> Result<()> {
+    let pool = db_inner!(pool);
+
+    Transaction::guard_one(pool, transaction_id, &user.uuid).await?;
+
+    let mut db_transaction = pool.begin().await?;
+
+    TransferService::unlink(&mut db_transaction, &user.uuid, transaction_id).await?;
+
+    db_transaction.commit().await?;
+
+    debug!("Unlinked transfer of '{}'", transaction_id);
+    Ok(())
+}
@@ -24,6 +24,7 @@ pub fn create_user_routes() -> Vec<Route> {
         get_me_info,
         update_me_info,
         update_me_password,
+        update_me_base_currency,
         delete_me,
         enable_mfa_me,
         disable_mfa_me,
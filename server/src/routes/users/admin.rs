@@ -29,7 +29,7 @@ pub async fn admin_get_users(
 
     let records = sqlx::query!(
         r#"
-            SELECT id, username, profile_image, role
+            SELECT id, username, profile_image, role, base_currency
             FROM users;
         "#
     )
@@ -43,6 +43,7 @@ pub async fn admin_get_users(
             username: record.username,
             profile_picture: record.profile_image,
             role: UserRole::from(record.role),
+            base_currency: record.base_currency,
         })
         .collect();
 
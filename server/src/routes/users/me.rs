@@ -8,6 +8,7 @@ use crate::models::dto::account::enable_mfa_dto::EnableMfaDto;
 use crate::models::dto::account::mfa_enabled_response_dto::MfaEnabledResponseDto;
 use crate::models::dto::users::admin_update_user_password_dto::AdminUpdateUserPasswordDto;
 use crate::models::dto::users::admin_user_info_dto::AdminUserInfoDto;
+use crate::models::dto::users::update_base_currency_dto::UpdateBaseCurrencyDto;
 use crate::models::dto::users::update_user_password_dto::UpdateUserPasswordDto;
 use crate::models::dto::users::user_dto::UserDto;
 use crate::models::dto::users::user_info_dto::UserInfoDto;
@@ -17,6 +18,7 @@ use crate::routes::users::shared_resolvers::{
     resolve_delete_user, resolve_update_user_info, resolve_update_user_password, resolve_user_by_id,
 };
 use crate::shared::{SharedBlobService, SharedPool};
+use crate::utils::currency::parse_currency;
 
 #[get("/me")]
 pub async fn get_me_info(pool: &SharedPool, user: JwtUserPayload) -> Result<Json<UserDto>> {
@@ -79,6 +81,32 @@ pub async fn update_me_password(
     .await
 }
 
+/// Changes the currency totals are converted to. Amounts themselves are never changed.
+#[patch("/me/currency", data = "<body>")]
+pub async fn update_me_base_currency(
+    pool: &SharedPool,
+    user: JwtUserPayload,
+    body: Json<UpdateBaseCurrencyDto>,
+) -> Result<()> {
+    let inner_pool = db_inner!(pool);
+    let currency = parse_currency(&body.base_currency)?;
+
+    debug!("Updating base currency of {} to '{}'", user, currency);
+    sqlx::query!(
+        r#"
+            UPDATE users
+            SET base_currency = $2
+            WHERE id = $1;
+        "#,
+        user.uuid,
+        currency
+    )
+    .execute(inner_pool)
+    .await?;
+
+    Ok(())
+}
+
 #[delete("/me")]
 pub async fn delete_me(pool: &SharedPool, user: JwtUserPayload) -> Result<()> {
     info!("{} deleted their own account", user);
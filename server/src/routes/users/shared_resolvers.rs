@@ -7,6 +7,7 @@ use crate::models::dto::users::admin_user_info_dto::AdminUserInfoDto;
 use crate::models::dto::users::user_dto::UserDto;
 use crate::models::entities::user::user_role::UserRole;
 use crate::prelude::*;
+use crate::services::blob_service::BlobUse;
 use crate::shared::{SharedBlobService, SharedPool};
 
 pub async fn resolve_user_by_id(pool: &SharedPool, id: &String) -> Result<Json<UserDto>> {
@@ -14,7 +15,7 @@ pub async fn resolve_user_by_id(pool: &SharedPool, id: &String) -> Result<Json<U
 
     let record = sqlx::query!(
         r#"
-            SELECT id, username, profile_image, role
+            SELECT id, username, profile_image, role, base_currency
             FROM users
             WHERE id = $1;
         "#,
@@ -28,6 +29,7 @@ pub async fn resolve_user_by_id(pool: &SharedPool, id: &String) -> Result<Json<U
         username: record.username,
         profile_picture: record.profile_image,
         role: UserRole::from(record.role),
+        base_currency: record.base_currency,
     }))
 }
 
@@ -41,7 +43,7 @@ pub async fn resolve_update_user_info(
 
     let blob_service = blob_service.read().await;
     let image_token = blob_service
-        .confirm_optional(user_id, inner_pool, body.image_token)
+        .confirm_optional(user_id, inner_pool, body.image_token, BlobUse::ProfileImage)
         .await?;
 
     let role_str: &str = body.role.into();
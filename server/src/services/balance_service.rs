@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use rocket::time::format_description::well_known::Rfc3339;
+use sqlx::types::time::OffsetDateTime;
+
+use crate::models::dto::aggregates::series_query_dto::SeriesInterval;
+use crate::models::dto::bank_accounts::balance_dto::BalanceDto;
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// Calculates bank account balances from the `complete_amount` of the transactions and corrections
+/// of the account. Splits are left out as their amount is already part of the parent transaction.
+pub struct BalanceService;
+
+impl BalanceService {
+    /// Returns the balance of the bank account including all transactions up to and including the
+    /// given datetime.
+    pub async fn balance_at(
+        pool: &DbPool,
+        user_id: &str,
+        bank_account_id: &str,
+        at: OffsetDateTime,
+    ) -> Result<i64> {
+        trace!("Querying balance of bank account '{}'", bank_account_id);
+        let record = sqlx::query!(
+            r#"
+                SELECT SUM(complete_amount)::bigint AS balance
+                FROM transactions
+                WHERE user_id = $1
+                    AND bank_account_id = $2
+                    AND transaction_type IN ('transaction', 'correction')
+                    AND date <= $3;
+            "#,
+            user_id,
+            bank_account_id,
+            at,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record.balance.unwrap_or(0))
+    }
+
+    /// Returns the balance at the end of every period between the given dates. The `to` date is
+    /// exclusive and periods without transactions are included, so the history has no gaps.
+    pub async fn history(
+        pool: &DbPool,
+        user_id: &str,
+        bank_account_id: &str,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+        interval: SeriesInterval,
+    ) -> Result<Vec<BalanceDto>> {
+        trace!("Querying balance history of bank account '{}'", bank_account_id);
+        // The balance of each period is the balance before the first period plus the running total
+        // of the amounts per period, so the transactions are only summed once.
+        let records = sqlx::query!(
+            r#"
+                WITH buckets AS (
+                    SELECT bucket
+                    FROM generate_series(
+                        date_trunc($5, $3::timestamptz, 'UTC'),
+                        $4::timestamptz,
+                        ('1 ' || $5)::interval
+                    ) bucket
+                    WHERE bucket < $4
+                ), amounts AS (
+                    SELECT date_trunc($5, date, 'UTC') AS bucket, SUM(complete_amount) AS amount
+                    FROM transactions
+                    WHERE user_id = $1
+                        AND bank_account_id = $2
+                        AND transaction_type IN ('transaction', 'correction')
+                        AND date >= date_trunc($5, $3::timestamptz, 'UTC')
+                    GROUP BY 1
+                ), opening AS (
+                    SELECT COALESCE(SUM(complete_amount), 0) AS balance
+                    FROM transactions
+                    WHERE user_id = $1
+                        AND bank_account_id = $2
+                        AND transaction_type IN ('transaction', 'correction')
+                        AND date < date_trunc($5, $3::timestamptz, 'UTC')
+                )
+                SELECT
+                    buckets.bucket AS "bucket!",
+                    (
+                        opening.balance
+                        + SUM(COALESCE(amounts.amount, 0)) OVER (ORDER BY buckets.bucket)
+                    )::bigint AS "balance!"
+                FROM buckets
+                CROSS JOIN opening
+                LEFT JOIN amounts ON amounts.bucket = buckets.bucket
+                ORDER BY buckets.bucket;
+            "#,
+            user_id,
+            bank_account_id,
+            from,
+            to,
+            <&str>::from(interval),
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| BalanceDto {
+                date: record.bucket.format(&Rfc3339).expect("Incorrect formatting"),
+                balance: record.balance,
+            })
+            .collect())
+    }
+
+    /// Returns the balance of the bank account directly after each of the given transactions. The
+    /// transactions are ordered by their date and order indicator, which is the order they appear
+    /// in on the bank statement.
+    pub async fn running_balances(
+        pool: &DbPool,
+        user_id: &str,
+        bank_account_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<HashMap<String, i64>> {
+        trace!("Querying running balances of bank account '{}'", bank_account_id);
+        let records = sqlx::query!(
+            r#"
+                SELECT id AS "id!", running_balance AS "running_balance!"
+                FROM (
+                    SELECT
+                        id,
+                        SUM(complete_amount) OVER (ORDER BY date, order_indicator, id)::bigint AS running_balance
+                    FROM transactions
+                    WHERE user_id = $1
+                        AND bank_account_id = $2
+                        AND transaction_type IN ('transaction', 'correction')
+                ) balances
+                WHERE id = ANY($3);
+            "#,
+            user_id,
+            bank_account_id,
+            transaction_ids,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| (record.id, record.running_balance))
+            .collect())
+    }
+}
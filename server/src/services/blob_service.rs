@@ -1,12 +1,20 @@
+use std::collections::HashSet;
 use std::fs;
+use std::fs::DirEntry;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use chrono::Utc;
 use rocket::data::DataStream;
+use rocket::http::ContentType;
 use sqlx::types::time::OffsetDateTime;
 
 use crate::error::blob_error::BlobError;
+use crate::models::dto::blobs::blob_content::BlobContent;
 use crate::prelude::*;
+#[cfg(test)]
+use crate::services::blob_storage::fs_storage::FsStorage;
+use crate::services::blob_storage::{BlobState, BlobStorage};
 use crate::shared::{DbPool, PROJECT_DIRS};
 use crate::utils::rand_string::rand_string;
 
@@ -18,49 +26,46 @@ use crate::utils::rand_string::rand_string;
 ///    to confirm the blob.
 /// 2. Next, the token may be provided in the body of a request, or in some other way. The token
 ///    is used to confirm the blob and makes the blob permanent and from that point the blob can
-///    be returned using the [get_blob] or [get_blob_with_mimetypes] methods.
+///    be returned using the [get_blob] or [get_blob_with_mimetype] methods.
+/// 3. Once nothing references the blob anymore, [cleanup] removes it together with its file.
 pub struct BlobService {
     max_blob_unconfirmed: u32,
     stream_to_root: PathBuf,
-    unconfirmed_root: PathBuf,
-    confirmed_root: PathBuf,
+    storage: Box<dyn BlobStorage>,
 }
 
 impl BlobService {
-    pub fn new(max_blob_unconfirmed: u32) -> Result<Self> {
+    pub fn new(max_blob_unconfirmed: u32, storage: Box<dyn BlobStorage>) -> Result<Self> {
         // This is the path the file is initially written to to infer the mime type to the token
-        // can be generated.
+        // can be generated. It's always local, whatever storage is used for the blobs.
         let stream_to_root = PROJECT_DIRS
             .get()
             .expect("Initialized")
             .cache_dir()
             .to_path_buf();
 
-        let unconfirmed_root = PROJECT_DIRS
-            .get()
-            .expect("Initialized")
-            .cache_dir()
-            .join("unconfirmed");
-
-        let confirmed_root = PROJECT_DIRS
-            .get()
-            .expect("Initialized")
-            .data_dir()
-            .join("blobs");
-
         fs::create_dir_all(&stream_to_root)?;
-        fs::create_dir_all(&unconfirmed_root)?;
-        fs::create_dir_all(&confirmed_root)?;
 
         debug!("'{:?}' is the root to stream new files to", stream_to_root);
-        debug!("'{:?}' is the root for unconfirmed blobs", unconfirmed_root);
-        debug!("'{:?}' is the root for confirmed blobs", confirmed_root);
 
         Ok(Self {
             max_blob_unconfirmed,
             stream_to_root,
-            unconfirmed_root,
-            confirmed_root,
+            storage,
+        })
+    }
+
+    /// Creates a blob service that stores everything in a new directory in the temp directory, so
+    /// tests don't need the project directories.
+    #[cfg(test)]
+    pub fn new_test() -> Result<Self> {
+        let root = std::env::temp_dir().join(format!("ledgero-blobs-{}", rand_string(16)));
+        let storage = FsStorage::new(root.join("unconfirmed"), root.join("blobs"))?;
+
+        Ok(Self {
+            max_blob_unconfirmed: 3600,
+            stream_to_root: root,
+            storage: Box::new(storage),
         })
     }
 
@@ -79,8 +84,20 @@ impl BlobService {
         debug!("Streaming into '{:?}'", temp_file);
         stream.into_file(&temp_file).await?;
 
+        self.upload_file(user_id, pool, &id, temp_file).await
+    }
+
+    /// Turns a file that was written to the stream root into an unconfirmed blob.
+    async fn upload_file(
+        &self,
+        user_id: impl Into<String>,
+        pool: &DbPool,
+        id: &str,
+        temp_file: PathBuf,
+    ) -> Result<String> {
         let Some(file_meta) = infer::get_from_path(&temp_file)? else {
             info!("Failed to infer mimetype for '{:?}'", temp_file);
+            fs::remove_file(&temp_file)?;
             return Err(BlobError::NoMimeType.into());
         };
 
@@ -103,30 +120,41 @@ impl BlobService {
         .execute(pool)
         .await?;
 
-        let target_location = self.unconfirmed_root.join(&token);
-
-        debug!(
-            "Moving streamed file from '{:?}' to '{:?}'",
-            temp_file, target_location
-        );
-        fs::rename(temp_file, target_location)?;
+        self.storage.put_unconfirmed(&token, &temp_file).await?;
 
         Ok(token)
     }
 
-    /// Does the same thing as the [confirm_token] method, but accepts an Option type. If the option
-    /// has Some value the blob is confirmed and otherwise it's ignored.
+    /// Stores the bytes like [upload_stream] would, for tests that cannot build a [DataStream].
+    #[cfg(test)]
+    pub async fn upload_bytes(
+        &self,
+        user_id: impl Into<String>,
+        pool: &DbPool,
+        bytes: &[u8],
+    ) -> Result<String> {
+        let id = rand_string(32);
+        let temp_file = self.stream_to_root.join(&id);
+
+        fs::write(&temp_file, bytes)?;
+
+        self.upload_file(user_id, pool, &id, temp_file).await
+    }
+
+    /// Does the same thing as the [confirm_token_for_use] method, but accepts an Option type. If
+    /// the option has Some value the blob is confirmed and otherwise it's ignored.
     pub async fn confirm_optional(
         &self,
         user_id: impl Into<String>,
         pool: &DbPool,
         token: Option<impl Into<String>>,
+        blob_use: BlobUse,
     ) -> Result<Option<String>> {
         let Some(token) = token else {
             return Ok(None);
         };
 
-        Ok(Some(self.confirm_token(user_id, pool, token).await?))
+        Ok(Some(self.confirm_token_for_use(user_id, pool, token, blob_use).await?))
     }
 
     /// Confirms that the blob is now used somewhere and should be available. There are a couple of
@@ -165,24 +193,14 @@ impl BlobService {
             return Err(BlobError::NoBlobToConfirm.into());
         }
 
-        let file_path = self.confirmed_root.join(&token);
-
-        if file_path.exists() {
+        if self.storage.exists(BlobState::Confirmed, &token).await? {
             return Ok(token);
         }
 
-        let unconfirmed_file_path = self.unconfirmed_root.join(&token);
-
-        if !unconfirmed_file_path.exists() {
+        if !self.storage.confirm(&token).await? {
             return Err(BlobError::NoBlobToConfirm.into());
         }
 
-        debug!(
-            "Move unconfirmed blob from '{:?}' to '{:?}'",
-            unconfirmed_file_path, file_path
-        );
-        fs::rename(unconfirmed_file_path, file_path)?;
-
         sqlx::query!(
             r#"
                 UPDATE blobs
@@ -199,19 +217,193 @@ impl BlobService {
         Ok(token)
     }
 
+    /// Confirms the token like [confirm_token], but only when the uploaded file has one of the
+    /// mime types that are allowed for the given use.
+    pub async fn confirm_token_for_use(
+        &self,
+        user_id: impl Into<String>,
+        pool: &DbPool,
+        token: impl Into<String>,
+        blob_use: BlobUse,
+    ) -> Result<String> {
+        let user_id = user_id.into();
+        let token = token.into();
+
+        BlobService::check_token_for_use(&user_id, pool, &token, blob_use).await?;
+
+        self.confirm_token(user_id, pool, token).await
+    }
+
+    /// Checks the uploaded file of the token exists and has one of the mime types that are allowed
+    /// for the given use, without confirming it. Returns the mime type of the file.
+    pub async fn check_token_for_use(
+        user_id: &str,
+        pool: &DbPool,
+        token: &str,
+        blob_use: BlobUse,
+    ) -> Result<String> {
+        let record = sqlx::query!(
+            r#"
+                SELECT mime_type
+                FROM blobs
+                WHERE token = $1 AND user_id = $2;
+            "#,
+            token,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(record) = record else {
+            return Err(BlobError::NoBlobToConfirm.into());
+        };
+
+        if !blob_use.mimetypes().contains(&record.mime_type.as_str()) {
+            return Err(BlobError::MimeTypeNotAllowed.into());
+        }
+
+        Ok(record.mime_type)
+    }
+
     /// Returns a stream for the given blob if it exists.
-    pub async fn get_blob(&self, _token: impl Into<String>) -> Result<()> {
-        todo!()
+    pub async fn get_blob(&self, token: impl Into<String>) -> Result<BlobContent> {
+        let token = token.into();
+
+        let Some(mimetype) = BlobService::mimetype_from_token(&token) else {
+            return Err(BlobError::NotFound.into());
+        };
+
+        let Some(reader) = self.storage.get(&token).await? else {
+            return Err(BlobError::NotFound.into());
+        };
+
+        Ok(BlobContent {
+            reader,
+            content_type: ContentType::parse_flexible(&mimetype).unwrap_or(ContentType::Binary),
+        })
     }
 
     /// Returns a stream for the given blob if it exists and matches at least one of the provided
     /// mime types.
-    pub async fn get_blob_with_mimetype(&self, _token: impl Into<String>) -> Result<()> {
-        todo!()
+    pub async fn get_blob_with_mimetype(
+        &self,
+        token: impl Into<String>,
+        mimetypes: &[&str],
+    ) -> Result<BlobContent> {
+        let token = token.into();
+
+        let Some(mimetype) = BlobService::mimetype_from_token(&token) else {
+            return Err(BlobError::NotFound.into());
+        };
+
+        if !mimetypes.contains(&mimetype.as_str()) {
+            return Err(BlobError::MimeTypeNotAllowed.into());
+        }
+
+        self.get_blob(token).await
+    }
+
+    /// Deletes the blob and its file. Anything referencing the blob is updated by the database.
+    pub async fn delete(
+        &self,
+        user_id: impl Into<String>,
+        pool: &DbPool,
+        token: impl Into<String>,
+    ) -> Result<()> {
+        let user_id = user_id.into();
+        let token = token.into();
+        debug!("Deleting blob with token '{}' for '{}'", token, user_id);
+
+        sqlx::query!(
+            r#"
+                DELETE FROM blobs
+                WHERE token = $1 AND user_id = $2;
+            "#,
+            token,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+
+        if BlobService::mimetype_from_token(&token).is_none() {
+            return Ok(());
+        }
+
+        self.storage.delete(BlobState::Confirmed, &token).await?;
+        self.storage.delete(BlobState::Unconfirmed, &token).await
+    }
+
+    /// Tokens are formatted as `storage-<base64 mime type>-<id>`. As tokens are also used as file
+    /// names and object keys, anything else is rejected so a token cannot point outside of the
+    /// storage.
+    fn mimetype_from_token(token: &str) -> Option<String> {
+        let (encoded, id) = token.strip_prefix("storage-")?.rsplit_once('-')?;
+
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        let mimetype = base64_url::decode(encoded).ok()?;
+
+        String::from_utf8(mimetype).ok()
+    }
+
+    /// Finds out where a confirmed blob of the user is used. Blobs that are not used anywhere
+    /// cannot be found.
+    pub async fn find_use(
+        &self,
+        user_id: impl Into<String>,
+        pool: &DbPool,
+        token: impl Into<String>,
+    ) -> Result<BlobUse> {
+        let record = sqlx::query!(
+            r#"
+                SELECT
+                    EXISTS(
+                        SELECT u.id
+                        FROM users u
+                        WHERE u.profile_image = b.token AND u.id = b.user_id
+                    ) AS "profile_image!",
+                    EXISTS(
+                        SELECT e.id
+                        FROM external_accounts e
+                        WHERE e.image = b.token AND e.user_id = b.user_id
+                    ) AS "external_account_image!",
+                    EXISTS(
+                        SELECT a.id
+                        FROM transaction_attachments a
+                        WHERE a.blob_token = b.token AND a.user_id = b.user_id
+                    ) AS "attachment!"
+                FROM blobs b
+                WHERE b.token = $1 AND b.user_id = $2 AND b.confirmed_at IS NOT null;
+            "#,
+            token.into(),
+            user_id.into()
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        match record {
+            Some(record) if record.profile_image => Ok(BlobUse::ProfileImage),
+            Some(record) if record.external_account_image => Ok(BlobUse::ExternalAccountImage),
+            Some(record) if record.attachment => Ok(BlobUse::Attachment),
+            _ => Err(BlobError::NotFound.into()),
+        }
     }
 
-    pub async fn cleanup(&self, pool: &DbPool) -> Result<()> {
+    /// Removes everything that is no longer needed:
+    ///
+    /// * Blobs that were never confirmed in time.
+    /// * Confirmed blobs that nothing references anymore, for example because the transaction with
+    ///   the attachment was deleted.
+    /// * Files without a blob record and streamed files that were never turned into a blob, for
+    ///   example because the upload was interrupted.
+    ///
+    /// Files are only removed once they are older than the time a blob can stay unconfirmed, so
+    /// uploads and confirmations that happen during the cleanup are left alone.
+    pub async fn cleanup(&self, pool: &DbPool) -> Result<CleanupReport> {
         info!("Starting blob cleanup");
+        let mut report = CleanupReport::default();
 
         let result = sqlx::query!(
             r#"
@@ -223,25 +415,166 @@ impl BlobService {
         .execute(pool)
         .await?;
 
-        info!(
-            "Deleted {} blob records from the database",
-            result.rows_affected()
-        );
+        report.records_deleted += result.rows_affected();
 
-        let _records = sqlx::query!(
+        let result = sqlx::query!(
             r#"
-                SELECT token,
-                       COUNT(e.*) +
-                       COUNT(u.*) AS "references"
-                FROM blobs
-                LEFT JOIN external_accounts e on blobs.token = e.image
-                LEFT JOIN users u on blobs.token = u.profile_image
-                GROUP BY blobs.token;
-            "#
+                DELETE FROM blobs b
+                WHERE confirmed_at IS NOT null
+                    AND EXTRACT(EPOCH FROM (now() - confirmed_at)) > $1::bigint
+                    AND NOT EXISTS(
+                        SELECT e.id
+                        FROM external_accounts e
+                        WHERE e.image = b.token AND e.user_id = b.user_id
+                    )
+                    AND NOT EXISTS(
+                        SELECT u.id
+                        FROM users u
+                        WHERE u.profile_image = b.token AND u.id = b.user_id
+                    )
+                    AND NOT EXISTS(
+                        SELECT a.id
+                        FROM transaction_attachments a
+                        WHERE a.blob_token = b.token AND a.user_id = b.user_id
+                    );
+            "#,
+            self.max_blob_unconfirmed as i64
         )
-        .fetch_all(pool)
+        .execute(pool)
         .await?;
 
+        report.records_deleted += result.rows_affected();
+
+        info!(
+            "Deleted {} blob records from the database",
+            report.records_deleted
+        );
+
+        let tokens: HashSet<String> = sqlx::query_scalar!("SELECT token FROM blobs")
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+
+        let max_age = chrono::Duration::seconds(self.max_blob_unconfirmed as i64);
+
+        for state in [BlobState::Unconfirmed, BlobState::Confirmed] {
+            for blob in self.storage.list(state).await? {
+                if tokens.contains(&blob.token) || Utc::now() - blob.modified <= max_age {
+                    continue;
+                }
+
+                self.storage.delete(state, &blob.token).await?;
+
+                report.files_deleted += 1;
+                report.bytes_reclaimed += blob.size;
+            }
+        }
+
+        for entry in fs::read_dir(&self.stream_to_root)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            // Only the files streamed by [upload_stream] are removed, as they are named after the
+            // random id of the upload.
+            let is_upload = name.len() == 32 && name.chars().all(|c| c.is_ascii_hexdigit());
+
+            if !is_upload || !self.is_stale(&entry)? {
+                continue;
+            }
+
+            self.remove_file(entry, &mut report)?;
+        }
+
+        info!(
+            "Deleted {} blob files, reclaiming {} bytes",
+            report.files_deleted, report.bytes_reclaimed
+        );
+
+        Ok(report)
+    }
+
+    fn is_stale(&self, entry: &DirEntry) -> Result<bool> {
+        let metadata = entry.metadata()?;
+
+        if !metadata.is_file() {
+            return Ok(false);
+        }
+
+        let age = metadata.modified()?.elapsed().unwrap_or_default();
+
+        Ok(age > Duration::from_secs(self.max_blob_unconfirmed as u64))
+    }
+
+    fn remove_file(&self, entry: DirEntry, report: &mut CleanupReport) -> Result<()> {
+        let size = entry.metadata()?.len();
+
+        debug!("Removing blob file '{:?}'", entry.path());
+        fs::remove_file(entry.path())?;
+
+        report.files_deleted += 1;
+        report.bytes_reclaimed += size;
+
+        Ok(())
+    }
+
+    /// Moves the modification time of all files back, as if they were written `age` ago. The
+    /// test service keeps the blobs in directories inside the stream root.
+    #[cfg(test)]
+    pub fn backdate_files(&self, age: Duration) -> Result<()> {
+        let modified = std::time::SystemTime::now() - age;
+        let mut directories = vec![self.stream_to_root.clone()];
+
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(directory)? {
+                let entry = entry?;
+
+                if entry.metadata()?.is_dir() {
+                    directories.push(entry.path());
+                    continue;
+                }
+
+                fs::File::options()
+                    .write(true)
+                    .open(entry.path())?
+                    .set_modified(modified)?;
+            }
+        }
+
         Ok(())
     }
+
+    #[cfg(test)]
+    pub fn stream_to_root(&self) -> &std::path::Path {
+        &self.stream_to_root
+    }
+}
+
+/// Where a blob is used, which decides what kind of files are accepted.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BlobUse {
+    ProfileImage,
+    ExternalAccountImage,
+    Attachment,
+}
+
+impl BlobUse {
+    pub fn mimetypes(self) -> &'static [&'static str] {
+        match self {
+            BlobUse::ProfileImage | BlobUse::ExternalAccountImage => {
+                &["image/jpeg", "image/png", "image/gif", "image/webp"]
+            }
+
+            // Receipts and other documents are expected to be scans or photos.
+            BlobUse::Attachment => &["application/pdf", "image/jpeg", "image/png"],
+        }
+    }
+}
+
+/// What a single run of [BlobService::cleanup] removed.
+#[derive(Debug, Default)]
+pub struct CleanupReport {
+    pub records_deleted: u64,
+    pub files_deleted: u64,
+    pub bytes_reclaimed: u64,
 }
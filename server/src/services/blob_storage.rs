@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rocket::tokio::io::AsyncRead;
+
+use crate::init::blob_storage_options::BlobStorageOptions;
+use crate::prelude::*;
+use crate::services::blob_storage::fs_storage::FsStorage;
+use crate::services::blob_storage::s3_storage::S3Storage;
+use crate::shared::PROJECT_DIRS;
+
+pub mod fs_storage;
+pub mod s3_storage;
+
+/// Reads the contents of a stored blob.
+pub type BlobReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// Blobs are kept apart until they are confirmed, so unconfirmed blobs can be cleaned up without
+/// looking at the database.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BlobState {
+    Unconfirmed,
+    Confirmed,
+}
+
+/// A blob as it is found in the storage.
+#[derive(Debug)]
+pub struct StoredBlob {
+    pub token: String,
+    pub size: u64,
+    pub modified: DateTime<Utc>,
+}
+
+/// Stores the files of blobs. The [BlobService](crate::services::blob_service::BlobService)
+/// keeps track of the blobs in the database and only uses the storage for the files, which are
+/// identified by their token.
+#[rocket::async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Stores the local file as an unconfirmed blob. The file is moved or removed afterwards.
+    async fn put_unconfirmed(&self, token: &str, file: &Path) -> Result<()>;
+
+    /// Moves an unconfirmed blob to the confirmed blobs. Returns false when there is no
+    /// unconfirmed blob with the token.
+    async fn confirm(&self, token: &str) -> Result<bool>;
+
+    async fn exists(&self, state: BlobState, token: &str) -> Result<bool>;
+
+    /// Opens a confirmed blob, or returns [None] if it doesn't exist.
+    async fn get(&self, token: &str) -> Result<Option<BlobReader>>;
+
+    /// Deletes the blob. Deleting a blob that doesn't exist is not an error.
+    async fn delete(&self, state: BlobState, token: &str) -> Result<()>;
+
+    async fn list(&self, state: BlobState) -> Result<Vec<StoredBlob>>;
+}
+
+/// Creates the storage that is configured in the start options. Without a root for the
+/// filesystem, the confirmed blobs are stored in the data directory and the unconfirmed ones in
+/// the cache directory, like before the storage could be configured.
+pub fn create_blob_storage(options: BlobStorageOptions) -> Result<Box<dyn BlobStorage>> {
+    let storage: Box<dyn BlobStorage> = match options {
+        BlobStorageOptions::Filesystem { root: Some(root) } => {
+            Box::new(FsStorage::new(root.join("unconfirmed"), root.join("blobs"))?)
+        }
+        BlobStorageOptions::Filesystem { root: None } => {
+            let project_dirs = PROJECT_DIRS.get().expect("Initialized");
+
+            Box::new(FsStorage::new(
+                project_dirs.cache_dir().join("unconfirmed"),
+                project_dirs.data_dir().join("blobs"),
+            )?)
+        }
+        BlobStorageOptions::S3(options) => Box::new(S3Storage::new(options)?),
+    };
+
+    Ok(storage)
+}
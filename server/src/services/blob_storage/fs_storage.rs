@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rocket::tokio::fs::File;
+
+use crate::prelude::*;
+use crate::services::blob_storage::{BlobReader, BlobState, BlobStorage, StoredBlob};
+
+/// Stores every blob as a file named after its token, in a directory per [BlobState].
+pub struct FsStorage {
+    unconfirmed_root: PathBuf,
+    confirmed_root: PathBuf,
+}
+
+impl FsStorage {
+    pub fn new(unconfirmed_root: PathBuf, confirmed_root: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&unconfirmed_root)?;
+        fs::create_dir_all(&confirmed_root)?;
+
+        debug!("'{:?}' is the root for unconfirmed blobs", unconfirmed_root);
+        debug!("'{:?}' is the root for confirmed blobs", confirmed_root);
+
+        Ok(Self {
+            unconfirmed_root,
+            confirmed_root,
+        })
+    }
+
+    fn path(&self, state: BlobState, token: &str) -> PathBuf {
+        match state {
+            BlobState::Unconfirmed => self.unconfirmed_root.join(token),
+            BlobState::Confirmed => self.confirmed_root.join(token),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl BlobStorage for FsStorage {
+    async fn put_unconfirmed(&self, token: &str, file: &Path) -> Result<()> {
+        let target_location = self.path(BlobState::Unconfirmed, token);
+
+        debug!("Moving streamed file from '{:?}' to '{:?}'", file, target_location);
+
+        // Renaming fails when the file was streamed to another filesystem, like a different
+        // volume in a container, in which case it's copied instead.
+        if fs::rename(file, &target_location).is_err() {
+            fs::copy(file, &target_location)?;
+            fs::remove_file(file)?;
+        }
+
+        Ok(())
+    }
+
+    async fn confirm(&self, token: &str) -> Result<bool> {
+        let unconfirmed_file_path = self.path(BlobState::Unconfirmed, token);
+
+        if !unconfirmed_file_path.exists() {
+            return Ok(false);
+        }
+
+        let file_path = self.path(BlobState::Confirmed, token);
+
+        debug!(
+            "Move unconfirmed blob from '{:?}' to '{:?}'",
+            unconfirmed_file_path, file_path
+        );
+        fs::rename(unconfirmed_file_path, file_path)?;
+
+        Ok(true)
+    }
+
+    async fn exists(&self, state: BlobState, token: &str) -> Result<bool> {
+        Ok(self.path(state, token).exists())
+    }
+
+    async fn get(&self, token: &str) -> Result<Option<BlobReader>> {
+        let file_path = self.path(BlobState::Confirmed, token);
+
+        if !file_path.exists() {
+            return Ok(None);
+        }
+
+        debug!("Opening blob '{:?}'", file_path);
+        let file = File::open(file_path).await?;
+
+        Ok(Some(Box::new(file)))
+    }
+
+    async fn delete(&self, state: BlobState, token: &str) -> Result<()> {
+        let file_path = self.path(state, token);
+
+        if file_path.exists() {
+            debug!("Removing blob file '{:?}'", file_path);
+            fs::remove_file(file_path)?;
+        }
+
+        Ok(())
+    }
+
+    async fn list(&self, state: BlobState) -> Result<Vec<StoredBlob>> {
+        let root = match state {
+            BlobState::Unconfirmed => &self.unconfirmed_root,
+            BlobState::Confirmed => &self.confirmed_root,
+        };
+
+        let mut blobs = vec![];
+
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+
+            if !metadata.is_file() {
+                continue;
+            }
+
+            blobs.push(StoredBlob {
+                token: entry.file_name().to_string_lossy().to_string(),
+                size: metadata.len(),
+                modified: DateTime::<Utc>::from(metadata.modified()?),
+            });
+        }
+
+        Ok(blobs)
+    }
+}
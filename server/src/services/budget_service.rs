@@ -0,0 +1,192 @@
+use std::slice;
+
+use chrono::{DateTime, Utc};
+use sqlx::types::time::OffsetDateTime;
+
+use crate::models::dto::budgets::budget_overview_dto::BudgetOverviewDto;
+use crate::models::entities::budget::Budget;
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+pub struct BudgetService;
+
+impl BudgetService {
+    /// Calculates the state of the budget for the period the given datetime falls in.
+    pub async fn overview(
+        pool: &DbPool,
+        budget: &Budget,
+        at: DateTime<Utc>,
+    ) -> Result<BudgetOverviewDto> {
+        let mut overviews =
+            Self::overviews(pool, &budget.user_id, slice::from_ref(budget), at).await?;
+
+        Ok(overviews.remove(0))
+    }
+
+    /// Calculates the state of all the given budgets of the user for the period the given datetime
+    /// falls in. The spending of every budget is summed per period in a single query.
+    pub async fn overviews(
+        pool: &DbPool,
+        user_id: &str,
+        budgets: &[Budget],
+        at: DateTime<Utc>,
+    ) -> Result<Vec<BudgetOverviewDto>> {
+        let period_starts: Vec<Vec<DateTime<Utc>>> = budgets
+            .iter()
+            .map(|budget| period_starts(budget, at))
+            .collect();
+
+        let mut budget_ids = vec![];
+        let mut category_ids = vec![];
+        let mut subcategory_ids = vec![];
+        let mut periods = vec![];
+        let mut starts = vec![];
+        let mut ends = vec![];
+
+        for (budget, budget_starts) in budgets.iter().zip(&period_starts) {
+            for (index, start) in budget_starts.iter().enumerate() {
+                budget_ids.push(budget.id.to_string());
+                category_ids.push(budget.category_id.to_string());
+                subcategory_ids.push(budget.subcategory_id.clone());
+                periods.push(index as i32);
+                starts.push(OffsetDateTime::from_unix_timestamp(start.timestamp())?);
+                ends.push(OffsetDateTime::from_unix_timestamp(
+                    budget.period.next(*start).timestamp(),
+                )?);
+            }
+        }
+
+        trace!("Querying spending for {} budgets", budgets.len());
+        let records = sqlx::query!(
+            r#"
+                WITH spending AS (
+                    SELECT periods.budget_id,
+                           periods.period,
+                           transactions.currency,
+                           convert_to_base(transactions.user_id, transactions.amount,
+                                           transactions.currency, transactions.date) AS amount
+                    FROM UNNEST($2::varchar[], $3::varchar[], $4::varchar[], $5::int[],
+                                $6::timestamptz[], $7::timestamptz[])
+                        AS periods(budget_id, category_id, subcategory_id, period, period_start, period_end)
+                    JOIN transactions
+                        ON transactions.user_id = $1
+                        AND transactions.category_id = periods.category_id
+                        AND (periods.subcategory_id IS NULL
+                            OR transactions.subcategory_id = periods.subcategory_id)
+                        AND transactions.date >= periods.period_start
+                        AND transactions.date < periods.period_end
+                        AND transactions.related_transfer_transaction IS NULL
+                )
+                SELECT budget_id AS "budget_id!",
+                       period AS "period!",
+                       COALESCE(SUM(-amount), 0)::bigint AS "spent!",
+                       ARRAY_AGG(DISTINCT currency) FILTER (WHERE amount IS NULL) AS missing_rates
+                FROM spending
+                GROUP BY budget_id, period;
+            "#,
+            user_id,
+            &budget_ids,
+            &category_ids,
+            &subcategory_ids as _,
+            &periods,
+            &starts,
+            &ends,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut overviews = vec![];
+
+        for (budget, budget_starts) in budgets.iter().zip(period_starts) {
+            // The amount spent in every period up to and including the requested period.
+            let mut spent_per_period = vec![0; budget_starts.len()];
+            let mut missing_rates = vec![];
+
+            for record in records.iter().filter(|record| record.budget_id == budget.id) {
+                spent_per_period[record.period as usize] = record.spent;
+                missing_rates.extend(record.missing_rates.iter().flatten().cloned());
+            }
+
+            missing_rates.sort();
+            missing_rates.dedup();
+
+            let period_start = *budget_starts.last().unwrap();
+            let period_end = budget.period.next(period_start);
+
+            let actual = spent_per_period.pop().unwrap_or(0);
+            let rolled_over = calculate_rollover(budget.amount, &spent_per_period);
+
+            overviews.push(BudgetOverviewDto {
+                budget_id: budget.id.to_string(),
+                category_id: budget.category_id.to_string(),
+                subcategory_id: budget.subcategory_id.clone(),
+                period_start: period_start.to_rfc3339(),
+                period_end: period_end.to_rfc3339(),
+                budgeted: budget.amount,
+                rolled_over,
+                actual,
+                remaining: budget.amount + rolled_over - actual,
+                missing_rates,
+            });
+        }
+
+        Ok(overviews)
+    }
+}
+
+/// Returns the start of every period of the budget up to and including the period the given
+/// datetime falls in. Only the requested period is returned when the budget doesn't roll over, as
+/// the periods before it are only needed to carry the unspent money over.
+fn period_starts(budget: &Budget, at: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let period = budget.period;
+    let period_start = period.start_of(at);
+
+    let mut start = if budget.rollover {
+        period.start_of(budget.starts_at).min(period_start)
+    } else {
+        period_start
+    };
+
+    let mut starts = vec![];
+
+    while start <= period_start {
+        starts.push(start);
+        start = period.next(start);
+    }
+
+    starts
+}
+
+/// Carries the unspent money over from period to period. When more is spent than is available in a
+/// period, the carried over amount is reset to zero instead of going negative, so overspending is
+/// not deducted from future periods.
+fn calculate_rollover(amount: i64, spent_per_period: &[i64]) -> i64 {
+    spent_per_period
+        .iter()
+        .fold(0, |carried, spent| (carried + amount - spent).max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::budget_service::calculate_rollover;
+
+    #[test]
+    fn nothing_is_rolled_over_without_previous_periods() {
+        assert_eq!(calculate_rollover(10000, &[]), 0);
+    }
+
+    #[test]
+    fn unspent_money_is_added_up() {
+        assert_eq!(calculate_rollover(10000, &[2500, 10000, 0]), 17500);
+    }
+
+    #[test]
+    fn overspending_resets_the_rollover() {
+        assert_eq!(calculate_rollover(10000, &[5000, 20000, 8000]), 2000);
+    }
+
+    #[test]
+    fn income_in_a_period_increases_the_rollover() {
+        assert_eq!(calculate_rollover(10000, &[-5000]), 15000);
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::models::dto::transactions::new_correction_dto::NewCorrectionDto;
+use crate::models::entities::bank_account::BankAccount;
+use crate::models::entities::transaction::transaction_type::TransactionType;
+use crate::models::entities::transaction::Transaction;
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+pub struct CorrectionService;
+
+impl CorrectionService {
+    /// Creates a correction at the given date and returns the id of the new correction.
+    pub async fn create_correction(
+        pool: &DbPool,
+        user_id: &str,
+        body: NewCorrectionDto,
+        date: DateTime<Utc>,
+    ) -> Result<String> {
+        trace!("Finding max order index");
+        let record = sqlx::query!(
+            r#"
+                SELECT MAX(order_indicator)
+                FROM transactions
+                WHERE user_id = $1;
+            "#,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let currency = BankAccount::find_currency(pool, &body.bank_account_id, user_id).await?;
+
+        let transaction = Transaction {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            transaction_type: TransactionType::Correction,
+            follow_number: Uuid::new_v4().to_string(),
+            original_description: body.description.to_string(),
+            description: body.description,
+            complete_amount: body.amount,
+            amount: body.amount,
+            date,
+            bank_account_id: Some(body.bank_account_id),
+            category_id: body.category_id,
+            parent_transaction_id: None,
+            external_account_name: "Correction".to_string(),
+            external_account_id: None,
+            external_account_name_id: None,
+            external_account_iban: None,
+            parent_import_id: None,
+            subcategory_id: body.subcategory_id,
+            order_indicator: record.max.unwrap_or(0) + 1,
+            related_move_transaction: None,
+            related_transfer_transaction: None,
+            currency,
+        };
+
+        debug!("Creating new correction '{}'", transaction.id);
+        transaction.create(pool).await?;
+
+        Ok(transaction.id)
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::Cursor;
+
+use chrono::{Datelike, NaiveDate};
+use csv::{StringRecord, Trim};
+use sqlx::types::time::Date;
+
+use crate::error::import_error::ImportError;
+use crate::models::entities::exchange_rate::ExchangeRate;
+use crate::prelude::*;
+use crate::routes::importing::map_csv_record::record_row;
+use crate::utils::currency::is_currency_code;
+
+/// Reads the exchange rates from a CSV in the format the ECB publishes its reference rates in. The
+/// first column holds the date and every other column the rates of one currency against the euro:
+///
+/// ```text
+/// Date, USD, JPY, GBP,
+/// 2023-01-10, 1.0747, 141.79, 0.88245,
+/// ```
+///
+/// Values that are empty or `N/A`, used by the ECB for currencies without a rate on that day, are
+/// skipped.
+pub fn parse_ecb_rates(user_id: &str, csv: &str) -> Result<Vec<ExchangeRate>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(Trim::All)
+        .flexible(true)
+        .from_reader(Cursor::new(csv));
+
+    let header = reader.headers()?.clone();
+
+    if !header
+        .get(0)
+        .is_some_and(|column| column.eq_ignore_ascii_case("date"))
+    {
+        return Err(ImportError::missing_column("Date").into());
+    }
+
+    // The ECB ends every line with a separator, which results in an empty column at the end.
+    let currencies: Vec<(usize, String)> = header
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, column)| !column.is_empty())
+        .map(|(index, column)| (index, column.to_uppercase()))
+        .collect();
+
+    if let Some((_, currency)) = currencies
+        .iter()
+        .find(|(_, currency)| !is_currency_code(currency))
+    {
+        return Err(ImportError::invalid_value(1, currency, "Not a currency code").into());
+    }
+
+    let mut rates = vec![];
+
+    for record in reader.records() {
+        let record = record?;
+        let row = record_row(&record);
+        let date = parse_date(&record, row)?;
+
+        for (index, currency) in &currencies {
+            let Some(value) = record.get(*index) else {
+                continue;
+            };
+
+            if value.is_empty() || value.eq_ignore_ascii_case("n/a") {
+                continue;
+            }
+
+            let rate = value
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| rate.is_finite() && *rate > 0.0)
+                .ok_or_else(|| {
+                    ImportError::invalid_value(row, currency, "Not a positive number")
+                })?;
+
+            rates.push(ExchangeRate {
+                user_id: user_id.to_string(),
+                currency: currency.clone(),
+                date,
+                rate,
+            });
+        }
+    }
+
+    if rates.is_empty() {
+        return Err(ImportError::NoRows.into());
+    }
+
+    Ok(rates)
+}
+
+fn parse_date(record: &StringRecord, row: u64) -> Result<Date> {
+    let invalid = || ImportError::invalid_value(row, "Date", "Expected a date like 2023-01-31");
+
+    let value = record.get(0).unwrap_or_default();
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| invalid())?;
+
+    // The time crate is only available through sqlx, which does not expose its `Month`.
+    let date = Date::from_ordinal_date(date.year(), date.ordinal() as u16).map_err(|_| invalid())?;
+
+    Ok(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::time::Date;
+
+    use crate::services::exchange_rate_service::parse_ecb_rates;
+
+    #[test]
+    fn ecb_csv_is_parsed() {
+        let csv = "Date, USD, JPY, CYP, \n\
+            2023-01-10, 1.0747, 141.79, N/A, \n\
+            2023-01-09, 1.0741, 141.82, N/A, \n";
+
+        let rates = parse_ecb_rates("user", csv).unwrap();
+
+        assert_eq!(rates.len(), 4);
+        assert_eq!(rates[0].currency, "USD");
+        assert_eq!(rates[0].rate, 1.0747);
+        assert_eq!(
+            rates[0].date,
+            Date::from_ordinal_date(2023, 10).unwrap()
+        );
+        assert_eq!(rates[3].currency, "JPY");
+        assert_eq!(rates[3].rate, 141.82);
+    }
+
+    #[test]
+    fn invalid_rates_are_rejected() {
+        assert!(parse_ecb_rates("user", "Date,USD\n2023-01-10,-1\n").is_err());
+        assert!(parse_ecb_rates("user", "Date,USD\n10-01-2023,1.07\n").is_err());
+        assert!(parse_ecb_rates("user", "Day,USD\n2023-01-10,1.07\n").is_err());
+        assert!(parse_ecb_rates("user", "Date,Dollar\n2023-01-10,1.07\n").is_err());
+    }
+
+    #[test]
+    fn csv_without_rates_is_rejected() {
+        assert!(parse_ecb_rates("user", "Date,USD\n2023-01-10,N/A\n").is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use regex::Regex;
+
+use crate::error::http_error::HttpError;
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+use crate::prelude::*;
+use crate::shared::DbPool;
+
+/// A name entry of an external account that is ready to be matched with transaction names.
+#[derive(Debug)]
+pub struct NamePattern {
+    pub name_id: String,
+    pub external_account_id: String,
+    pub default_category_id: Option<String>,
+    pub name: String,
+    pub match_kind: NameMatchKind,
+
+    /// The compiled glob or regex, or the lowercase name for the other patterns.
+    matcher: Matcher,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Exact,
+    Lowercase(String),
+    Regex(Regex),
+}
+
+impl NamePattern {
+    /// Compiles the name entry. Fails when the name is empty for one of the patterns, as it would
+    /// match everything, or when it is an invalid regex.
+    pub fn new(
+        name_id: impl Into<String>,
+        external_account_id: impl Into<String>,
+        default_category_id: Option<String>,
+        name: impl Into<String>,
+        match_kind: NameMatchKind,
+    ) -> Result<Self> {
+        let name = name.into();
+
+        if match_kind != NameMatchKind::Exact && name.is_empty() {
+            return HttpError::new(400)
+                .message("The name of a pattern cannot be empty")
+                .into();
+        }
+
+        let matcher = match match_kind {
+            NameMatchKind::Exact => Matcher::Exact,
+            NameMatchKind::Prefix | NameMatchKind::Contains => {
+                Matcher::Lowercase(name.to_lowercase())
+            }
+            NameMatchKind::Glob => Matcher::Regex(
+                Regex::new(&glob_to_regex(&name)).expect("Escaped globs are valid regexes"),
+            ),
+            NameMatchKind::Regex => match Regex::new(&name) {
+                Ok(regex) => Matcher::Regex(regex),
+                Err(_) => {
+                    return HttpError::new(400)
+                        .message("The name is not a valid regex")
+                        .into()
+                }
+            },
+        };
+
+        Ok(Self {
+            name_id: name_id.into(),
+            external_account_id: external_account_id.into(),
+            default_category_id,
+            name,
+            match_kind,
+            matcher,
+        })
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        match (&self.matcher, self.match_kind) {
+            (Matcher::Exact, _) => self.name == name,
+            (Matcher::Lowercase(pattern), NameMatchKind::Prefix) => {
+                name.to_lowercase().starts_with(pattern)
+            }
+            (Matcher::Lowercase(pattern), _) => name.to_lowercase().contains(pattern),
+            (Matcher::Regex(regex), _) => regex.is_match(name),
+        }
+    }
+
+    /// Exact names win over patterns, and narrower kinds of patterns over wider ones. Within the
+    /// same kind the longest name is the most specific one.
+    fn precedence(&self) -> (u8, std::cmp::Reverse<usize>) {
+        let kind = match self.match_kind {
+            NameMatchKind::Exact => 0,
+            NameMatchKind::Prefix => 1,
+            NameMatchKind::Contains => 2,
+            NameMatchKind::Glob => 3,
+            NameMatchKind::Regex => 4,
+        };
+
+        (kind, std::cmp::Reverse(self.name.len()))
+    }
+}
+
+/// Converts a glob to an anchored, case-insensitive regex.
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("(?i)^");
+
+    for char in glob.chars() {
+        match char {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            char => pattern.push_str(&regex::escape(&char.to_string())),
+        }
+    }
+
+    pattern.push('$');
+    pattern
+}
+
+/// Holds all the name entries of the external accounts of a user, so the importer and the routes
+/// that apply names to existing transactions match names the same way.
+pub struct ExternalAccountMatcher {
+    patterns: Vec<NamePattern>,
+}
+
+impl ExternalAccountMatcher {
+    pub fn new(mut patterns: Vec<NamePattern>) -> Self {
+        patterns.sort_by(|a, b| {
+            a.precedence()
+                .cmp(&b.precedence())
+                .then_with(|| a.name_id.cmp(&b.name_id))
+        });
+
+        Self { patterns }
+    }
+
+    /// Loads the name entries of all external accounts of the user. Entries that cannot be
+    /// compiled anymore are skipped.
+    pub async fn load(pool: &DbPool, user_id: &str) -> Result<Self> {
+        trace!("Loading external account names for user '{}'", user_id);
+        let records = sqlx::query!(
+            r#"
+                SELECT n.id, n.name, n.match_kind, n.parent_external_account, e.default_category_id
+                FROM external_account_names n
+                INNER JOIN external_accounts e ON e.id = n.parent_external_account
+                WHERE n.user_id = $1;
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let patterns = records
+            .into_iter()
+            .filter_map(|record| {
+                let name_id = record.id.to_string();
+
+                let pattern = NamePattern::new(
+                    record.id,
+                    record.parent_external_account,
+                    record.default_category_id,
+                    record.name,
+                    NameMatchKind::from(&*record.match_kind),
+                );
+
+                if pattern.is_err() {
+                    warn!("Skipping external account name '{}' because it is invalid", name_id);
+                }
+
+                pattern.ok()
+            })
+            .collect();
+
+        Ok(Self::new(patterns))
+    }
+
+    /// Returns the name entry that matches the transaction name. When several entries match, the
+    /// one that takes precedence wins.
+    pub fn find(&self, name: &str) -> Option<&NamePattern> {
+        self.patterns.iter().find(|pattern| pattern.is_match(name))
+    }
+
+    /// Returns the names that would match both the new pattern and a pattern of another external
+    /// account. Whether two patterns overlap is checked against the given transaction names and
+    /// the literal names of the entries that are not globs or regexes.
+    pub fn conflicts(&self, pattern: &NamePattern, transaction_names: &[String]) -> Vec<String> {
+        let literals = self
+            .patterns
+            .iter()
+            .chain(std::iter::once(pattern))
+            .filter(|pattern| {
+                !matches!(pattern.match_kind, NameMatchKind::Glob | NameMatchKind::Regex)
+            })
+            .map(|pattern| &pattern.name);
+
+        let mut conflicts: Vec<String> = transaction_names
+            .iter()
+            .chain(literals)
+            .filter(|name| pattern.is_match(name))
+            .filter(|name| {
+                self.patterns.iter().any(|other| {
+                    other.external_account_id != pattern.external_account_id && other.is_match(name)
+                })
+            })
+            .cloned()
+            .collect();
+
+        conflicts.sort();
+        conflicts.dedup();
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+    use crate::services::external_account_matcher::{ExternalAccountMatcher, NamePattern};
+
+    fn pattern(id: &str, account: &str, name: &str, match_kind: NameMatchKind) -> NamePattern {
+        NamePattern::new(id, account, None, name, match_kind).unwrap()
+    }
+
+    #[test]
+    fn every_kind_is_matched() {
+        let exact = pattern("1", "a", "Jumbo", NameMatchKind::Exact);
+        assert!(exact.is_match("Jumbo"));
+        assert!(!exact.is_match("JUMBO"));
+
+        let prefix = pattern("2", "a", "AH ", NameMatchKind::Prefix);
+        assert!(prefix.is_match("ah to go 1234"));
+        assert!(!prefix.is_match("Bakery AH 12"));
+
+        let contains = pattern("3", "a", "albert heijn", NameMatchKind::Contains);
+        assert!(contains.is_match("BEA ALBERT HEIJN 1234"));
+
+        let glob = pattern("4", "a", "CCV*Parking ?", NameMatchKind::Glob);
+        assert!(glob.is_match("ccv 1234 parking A"));
+        assert!(!glob.is_match("ccv 1234 parking AB"));
+
+        let regex = pattern("5", "a", r"^Shell \d+$", NameMatchKind::Regex);
+        assert!(regex.is_match("Shell 0042"));
+        assert!(!regex.is_match("shell 0042"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        assert!(NamePattern::new("1", "a", None, "(", NameMatchKind::Regex).is_err());
+        assert!(NamePattern::new("1", "a", None, "", NameMatchKind::Prefix).is_err());
+        assert!(NamePattern::new("1", "a", None, "(", NameMatchKind::Glob).is_ok());
+    }
+
+    #[test]
+    fn narrowest_pattern_wins() {
+        let matcher = ExternalAccountMatcher::new(vec![
+            pattern("1", "a", "ah", NameMatchKind::Contains),
+            pattern("2", "b", "AH to go", NameMatchKind::Prefix),
+            pattern("3", "c", "AH", NameMatchKind::Prefix),
+            pattern("4", "d", "AH to go 1234", NameMatchKind::Exact),
+        ]);
+
+        assert_eq!(matcher.find("AH to go 1234").unwrap().name_id, "4");
+        assert_eq!(matcher.find("AH to go 5678").unwrap().name_id, "2");
+        assert_eq!(matcher.find("AH 5678").unwrap().name_id, "3");
+        assert_eq!(matcher.find("Bakery ah").unwrap().name_id, "1");
+        assert!(matcher.find("Jumbo").is_none());
+    }
+
+    #[test]
+    fn overlapping_patterns_of_other_accounts_conflict() {
+        let matcher = ExternalAccountMatcher::new(vec![
+            pattern("1", "a", "AH to go", NameMatchKind::Prefix),
+            pattern("2", "b", "Jumbo*", NameMatchKind::Glob),
+        ]);
+
+        let names = vec!["Jumbo 12".to_string(), "Lidl".to_string()];
+
+        // The literal name of the prefix is matched by the new pattern.
+        let new = pattern("3", "c", "ah", NameMatchKind::Contains);
+        assert_eq!(matcher.conflicts(&new, &names), vec!["AH to go"]);
+
+        // Overlapping globs are only found through the names of transactions.
+        let new = pattern("4", "c", "*12", NameMatchKind::Glob);
+        assert_eq!(matcher.conflicts(&new, &names), vec!["Jumbo 12"]);
+
+        // Patterns of the same account never conflict.
+        let new = pattern("5", "b", "Jumbo 1?", NameMatchKind::Glob);
+        assert!(matcher.conflicts(&new, &names).is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::models::entities::external_account::ExternalAccount;
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+use crate::models::entities::external_account_names::ExternalAccountName;
+use crate::prelude::*;
+use crate::services::external_account_matcher::ExternalAccountMatcher;
+use crate::shared::{DbPool, DbTransaction};
+
+/// The minimum similarity of two normalized names to end up in the same cluster.
+const SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// The minimum length of a normalized name before it absorbs the longer names it is a prefix of,
+/// so short words like "de" or "ah" do not collect unrelated names.
+const MIN_PREFIX_LENGTH: usize = 4;
+
+/// A group of similar transaction names that are not linked to an external account yet.
+#[derive(Debug)]
+pub struct NameCluster {
+    /// The normalized name the cluster started with, which identifies the cluster.
+    pub key: String,
+
+    /// The name that is used for the most transactions of the cluster.
+    pub suggested_name: String,
+    pub names: Vec<String>,
+    pub transaction_count: i64,
+}
+
+pub struct ExternalAccountSuggestionService;
+
+impl ExternalAccountSuggestionService {
+    /// Clusters the names of the transactions of the user that are not linked to an external
+    /// account. Names that are already matched by the names of an external account are left out,
+    /// as they only have to be applied.
+    pub async fn get_clusters(pool: &DbPool, user_id: &str) -> Result<Vec<NameCluster>> {
+        let records = sqlx::query!(
+            r#"
+                SELECT external_account_name, COUNT(*) AS "transaction_count!"
+                FROM transactions
+                WHERE user_id = $1
+                    AND transaction_type = 'transaction'
+                    AND external_account_id IS NULL
+                    AND external_account_name != ''
+                GROUP BY external_account_name;
+            "#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let matcher = ExternalAccountMatcher::load(pool, user_id).await?;
+
+        let names = records
+            .into_iter()
+            .filter(|record| matcher.find(&record.external_account_name).is_none())
+            .map(|record| (record.external_account_name, record.transaction_count))
+            .collect();
+
+        Ok(cluster_names(names))
+    }
+
+    /// Creates the external account with an exact name entry for every name in the cluster, and
+    /// links the unlinked transactions with those names to it.
+    pub async fn accept(
+        db_transaction: &mut DbTransaction<'_>,
+        external_account: &ExternalAccount,
+        cluster: &NameCluster,
+    ) -> Result<()> {
+        external_account.create(&mut **db_transaction).await?;
+
+        for name in &cluster.names {
+            let external_account_name = ExternalAccountName {
+                id: Uuid::new_v4().to_string(),
+                user_id: external_account.user_id.to_string(),
+                name: name.to_string(),
+                parent_external_account: external_account.id.to_string(),
+                match_kind: NameMatchKind::Exact,
+            };
+
+            external_account_name.create(&mut **db_transaction).await?;
+
+            sqlx::query!(
+                r#"
+                    UPDATE transactions
+                    SET external_account_id = $3, external_account_name_id = $4
+                    WHERE user_id = $1 AND external_account_name = $2 AND external_account_id IS NULL;
+                "#,
+                external_account.user_id,
+                name,
+                external_account.id,
+                external_account_name.id
+            )
+            .execute(&mut **db_transaction)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reduces a transaction name to the lowercase words it consists of. Digits and punctuation are
+/// dropped, as banks add things like terminal numbers, dates and references to the names.
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|char: char| !char.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The Sørensen–Dice coefficient of the character bigrams of both names.
+fn similarity(a: &str, b: &str) -> f64 {
+    fn bigrams(value: &str) -> Vec<(char, char)> {
+        let chars: Vec<char> = value.chars().filter(|char| !char.is_whitespace()).collect();
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+
+    let a = bigrams(a);
+    let mut b = bigrams(b);
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = (a.len() + b.len()) as f64;
+    let mut shared = 0;
+
+    for bigram in a {
+        if let Some(index) = b.iter().position(|other| *other == bigram) {
+            b.swap_remove(index);
+            shared += 1;
+        }
+    }
+
+    2.0 * shared as f64 / total
+}
+
+fn belongs_to(cluster_key: &str, key: &str) -> bool {
+    let is_word_prefix = key
+        .strip_prefix(cluster_key)
+        .is_some_and(|rest| rest.starts_with(' '));
+
+    (is_word_prefix && cluster_key.len() >= MIN_PREFIX_LENGTH)
+        || similarity(cluster_key, key) >= SIMILARITY_THRESHOLD
+}
+
+/// Clusters the names with their transaction counts. Names are first grouped on their normalized
+/// form, after which a group joins the first cluster whose key is a prefix of the group or that is
+/// similar enough. Shorter groups are handled first, so they become the keys of the clusters. The
+/// clusters with the most transactions come first.
+pub fn cluster_names(names: Vec<(String, i64)>) -> Vec<NameCluster> {
+    let mut groups: BTreeMap<String, Vec<(String, i64)>> = BTreeMap::new();
+
+    for (name, count) in names {
+        let key = normalize_name(&name);
+
+        if !key.is_empty() {
+            groups.entry(key).or_default().push((name, count));
+        }
+    }
+
+    let mut keys: Vec<&String> = groups.keys().collect();
+    keys.sort_by_key(|key| key.split(' ').count());
+
+    let mut clusters: Vec<(String, Vec<(String, i64)>)> = vec![];
+
+    for key in keys {
+        let group = groups[key].clone();
+
+        match clusters.iter_mut().find(|(cluster_key, _)| belongs_to(cluster_key, key)) {
+            Some((_, names)) => names.extend(group),
+            None => clusters.push((key.to_string(), group)),
+        }
+    }
+
+    let mut clusters: Vec<NameCluster> = clusters
+        .into_iter()
+        .map(|(key, mut names)| {
+            names.sort_by(|(a, a_count), (b, b_count)| {
+                b_count
+                    .cmp(a_count)
+                    .then_with(|| a.len().cmp(&b.len()))
+                    .then_with(|| a.cmp(b))
+            });
+
+            let mut seen = HashSet::new();
+            names.retain(|(name, _)| seen.insert(name.to_string()));
+
+            NameCluster {
+                key,
+                suggested_name: names[0].0.to_string(),
+                transaction_count: names.iter().map(|(_, count)| count).sum(),
+                names: names.into_iter().map(|(name, _)| name).collect(),
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.transaction_count
+            .cmp(&a.transaction_count)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::external_account_suggestion_service::{cluster_names, normalize_name};
+
+    fn names(names: &[(&str, i64)]) -> Vec<(String, i64)> {
+        names
+            .iter()
+            .map(|(name, count)| (name.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn names_are_normalized() {
+        assert_eq!(normalize_name("AH to go 1234 UTRECHT"), "ah to go utrecht");
+        assert_eq!(normalize_name("CCV*Parking-Garage 12/03"), "ccv parking garage");
+        assert_eq!(normalize_name("1234 5678"), "");
+    }
+
+    #[test]
+    fn similar_names_are_clustered() {
+        let clusters = cluster_names(names(&[
+            ("Albert Heijn 1234", 4),
+            ("ALBERT HEIJN 5678", 2),
+            ("Albert Heijn Utrecht", 1),
+            ("Albert Hein", 1),
+            ("Jumbo", 3),
+            ("1234", 10),
+        ]));
+
+        assert_eq!(clusters.len(), 2);
+
+        assert_eq!(clusters[0].key, "albert heijn");
+        assert_eq!(clusters[0].suggested_name, "Albert Heijn 1234");
+        assert_eq!(clusters[0].transaction_count, 8);
+        assert_eq!(
+            clusters[0].names,
+            vec!["Albert Heijn 1234", "ALBERT HEIJN 5678", "Albert Hein", "Albert Heijn Utrecht"]
+        );
+
+        assert_eq!(clusters[1].key, "jumbo");
+        assert_eq!(clusters[1].names, vec!["Jumbo"]);
+    }
+
+    #[test]
+    fn short_names_do_not_absorb_other_names() {
+        let clusters = cluster_names(names(&[("AH", 1), ("AH to go", 1), ("De Bakker", 1)]));
+
+        assert_eq!(clusters.len(), 3);
+    }
+}
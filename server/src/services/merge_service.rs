@@ -0,0 +1,280 @@
+use crate::error::http_error::HttpError;
+use crate::prelude::*;
+use crate::shared::DbTransaction;
+
+/// Merges duplicate external accounts, categories and subcategories. Everything that references
+/// the source is moved to the target, after which the source is deleted.
+///
+/// Some references are part of the key of the row they point to, like the parent category of a
+/// subcategory. Those are moved in a single statement, as the foreign keys are only checked at the
+/// end of a statement.
+pub struct MergeService;
+
+impl MergeService {
+    /// Moves the transactions, names, IBANs, rules, planned transactions and recurring series of
+    /// the source external account to the target. The target takes over the default category of
+    /// the source when it has none of its own.
+    pub async fn merge_external_accounts(
+        db_transaction: &mut DbTransaction<'_>,
+        user_id: &str,
+        source_id: &str,
+        target_id: &str,
+    ) -> Result<()> {
+        guard_different(source_id, target_id)?;
+
+        sqlx::query!(
+            r#"
+                UPDATE external_accounts target
+                SET default_category_id = source.default_category_id,
+                    default_subcategory_id = source.default_subcategory_id
+                FROM external_accounts source
+                WHERE target.user_id = $1 AND target.id = $3
+                    AND source.user_id = $1 AND source.id = $2
+                    AND target.default_category_id IS NULL;
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        // Transactions reference the name entry together with its external account.
+        sqlx::query!(
+            r#"
+                WITH names AS (
+                    UPDATE external_account_names
+                    SET parent_external_account = $3
+                    WHERE user_id = $1 AND parent_external_account = $2
+                )
+                UPDATE transactions
+                SET external_account_id = $3
+                WHERE user_id = $1 AND external_account_id = $2;
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                WITH ibans AS (
+                    UPDATE external_account_ibans
+                    SET parent_external_account = $3
+                    WHERE user_id = $1 AND parent_external_account = $2
+                ), rules AS (
+                    UPDATE rules
+                    SET set_external_account_id = $3
+                    WHERE user_id = $1 AND set_external_account_id = $2
+                )
+                UPDATE planned_transactions
+                SET external_account_id = $3
+                WHERE user_id = $1 AND external_account_id = $2;
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        // Recurring series are keyed on their external account. When the target already has a
+        // series, it is kept as it is, including whether the user confirmed or dismissed it.
+        sqlx::query!(
+            r#"
+                DELETE FROM recurring_series
+                WHERE user_id = $1 AND series_key = $2 AND EXISTS (
+                    SELECT 1 FROM recurring_series WHERE user_id = $1 AND series_key = $3
+                );
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                UPDATE recurring_series
+                SET series_key = $3, external_account_id = $3
+                WHERE user_id = $1 AND series_key = $2;
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM external_accounts
+                WHERE id = $2 AND user_id = $1;
+            "#,
+            user_id,
+            source_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Moves the subcategories, transactions, budgets, rules, planned transactions and default
+    /// categories of external accounts from the source category to the target. A budget for the
+    /// whole source category is dropped when the target already has one.
+    pub async fn merge_categories(
+        db_transaction: &mut DbTransaction<'_>,
+        user_id: &str,
+        source_id: &str,
+        target_id: &str,
+    ) -> Result<()> {
+        guard_different(source_id, target_id)?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM budgets
+                WHERE user_id = $1 AND category_id = $2 AND subcategory_id IS NULL AND EXISTS (
+                    SELECT 1 FROM budgets
+                    WHERE user_id = $1 AND category_id = $3 AND subcategory_id IS NULL
+                );
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                WITH subcategories AS (
+                    UPDATE subcategories
+                    SET parent_category = $3
+                    WHERE user_id = $1 AND parent_category = $2
+                ), transactions AS (
+                    UPDATE transactions
+                    SET category_id = $3
+                    WHERE user_id = $1 AND category_id = $2
+                ), external_accounts AS (
+                    UPDATE external_accounts
+                    SET default_category_id = $3
+                    WHERE user_id = $1 AND default_category_id = $2
+                ), rules AS (
+                    UPDATE rules
+                    SET set_category_id = $3
+                    WHERE user_id = $1 AND set_category_id = $2
+                ), planned_transactions AS (
+                    UPDATE planned_transactions
+                    SET category_id = $3
+                    WHERE user_id = $1 AND category_id = $2
+                )
+                UPDATE budgets
+                SET category_id = $3
+                WHERE user_id = $1 AND category_id = $2;
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM categories
+                WHERE id = $2 AND user_id = $1;
+            "#,
+            user_id,
+            source_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Moves everything that references the source subcategory to the target, which can belong to
+    /// another category. References to the source move to the category of the target as well. A
+    /// budget for the source is dropped when the target already has one.
+    pub async fn merge_subcategories(
+        db_transaction: &mut DbTransaction<'_>,
+        user_id: &str,
+        source_id: &str,
+        target_category_id: &str,
+        target_id: &str,
+    ) -> Result<()> {
+        guard_different(source_id, target_id)?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM budgets
+                WHERE user_id = $1 AND subcategory_id = $2 AND EXISTS (
+                    SELECT 1 FROM budgets WHERE user_id = $1 AND subcategory_id = $3
+                );
+            "#,
+            user_id,
+            source_id,
+            target_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                WITH transactions AS (
+                    UPDATE transactions
+                    SET category_id = $4, subcategory_id = $3
+                    WHERE user_id = $1 AND subcategory_id = $2
+                ), external_accounts AS (
+                    UPDATE external_accounts
+                    SET default_category_id = $4, default_subcategory_id = $3
+                    WHERE user_id = $1 AND default_subcategory_id = $2
+                ), rules AS (
+                    UPDATE rules
+                    SET set_category_id = $4, set_subcategory_id = $3
+                    WHERE user_id = $1 AND set_subcategory_id = $2
+                ), planned_transactions AS (
+                    UPDATE planned_transactions
+                    SET category_id = $4, subcategory_id = $3
+                    WHERE user_id = $1 AND subcategory_id = $2
+                )
+                UPDATE budgets
+                SET category_id = $4, subcategory_id = $3
+                WHERE user_id = $1 AND subcategory_id = $2;
+            "#,
+            user_id,
+            source_id,
+            target_id,
+            target_category_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM subcategories
+                WHERE id = $2 AND user_id = $1;
+            "#,
+            user_id,
+            source_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn guard_different(source_id: &str, target_id: &str) -> Result<()> {
+    if source_id == target_id {
+        return HttpError::new(400)
+            .message("Cannot merge something into itself")
+            .into();
+    }
+
+    Ok(())
+}
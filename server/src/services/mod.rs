@@ -1,4 +1,16 @@
+pub(crate) mod balance_service;
 pub(crate) mod blob_service;
+pub(crate) mod blob_storage;
+pub(crate) mod budget_service;
+pub(crate) mod correction_service;
+pub(crate) mod exchange_rate_service;
+pub(crate) mod external_account_matcher;
+pub(crate) mod external_account_suggestion_service;
+pub(crate) mod merge_service;
+pub(crate) mod planned_transaction_service;
 pub(crate) mod split_service;
 pub(crate) mod rate_limiter;
+pub(crate) mod recurring_service;
+pub(crate) mod rule_service;
+pub(crate) mod transfer_service;
 pub mod external_user_service;
@@ -0,0 +1,233 @@
+use chrono::{DateTime, Months, Utc};
+
+use crate::models::dto::bank_accounts::balance_dto::BalanceDto;
+use crate::models::dto::forecast::forecast_item_dto::ForecastItemDto;
+use crate::models::entities::planned_transaction::PlannedTransaction;
+use crate::models::entities::transaction::Transaction;
+
+/// How many days an imported transaction can be booked before or after the planned date to still
+/// be matched with it.
+const MATCH_DAYS: i64 = 7;
+
+/// Returns the index of the open planned transaction the imported transaction fulfils, if any.
+/// It has to be on the same bank account with the same external account, have an amount within
+/// 10% of the planned amount, and be booked within [MATCH_DAYS] of the planned date. When several
+/// planned transactions match, the one planned closest to the booking date wins.
+pub fn find_match(planned: &[PlannedTransaction], transaction: &Transaction) -> Option<usize> {
+    let external_account_id = transaction.external_account_id.as_ref()?;
+    let bank_account_id = transaction.bank_account_id.as_ref()?;
+
+    planned
+        .iter()
+        .enumerate()
+        .filter(|(_, planned)| {
+            !planned.completed
+                && planned.bank_account_id == *bank_account_id
+                && planned.external_account_id.as_ref() == Some(external_account_id)
+                && planned.amount.signum() == transaction.complete_amount.signum()
+                && (transaction.complete_amount - planned.amount).abs() <= planned.amount.abs() / 10
+                && (transaction.date - planned.date).num_days().abs() <= MATCH_DAYS
+        })
+        .min_by_key(|(_, planned)| (transaction.date - planned.date).num_seconds().abs())
+        .map(|(index, _)| index)
+}
+
+/// The projected balance of a single bank account.
+#[derive(Debug)]
+pub struct Projection {
+    pub items: Vec<ForecastItemDto>,
+
+    /// The balance at the end of every month from now on.
+    pub balances: Vec<BalanceDto>,
+    pub end_balance: i64,
+    pub lowest_balance: i64,
+}
+
+/// Projects the balance forward from `now` by the given number of months by adding the planned
+/// occurrences in order. The next occurrence of a planned transaction is still expected when it's
+/// overdue, so it is counted as happening right now, but the overdue ones after it are left out
+/// like in the upcoming transactions of recurring series.
+pub fn project(
+    balance: i64,
+    planned: &[PlannedTransaction],
+    now: DateTime<Utc>,
+    months: u32,
+) -> Projection {
+    let until = now + Months::new(months);
+
+    let mut occurrences: Vec<(DateTime<Utc>, &PlannedTransaction)> = planned
+        .iter()
+        .flat_map(|planned| {
+            planned
+                .occurrences(until)
+                .into_iter()
+                .filter(|date| *date >= now || *date == planned.date)
+                .map(move |date| (date.max(now), planned))
+        })
+        .collect();
+
+    occurrences.sort_by_key(|(date, _)| *date);
+
+    let mut items = vec![];
+    let mut balances = vec![];
+    let mut running = balance;
+    let mut lowest_balance = balance;
+    let mut month_end = now + Months::new(1);
+    let mut occurrences = occurrences.into_iter().peekable();
+
+    while month_end <= until {
+        while let Some((date, planned)) = occurrences.next_if(|(date, _)| *date <= month_end) {
+            running += planned.amount;
+            lowest_balance = lowest_balance.min(running);
+
+            items.push(ForecastItemDto {
+                planned_transaction_id: planned.id.to_string(),
+                description: planned.description.to_string(),
+                date: date.to_rfc3339(),
+                amount: planned.amount,
+                balance: running,
+            });
+        }
+
+        balances.push(BalanceDto {
+            date: month_end.to_rfc3339(),
+            balance: running,
+        });
+
+        month_end = now + Months::new(balances.len() as u32 + 1);
+    }
+
+    Projection {
+        items,
+        balances,
+        end_balance: running,
+        lowest_balance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use crate::models::entities::planned_transaction::PlannedTransaction;
+    use crate::models::entities::recurring_series::recurrence::Recurrence;
+    use crate::models::entities::transaction::transaction_type::TransactionType;
+    use crate::models::entities::transaction::Transaction;
+    use crate::services::planned_transaction_service::{find_match, project};
+
+    fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    fn planned(
+        id: &str,
+        amount: i64,
+        date: DateTime<Utc>,
+        recurrence: Option<Recurrence>,
+    ) -> PlannedTransaction {
+        PlannedTransaction {
+            id: id.to_string(),
+            user_id: "user".to_string(),
+            bank_account_id: "bank-account".to_string(),
+            category_id: None,
+            subcategory_id: None,
+            external_account_id: Some("landlord".to_string()),
+            description: id.to_string(),
+            amount,
+            date,
+            recurrence,
+            ends_at: None,
+            completed: false,
+            linked_transaction_ids: vec![],
+        }
+    }
+
+    fn transaction(amount: i64, date: DateTime<Utc>) -> Transaction {
+        Transaction {
+            id: "transaction".to_string(),
+            user_id: "user".to_string(),
+            transaction_type: TransactionType::Transaction,
+            follow_number: "1".to_string(),
+            original_description: "Rent".to_string(),
+            description: "Rent".to_string(),
+            complete_amount: amount,
+            amount,
+            date,
+            bank_account_id: Some("bank-account".to_string()),
+            category_id: None,
+            parent_transaction_id: None,
+            external_account_name: "Landlord".to_string(),
+            external_account_id: Some("landlord".to_string()),
+            external_account_name_id: None,
+            external_account_iban: None,
+            parent_import_id: None,
+            subcategory_id: None,
+            order_indicator: 0,
+            related_move_transaction: None,
+            related_transfer_transaction: None,
+            currency: "EUR".to_string(),
+        }
+    }
+
+    #[test]
+    fn similar_transaction_matches() {
+        let planned = vec![
+            planned("rent", -95000, date(2023, 3, 1), Some(Recurrence::Month)),
+            planned("deposit", -95000, date(2023, 3, 6), None),
+        ];
+
+        assert_eq!(find_match(&planned, &transaction(-97500, date(2023, 3, 2))), Some(0));
+        assert_eq!(find_match(&planned, &transaction(-95000, date(2023, 3, 5))), Some(1));
+    }
+
+    #[test]
+    fn different_transaction_does_not_match() {
+        let planned = vec![planned("rent", -95000, date(2023, 3, 1), None)];
+
+        // Too far off in amount, in date, or the money goes the other way.
+        assert_eq!(find_match(&planned, &transaction(-110000, date(2023, 3, 1))), None);
+        assert_eq!(find_match(&planned, &transaction(-95000, date(2023, 3, 12))), None);
+        assert_eq!(find_match(&planned, &transaction(95000, date(2023, 3, 1))), None);
+
+        let mut other_account = transaction(-95000, date(2023, 3, 1));
+        other_account.external_account_id = None;
+
+        assert_eq!(find_match(&planned, &other_account), None);
+    }
+
+    #[test]
+    fn balance_is_projected_per_month() {
+        let planned = vec![
+            planned("salary", 300000, date(2023, 3, 25), Some(Recurrence::Month)),
+            planned("rent", -95000, date(2023, 3, 1), Some(Recurrence::Month)),
+            planned("car", -500000, date(2023, 4, 10), None),
+        ];
+
+        let projection = project(10000, &planned, date(2023, 3, 15), 2);
+
+        // The overdue rent of March is expected right away.
+        let items: Vec<(&str, &str, i64)> = projection
+            .items
+            .iter()
+            .map(|item| (&*item.description, &*item.date, item.balance))
+            .collect();
+
+        assert_eq!(
+            items,
+            vec![
+                ("rent", "2023-03-15T00:00:00+00:00", -85000),
+                ("salary", "2023-03-25T00:00:00+00:00", 215000),
+                ("rent", "2023-04-01T00:00:00+00:00", 120000),
+                ("car", "2023-04-10T00:00:00+00:00", -380000),
+                ("salary", "2023-04-25T00:00:00+00:00", -80000),
+                ("rent", "2023-05-01T00:00:00+00:00", -175000),
+            ]
+        );
+
+        let balances: Vec<i64> = projection.balances.iter().map(|b| b.balance).collect();
+
+        assert_eq!(balances, vec![-380000, -175000]);
+        assert_eq!(projection.end_balance, -175000);
+        assert_eq!(projection.lowest_balance, -380000);
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+use crate::models::entities::recurring_series::{RecurringSeries, RecurringStatus};
+use crate::prelude::*;
+use crate::shared::DbPool;
+use crate::utils::datetime::to_utc;
+
+/// Only the transactions of this many days back are scanned, so series that ended long ago don't
+/// have to be looked at every time.
+const HISTORY_DAYS: i64 = 2 * 366;
+
+/// A transaction that is part of a possible series.
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    pub date: DateTime<Utc>,
+    pub amount: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DetectedSeries {
+    pub recurrence: Recurrence,
+
+    /// The amount of the latest transaction, which is the best guess for the next one as prices
+    /// tend to change over time.
+    pub amount: i64,
+    pub occurrences: usize,
+    pub last_date: DateTime<Utc>,
+    pub next_date: DateTime<Utc>,
+}
+
+pub struct RecurringService;
+
+impl RecurringService {
+    /// Detects the recurring series in the transactions of the user and stores them. Series that
+    /// are not detected anymore are removed, unless the user confirmed or dismissed them.
+    pub async fn recompute(pool: &DbPool, user_id: &str, now: DateTime<Utc>) -> Result<()> {
+        trace!("Querying transactions for recurring series of user '{}'", user_id);
+        let records = sqlx::query!(
+            r#"
+                SELECT external_account_id, external_account_name, complete_amount, currency, date
+                FROM transactions
+                WHERE user_id = $1 AND transaction_type = 'transaction' AND date >= $2
+                ORDER BY date, order_indicator;
+            "#,
+            user_id,
+            OffsetDateTime::from_unix_timestamp(
+                (now - Duration::days(HISTORY_DAYS)).timestamp()
+            )?,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut groups = BTreeMap::new();
+
+        for record in records {
+            let key = match &record.external_account_id {
+                Some(external_account_id) => external_account_id.to_string(),
+                None => record.external_account_name.trim().to_lowercase(),
+            };
+
+            if key.is_empty() {
+                continue;
+            }
+
+            groups.entry(key).or_insert_with(Vec::new).push(record);
+        }
+
+        let mut series = vec![];
+
+        for (key, records) in groups {
+            let Some(last) = records.last() else {
+                continue;
+            };
+
+            // A series is always in a single currency, the one it is currently paid in.
+            let occurrences: Vec<Occurrence> = records
+                .iter()
+                .filter(|record| record.currency == last.currency)
+                .map(|record| Occurrence {
+                    date: to_utc(record.date),
+                    amount: record.complete_amount,
+                })
+                .collect();
+
+            let Some(detected) = detect_series(&occurrences, now) else {
+                continue;
+            };
+
+            series.push(RecurringSeries {
+                id: Uuid::new_v4().to_string(),
+                user_id: user_id.to_string(),
+                series_key: key,
+                external_account_id: last.external_account_id.clone(),
+                external_account_name: last.external_account_name.to_string(),
+                recurrence: detected.recurrence,
+                amount: detected.amount,
+                currency: last.currency.to_string(),
+                occurrences: detected.occurrences as i32,
+                last_date: detected.last_date,
+                next_date: detected.next_date,
+                status: RecurringStatus::Detected,
+            });
+        }
+
+        debug!("Detected {} recurring series for user '{}'", series.len(), user_id);
+        let mut db_transaction = pool.begin().await?;
+
+        for series in &series {
+            series.upsert(&mut *db_transaction).await?;
+        }
+
+        let keys: Vec<String> = series.into_iter().map(|series| series.series_key).collect();
+        RecurringSeries::delete_detected_except(&mut *db_transaction, user_id, &keys).await?;
+
+        db_transaction.commit().await?;
+
+        Ok(())
+    }
+
+    /// Recomputes the recurring series of every user. A failure for one user doesn't stop the
+    /// others from being recomputed.
+    pub async fn recompute_all(pool: &DbPool, now: DateTime<Utc>) -> Result<()> {
+        let user_ids = sqlx::query!("SELECT id FROM users;")
+            .fetch_all(pool)
+            .await?;
+
+        for record in user_ids {
+            if let Err(error) = RecurringService::recompute(pool, &record.id, now).await {
+                warn!("Failed to detect recurring series for user '{}': {:?}", record.id, error);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Looks for a series in the transactions of a single external account, which are expected to be
+/// ordered by date. Transactions with an amount that is far off from the others, like a one-off
+/// purchase at the same store, are ignored. The rest has to happen at a regular interval.
+///
+/// Returns [None] when there is no series, or when the series seems to have ended because the
+/// last two expected transactions didn't happen.
+pub fn detect_series(occurrences: &[Occurrence], now: DateTime<Utc>) -> Option<DetectedSeries> {
+    let amounts = occurrences.iter().map(|occurrence| occurrence.amount).collect();
+    let typical_amount = median(amounts)?;
+
+    let similar: Vec<&Occurrence> = occurrences
+        .iter()
+        .filter(|occurrence| {
+            (occurrence.amount - typical_amount).abs() <= typical_amount.abs() / 5
+        })
+        .collect();
+
+    let intervals: Vec<i64> = similar
+        .windows(2)
+        .map(|pair| (pair[1].date - pair[0].date).num_days())
+        .collect();
+
+    let recurrence = Recurrence::from_interval(median(intervals.clone())?)?;
+
+    // Allow for an occasional skipped or extra transaction, as long as most of them are regular.
+    let regular = intervals
+        .iter()
+        .filter(|interval| recurrence.interval_days().contains(*interval))
+        .count();
+
+    let minimum = match recurrence {
+        Recurrence::Year => 2,
+        _ => 3,
+    };
+
+    if similar.len() < minimum || regular * 4 < intervals.len() * 3 {
+        return None;
+    }
+
+    let last = similar.last()?;
+    let next_date = recurrence.next(last.date);
+
+    if recurrence.next(next_date) < now {
+        return None;
+    }
+
+    Some(DetectedSeries {
+        recurrence,
+        amount: last.amount,
+        occurrences: similar.len(),
+        last_date: last.date,
+        next_date,
+    })
+}
+
+fn median(mut values: Vec<i64>) -> Option<i64> {
+    values.sort();
+    values.get(values.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use crate::models::entities::recurring_series::recurrence::Recurrence;
+    use crate::services::recurring_service::{detect_series, Occurrence};
+
+    fn occurrence(year: i32, month: u32, day: u32, amount: i64) -> Occurrence {
+        Occurrence {
+            date: date(year, month, day),
+            amount,
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn monthly_subscription_is_detected() {
+        let occurrences = vec![
+            occurrence(2023, 1, 3, -1299),
+            occurrence(2023, 2, 3, -1299),
+            occurrence(2023, 3, 6, -1299),
+            occurrence(2023, 4, 3, -1499),
+        ];
+
+        let series = detect_series(&occurrences, date(2023, 4, 20)).unwrap();
+
+        assert_eq!(series.recurrence, Recurrence::Month);
+        assert_eq!(series.amount, -1499);
+        assert_eq!(series.occurrences, 4);
+        assert_eq!(series.next_date, date(2023, 5, 3));
+    }
+
+    #[test]
+    fn outliers_are_ignored() {
+        let occurrences = vec![
+            occurrence(2023, 1, 25, 300000),
+            occurrence(2023, 2, 10, 2500),
+            occurrence(2023, 2, 24, 300000),
+            occurrence(2023, 3, 24, 310000),
+        ];
+
+        let series = detect_series(&occurrences, date(2023, 4, 1)).unwrap();
+
+        assert_eq!(series.recurrence, Recurrence::Month);
+        assert_eq!(series.occurrences, 3);
+        assert_eq!(series.last_date, date(2023, 3, 24));
+    }
+
+    #[test]
+    fn irregular_transactions_are_not_a_series() {
+        let occurrences = vec![
+            occurrence(2023, 1, 3, -2000),
+            occurrence(2023, 1, 20, -2100),
+            occurrence(2023, 3, 1, -1900),
+            occurrence(2023, 3, 4, -2000),
+        ];
+
+        assert_eq!(detect_series(&occurrences, date(2023, 3, 10)), None);
+    }
+
+    #[test]
+    fn too_few_transactions_are_not_a_series() {
+        let occurrences = vec![occurrence(2023, 1, 3, -1299), occurrence(2023, 2, 3, -1299)];
+
+        assert_eq!(detect_series(&occurrences, date(2023, 2, 10)), None);
+        assert_eq!(detect_series(&[], date(2023, 2, 10)), None);
+    }
+
+    #[test]
+    fn ended_series_is_not_detected() {
+        let occurrences = vec![
+            occurrence(2023, 1, 3, -1299),
+            occurrence(2023, 2, 3, -1299),
+            occurrence(2023, 3, 3, -1299),
+        ];
+
+        assert!(detect_series(&occurrences, date(2023, 4, 10)).is_some());
+        assert_eq!(detect_series(&occurrences, date(2023, 6, 10)), None);
+    }
+}
@@ -0,0 +1,334 @@
+use chrono::{DateTime, Datelike, Utc};
+use regex::Regex;
+use sqlx::{Executor, Postgres};
+
+use crate::db_executor;
+use crate::models::entities::rule::Rule;
+use crate::prelude::*;
+use crate::utils::iban::normalize_iban;
+
+/// The information of a transaction that rules are able to match against.
+pub struct RuleSubject<'a> {
+    pub description: &'a str,
+    pub amount: i64,
+
+    /// The IBAN of the other party of the transaction, when it is known.
+    pub external_account_iban: Option<&'a str>,
+    pub bank_account_id: &'a str,
+    pub date: DateTime<Utc>,
+}
+
+/// The combined result of all the rules that matched a transaction. Fields that are [None] were not
+/// set by any of the rules and should be left alone.
+#[derive(Debug, Default, PartialEq)]
+pub struct RuleOutcome {
+    /// The category and optional subcategory, these are always set together.
+    pub category: Option<(String, Option<String>)>,
+    pub description: Option<String>,
+    pub external_account_id: Option<String>,
+}
+
+struct CompiledRule {
+    rule: Rule,
+    regex: Option<Regex>,
+}
+
+/// Holds all the rules of a user so they can be evaluated against many transactions without having
+/// to query or compile them again for every transaction.
+pub struct RuleService {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleService {
+    /// Creates a new service with the given rules. The rules are expected to already be sorted in
+    /// the order they should be evaluated in.
+    pub fn new(rules: Vec<Rule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter_map(|rule| {
+                let regex = match &rule.description_regex {
+                    Some(pattern) => match Regex::new(pattern) {
+                        Ok(regex) => Some(regex),
+                        Err(error) => {
+                            warn!("Skipping rule '{}' because of an invalid regex: {}", rule.id, error);
+                            return None;
+                        }
+                    },
+                    None => None,
+                };
+
+                Some(CompiledRule { rule, regex })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Loads all the rules of the given user in the order they should be evaluated in.
+    pub async fn load<'d>(executor: db_executor!('d), user_id: &str) -> Result<Self> {
+        trace!("Loading rules for user '{}'", user_id);
+        let rules = sqlx::query_as!(
+            Rule,
+            r#"
+                SELECT *
+                FROM rules
+                WHERE user_id = $1
+                ORDER BY priority, name;
+            "#,
+            user_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(Self::new(rules))
+    }
+
+    /// Runs all the rules against the given subject. When multiple rules perform the same action,
+    /// the first rule that matches wins.
+    pub fn evaluate(&self, subject: &RuleSubject) -> RuleOutcome {
+        let mut outcome = RuleOutcome::default();
+
+        for compiled in self.rules.iter().filter(|compiled| compiled.matches(subject)) {
+            let rule = &compiled.rule;
+
+            if outcome.category.is_none() {
+                if let Some(category_id) = &rule.set_category_id {
+                    outcome.category =
+                        Some((category_id.to_string(), rule.set_subcategory_id.clone()));
+                }
+            }
+
+            if outcome.description.is_none() {
+                outcome.description = rule.set_description.clone();
+            }
+
+            if outcome.external_account_id.is_none() {
+                outcome.external_account_id = rule.set_external_account_id.clone();
+            }
+        }
+
+        outcome
+    }
+}
+
+impl CompiledRule {
+    fn matches(&self, subject: &RuleSubject) -> bool {
+        let rule = &self.rule;
+
+        if let Some(contains) = &rule.description_contains {
+            if !subject
+                .description
+                .to_lowercase()
+                .contains(&contains.to_lowercase())
+            {
+                return false;
+            }
+        }
+
+        if let Some(regex) = &self.regex {
+            if !regex.is_match(subject.description) {
+                return false;
+            }
+        }
+
+        if let Some(min_amount) = rule.min_amount {
+            if subject.amount < min_amount {
+                return false;
+            }
+        }
+
+        if let Some(max_amount) = rule.max_amount {
+            if subject.amount > max_amount {
+                return false;
+            }
+        }
+
+        if let Some(iban) = &rule.iban {
+            let matches = subject
+                .external_account_iban
+                .is_some_and(|external_iban| normalize_iban(iban) == normalize_iban(external_iban));
+
+            if !matches {
+                return false;
+            }
+        }
+
+        if let Some(bank_account_id) = &rule.bank_account_id {
+            if bank_account_id != subject.bank_account_id {
+                return false;
+            }
+        }
+
+        if let Some(weekday) = rule.weekday {
+            if subject.date.weekday().num_days_from_monday() as i32 != weekday {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::models::entities::rule::Rule;
+    use crate::services::rule_service::{RuleOutcome, RuleService, RuleSubject};
+
+    fn rule(id: &str) -> Rule {
+        Rule {
+            id: id.to_string(),
+            user_id: "abc".to_string(),
+            name: id.to_string(),
+            priority: 0,
+            description_contains: None,
+            description_regex: None,
+            min_amount: None,
+            max_amount: None,
+            iban: None,
+            bank_account_id: None,
+            weekday: None,
+            set_category_id: None,
+            set_subcategory_id: None,
+            set_description: None,
+            set_external_account_id: None,
+        }
+    }
+
+    fn subject(description: &str, amount: i64) -> RuleSubject<'_> {
+        RuleSubject {
+            description,
+            amount,
+            external_account_iban: Some("NL99 INGB 0001234567"),
+            bank_account_id: "bank-account-1",
+
+            // This is a Saturday
+            date: Utc.with_ymd_and_hms(2023, 2, 11, 11, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn rule_without_conditions_matches_everything() {
+        let mut rule = rule("rule-1");
+        rule.set_description = Some("Everything".to_string());
+
+        let service = RuleService::new(vec![rule]);
+        let outcome = service.evaluate(&subject("Payment for Jumbo", -9300));
+
+        assert_eq!(outcome.description, Some("Everything".to_string()));
+    }
+
+    #[test]
+    fn description_contains_is_case_insensitive() {
+        let mut rule = rule("rule-1");
+        rule.description_contains = Some("jumbo".to_string());
+        rule.set_category_id = Some("category-1".to_string());
+
+        let service = RuleService::new(vec![rule]);
+
+        assert_eq!(
+            service.evaluate(&subject("Payment for JUMBO", -9300)).category,
+            Some(("category-1".to_string(), None))
+        );
+        assert_eq!(service.evaluate(&subject("Payment for Lidl", -9300)), RuleOutcome::default());
+    }
+
+    #[test]
+    fn description_regex_is_used() {
+        let mut rule = rule("rule-1");
+        rule.description_regex = Some(r"^Payment \d+$".to_string());
+        rule.set_description = Some("Payment".to_string());
+
+        let service = RuleService::new(vec![rule]);
+
+        assert!(service.evaluate(&subject("Payment 123", -100)).description.is_some());
+        assert!(service.evaluate(&subject("Payment abc", -100)).description.is_none());
+    }
+
+    #[test]
+    fn rules_with_invalid_regex_are_skipped() {
+        let mut rule = rule("rule-1");
+        rule.description_regex = Some("(".to_string());
+        rule.set_description = Some("Invalid".to_string());
+
+        let service = RuleService::new(vec![rule]);
+
+        assert_eq!(service.evaluate(&subject("(", -100)), RuleOutcome::default());
+    }
+
+    #[test]
+    fn amount_range_is_inclusive() {
+        let mut rule = rule("rule-1");
+        rule.min_amount = Some(-10000);
+        rule.max_amount = Some(-5000);
+        rule.set_description = Some("In range".to_string());
+
+        let service = RuleService::new(vec![rule]);
+
+        assert!(service.evaluate(&subject("", -10000)).description.is_some());
+        assert!(service.evaluate(&subject("", -5000)).description.is_some());
+        assert!(service.evaluate(&subject("", -10001)).description.is_none());
+        assert!(service.evaluate(&subject("", -4999)).description.is_none());
+    }
+
+    #[test]
+    fn iban_is_compared_without_spaces_and_case() {
+        let mut rule = rule("rule-1");
+        rule.iban = Some("nl99ingb0001234567".to_string());
+        rule.set_description = Some("Matched".to_string());
+
+        let service = RuleService::new(vec![rule]);
+
+        assert!(service.evaluate(&subject("", -100)).description.is_some());
+    }
+
+    #[test]
+    fn iban_does_not_match_transactions_without_other_party_iban() {
+        let mut rule = rule("rule-1");
+        rule.iban = Some("NL99INGB0001234567".to_string());
+        rule.set_description = Some("Matched".to_string());
+
+        let service = RuleService::new(vec![rule]);
+
+        let mut subject = subject("", -100);
+        subject.external_account_iban = None;
+
+        assert!(service.evaluate(&subject).description.is_none());
+    }
+
+    #[test]
+    fn bank_account_and_weekday_must_match() {
+        let mut saturday = rule("rule-1");
+        saturday.bank_account_id = Some("bank-account-1".to_string());
+        saturday.weekday = Some(5);
+        saturday.set_description = Some("Saturday".to_string());
+
+        let mut monday = rule("rule-2");
+        monday.weekday = Some(0);
+        monday.set_external_account_id = Some("external-account-1".to_string());
+
+        let service = RuleService::new(vec![saturday, monday]);
+        let outcome = service.evaluate(&subject("", -100));
+
+        assert_eq!(outcome.description, Some("Saturday".to_string()));
+        assert_eq!(outcome.external_account_id, None);
+    }
+
+    #[test]
+    fn first_matching_rule_wins_per_action() {
+        let mut first = rule("rule-1");
+        first.set_category_id = Some("category-1".to_string());
+
+        let mut second = rule("rule-2");
+        second.set_category_id = Some("category-2".to_string());
+        second.set_subcategory_id = Some("subcategory-2".to_string());
+        second.set_description = Some("Second".to_string());
+
+        let service = RuleService::new(vec![first, second]);
+        let outcome = service.evaluate(&subject("", -100));
+
+        assert_eq!(outcome.category, Some(("category-1".to_string(), None)));
+        assert_eq!(outcome.description, Some("Second".to_string()));
+    }
+}
@@ -23,7 +23,7 @@ impl SplitService {
         trace!("Fetching parent transaction from database");
         let parent_transaction = sqlx::query!(
             r#"
-                SELECT id, bank_account_id, amount, external_account_name, external_account_id
+                SELECT id, bank_account_id, amount, external_account_name, external_account_id, external_account_iban, currency
                 FROM transactions
                 WHERE id = $1 AND user_id = $2;
             "#,
@@ -58,9 +58,12 @@ impl SplitService {
             external_account_name: parent_transaction.external_account_name,
             external_account_id: parent_transaction.external_account_id,
             external_account_name_id: None,
+            external_account_iban: parent_transaction.external_account_iban,
             parent_import_id: None,
             order_indicator: 0,
             related_move_transaction: None,
+            related_transfer_transaction: None,
+            currency: parent_transaction.currency,
         };
 
         debug!("Creating new split with id '{}'", split_transaction.id);
@@ -94,7 +97,7 @@ impl SplitService {
     ) -> Result<()> {
         let parent_transaction = sqlx::query!(
             r#"
-                SELECT id, bank_account_id, amount, external_account_name, external_account_id
+                SELECT id, bank_account_id, amount, external_account_name, external_account_id, currency
                 FROM transactions
                 WHERE id = $1 AND user_id = $2;
             "#,
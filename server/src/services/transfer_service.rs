@@ -0,0 +1,167 @@
+use sqlx::types::time::OffsetDateTime;
+
+use crate::error::http_error::HttpError;
+use crate::models::entities::transaction::Transaction;
+use crate::prelude::*;
+use crate::shared::DbTransaction;
+
+/// How many days the two legs of a transfer can be booked apart to still be linked on import.
+const TRANSFER_DAYS: i64 = 3;
+
+pub struct TransferService;
+
+impl TransferService {
+    /// Finds the other leg of a transfer for a transaction that was just created. The other leg is
+    /// an unlinked transaction on the given bank account with the opposite amount in the same
+    /// currency, booked within [TRANSFER_DAYS] of the transaction. When several transactions
+    /// qualify, the one booked closest to the transaction wins.
+    pub async fn find_counterpart(
+        db_transaction: &mut DbTransaction<'_>,
+        transaction: &Transaction,
+        bank_account_id: &str,
+    ) -> Result<Option<String>> {
+        let date = OffsetDateTime::from_unix_timestamp(transaction.date.timestamp())?;
+
+        let record = sqlx::query!(
+            r#"
+                SELECT id
+                FROM transactions
+                WHERE user_id = $1
+                    AND id != $2
+                    AND bank_account_id = $3
+                    AND transaction_type = 'transaction'
+                    AND related_transfer_transaction IS NULL
+                    AND complete_amount = -$4::bigint
+                    AND currency = $5
+                    AND date BETWEEN $6::timestamptz - make_interval(days => $7) AND $6::timestamptz + make_interval(days => $7)
+                ORDER BY abs(EXTRACT(EPOCH FROM (date - $6::timestamptz))), order_indicator
+                LIMIT 1;
+            "#,
+            transaction.user_id,
+            transaction.id,
+            bank_account_id,
+            transaction.complete_amount,
+            transaction.currency,
+            date,
+            TRANSFER_DAYS as i32,
+        )
+        .fetch_optional(&mut **db_transaction)
+        .await?;
+
+        Ok(record.map(|record| record.id))
+    }
+
+    /// Links two transactions as the legs of a single transfer. Both have to be unlinked
+    /// transactions on different bank accounts of the user, one outgoing and one incoming.
+    pub async fn link(
+        db_transaction: &mut DbTransaction<'_>,
+        user_id: &str,
+        transaction_id: &str,
+        other_id: &str,
+    ) -> Result<()> {
+        if transaction_id == other_id {
+            return HttpError::new(400)
+                .message("A transaction cannot be a transfer to itself")
+                .into();
+        }
+
+        let records = sqlx::query!(
+            r#"
+                SELECT id, transaction_type, bank_account_id, complete_amount, related_transfer_transaction
+                FROM transactions
+                WHERE user_id = $1 AND id IN ($2, $3)
+                FOR UPDATE;
+            "#,
+            user_id,
+            transaction_id,
+            other_id
+        )
+        .fetch_all(&mut **db_transaction)
+        .await?;
+
+        let [first, second] = records.as_slice() else {
+            return HttpError::new(404)
+                .message("The transaction could not be found")
+                .into();
+        };
+
+        if first.transaction_type != "transaction" || second.transaction_type != "transaction" {
+            return HttpError::new(400)
+                .message("Only transactions can be linked as a transfer")
+                .into();
+        }
+
+        if first.bank_account_id.is_none() || first.bank_account_id == second.bank_account_id {
+            return HttpError::new(400)
+                .message("A transfer has to be between two different bank accounts")
+                .into();
+        }
+
+        if first.complete_amount.signum() * second.complete_amount.signum() != -1 {
+            return HttpError::new(400)
+                .message("A transfer consists of an outgoing and an incoming transaction")
+                .into();
+        }
+
+        if first.related_transfer_transaction.is_some()
+            || second.related_transfer_transaction.is_some()
+        {
+            return HttpError::new(409)
+                .message("The transaction is already part of a transfer")
+                .into();
+        }
+
+        Self::set_link(db_transaction, user_id, transaction_id, other_id).await
+    }
+
+    /// Links two transactions without any checks, for when they are known to belong together.
+    pub async fn set_link(
+        db_transaction: &mut DbTransaction<'_>,
+        user_id: &str,
+        transaction_id: &str,
+        other_id: &str,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE transactions
+                SET related_transfer_transaction = CASE WHEN id = $2 THEN $3 ELSE $2 END
+                WHERE user_id = $1 AND id IN ($2, $3);
+            "#,
+            user_id,
+            transaction_id,
+            other_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes the link between both legs of the transfer the transaction is part of.
+    pub async fn unlink(
+        db_transaction: &mut DbTransaction<'_>,
+        user_id: &str,
+        transaction_id: &str,
+    ) -> Result<()> {
+        let result = sqlx::query!(
+            r#"
+                UPDATE transactions
+                SET related_transfer_transaction = NULL
+                WHERE user_id = $1 AND (id = $2 OR related_transfer_transaction = $2)
+                    AND related_transfer_transaction IS NOT NULL;
+            "#,
+            user_id,
+            transaction_id
+        )
+        .execute(&mut **db_transaction)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return HttpError::new(404)
+                .message("The transaction is not part of a transfer")
+                .into();
+        }
+
+        Ok(())
+    }
+}
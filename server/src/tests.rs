@@ -7,4 +7,22 @@ mod transactions;
 mod regressions;
 mod external_accounts;
 mod categories_move;
+mod rules;
+mod budgets;
+mod aggregates;
 
+mod bank_accounts;
+mod reconciliations;
+mod statement_import;
+mod csv_mapping_profiles;
+mod csv_import;
+mod currencies;
+mod recurring;
+mod planned_transactions;
+mod tags;
+mod attachments;
+mod blobs;
+mod blob_storage;
+mod transfers;
+mod external_account_suggestions;
+mod merging;
@@ -0,0 +1,119 @@
+use sqlx::PgPool;
+use crate::models::dto::aggregates::series_query_dto::{SeriesGroupBy, SeriesInterval, SeriesQueryDto};
+use crate::routes::aggregates::get_series;
+use crate::tests::common::TestApp;
+
+#[sqlx::test(fixtures("users", "aggregates"))]
+async fn series_is_returned_per_month(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = get_series(
+        app.pool_state(),
+        app.alice(),
+        SeriesQueryDto {
+            from: None,
+            to: None,
+            interval: SeriesInterval::Month,
+            group_by: None,
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(series.len(), 2);
+
+    // The split and the remainder of the parent transaction together count as the full amount.
+    let january = series.get(0).unwrap();
+    assert_eq!(january.bucket, "2023-01-01T00:00:00Z");
+    assert_eq!(january.group_id, None);
+    assert_eq!(january.income, 300000);
+    assert_eq!(january.expense, 10000);
+    assert_eq!(january.net, 290000);
+
+    // Moves are ignored as they only move money between categories.
+    let february = series.get(1).unwrap();
+    assert_eq!(february.bucket, "2023-02-01T00:00:00Z");
+    assert_eq!(february.income, 1500);
+    assert_eq!(february.expense, 50000);
+    assert_eq!(february.net, -48500);
+}
+
+#[sqlx::test(fixtures("users", "aggregates"))]
+async fn series_can_be_limited_by_date(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = get_series(
+        app.pool_state(),
+        app.alice(),
+        SeriesQueryDto {
+            from: Some("2023-01-20T00:00:00Z".to_string()),
+            to: Some("2023-02-10T00:00:00Z".to_string()),
+            interval: SeriesInterval::Day,
+            group_by: None,
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(series.len(), 2);
+    assert_eq!(series.get(0).unwrap().bucket, "2023-01-25T00:00:00Z");
+    assert_eq!(series.get(1).unwrap().bucket, "2023-02-01T00:00:00Z");
+}
+
+#[sqlx::test(fixtures("users", "aggregates"))]
+async fn series_can_be_grouped_by_category(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = get_series(
+        app.pool_state(),
+        app.alice(),
+        SeriesQueryDto {
+            from: Some("2023-02-01T00:00:00Z".to_string()),
+            to: None,
+            interval: SeriesInterval::Year,
+            group_by: Some(SeriesGroupBy::Category),
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(series.len(), 3);
+
+    let uncategorized = series.get(0).unwrap();
+    assert_eq!(uncategorized.group_id, None);
+    assert_eq!(uncategorized.net, 1500);
+
+    let groceries = series.get(1).unwrap();
+    assert_eq!(groceries.group_id, Some("aggregate-category-1".to_string()));
+    assert_eq!(groceries.expense, 2000);
+
+    let rent = series.get(2).unwrap();
+    assert_eq!(rent.group_id, Some("aggregate-category-2".to_string()));
+    assert_eq!(rent.income, 2000);
+    assert_eq!(rent.expense, 50000);
+    assert_eq!(rent.net, -48000);
+}
+
+#[sqlx::test(fixtures("users", "aggregates"))]
+async fn series_of_other_user_is_empty(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = get_series(
+        app.pool_state(),
+        app.bob(),
+        SeriesQueryDto {
+            from: None,
+            to: None,
+            interval: SeriesInterval::Week,
+            group_by: Some(SeriesGroupBy::BankAccount),
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(series.is_empty());
+}
@@ -0,0 +1,242 @@
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::tokio::io::AsyncReadExt;
+use sqlx::PgPool;
+use crate::error::error_dto_trait::ToErrorDto;
+use crate::error::Error;
+use crate::models::dto::transactions::new_attachment_dto::NewAttachmentDto;
+use crate::routes::transactions::attachments::{create_attachment, delete_attachment, download_attachment, get_attachments};
+use crate::tests::common::TestApp;
+
+const PDF: &[u8] = b"%PDF-1.4\n%%EOF\n";
+const GIF: &[u8] = b"GIF89a\x01\x00\x01\x00\x00\x00\x00;";
+
+async fn blob_count(pool: &PgPool) -> i64 {
+    sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM blobs"#)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn receipt_can_be_attached_and_downloaded(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let token = app.upload("abc", PDF).await;
+
+    let attachment = create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        Json(NewAttachmentDto { token: &token, filename: " receipt.pdf " }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(attachment.filename, "receipt.pdf");
+    assert_eq!(attachment.mime_type, "application/pdf");
+
+    let attachments = get_attachments(app.pool_state(), app.alice(), "transaction-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(attachments.len(), 1);
+
+    let mut content = download_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        &attachment.id,
+    )
+        .await
+        .unwrap();
+
+    let mut bytes = vec![];
+    content.reader.read_to_end(&mut bytes).await.unwrap();
+
+    assert_eq!(content.content_type.to_string(), "application/pdf");
+    assert_eq!(bytes, PDF);
+
+    // Other users cannot download it.
+    let result = download_attachment(
+        app.pool_state(),
+        app.bob(),
+        app.blob_service(),
+        "transaction-1",
+        &attachment.id,
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn file_can_only_be_attached_once(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let token = app.upload("abc", PDF).await;
+
+    create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        Json(NewAttachmentDto { token: &token, filename: "receipt.pdf" }),
+    )
+        .await
+        .unwrap();
+
+    let result = create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-2",
+        Json(NewAttachmentDto { token: &token, filename: "receipt.pdf" }),
+    )
+        .await;
+
+    let Err(Error::HttpError(error)) = result else {
+        panic!("Expected an HTTP error");
+    };
+
+    assert_eq!(error.get_status_code(), Status::BadRequest);
+
+    let attachments = get_attachments(app.pool_state(), app.alice(), "transaction-2")
+        .await
+        .unwrap()
+        .0;
+
+    assert!(attachments.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn only_documents_can_be_attached(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let token = app.upload("abc", GIF).await;
+
+    let result = create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        Json(NewAttachmentDto { token: &token, filename: "funny.gif" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // The blob was uploaded by alice, so bob cannot attach it.
+    let token = app.upload("abc", PDF).await;
+
+    let result = create_attachment(
+        app.pool_state(),
+        app.bob(),
+        app.blob_service(),
+        "transaction-4",
+        Json(NewAttachmentDto { token: &token, filename: "receipt.pdf" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // Alice cannot attach it to a transaction of bob.
+    let result = create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-4",
+        Json(NewAttachmentDto { token: &token, filename: "receipt.pdf" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn attachment_can_be_deleted(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+    let token = app.upload("abc", PDF).await;
+
+    let attachment = create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        Json(NewAttachmentDto { token: &token, filename: "receipt.pdf" }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    let result = delete_attachment(
+        app.pool_state(),
+        app.bob(),
+        app.blob_service(),
+        "transaction-1",
+        &attachment.id,
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    delete_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        &attachment.id,
+    )
+        .await
+        .unwrap();
+
+    let attachments = get_attachments(app.pool_state(), app.alice(), "transaction-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert!(attachments.is_empty());
+    assert_eq!(blob_count(&pool).await, 0);
+
+    let result = app.blob_service().read().await.get_blob(&token).await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn attachments_are_deleted_with_transaction(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+    let token = app.upload("abc", PDF).await;
+
+    create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        Json(NewAttachmentDto { token: &token, filename: "receipt.pdf" }),
+    )
+        .await
+        .unwrap();
+
+    sqlx::query!("DELETE FROM transactions WHERE id = 'transaction-1' AND user_id = 'abc'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(blob_count(&pool).await, 0);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn tokens_outside_of_the_blob_root_are_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let blob_service = app.blob_service().read().await;
+
+    let encoded = base64_url::encode("application/pdf");
+
+    for token in [format!("storage-{}-../../etc/passwd", encoded), "../blobs".to_string()] {
+        let result = blob_service.get_blob(token).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use sqlx::PgPool;
+use crate::models::dto::aggregates::series_query_dto::SeriesInterval;
+use crate::models::dto::bank_accounts::balance_history_query_dto::BalanceHistoryQueryDto;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::routes::bank_accounts::{get_bank_account_balance, get_bank_account_balance_history, get_transactions_for_bank_account};
+use crate::tests::common::TestApp;
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn balance_is_calculated_at_date(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let balance = get_bank_account_balance(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1",
+        Some("2023-02-01T00:00:00Z"),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    // Splits are already part of the complete amount of their parent transaction.
+    assert_eq!(balance.date, "2023-02-01T00:00:00Z");
+    assert_eq!(balance.balance, 390000);
+
+    let balance = get_bank_account_balance(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1",
+        None,
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(balance.balance, 340000);
+}
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn balance_of_other_user_cannot_be_requested(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_bank_account_balance(
+        app.pool_state(),
+        app.bob(),
+        "balance-bank-account-1",
+        None,
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn balance_history_includes_periods_without_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let history = get_bank_account_balance_history(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1",
+        BalanceHistoryQueryDto {
+            from: "2023-01-15T00:00:00Z".to_string(),
+            to: "2023-04-01T00:00:00Z".to_string(),
+            interval: SeriesInterval::Month,
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(history.len(), 3);
+
+    assert_eq!(history.get(0).unwrap().date, "2023-01-01T00:00:00Z");
+    assert_eq!(history.get(0).unwrap().balance, 390000);
+
+    assert_eq!(history.get(1).unwrap().date, "2023-02-01T00:00:00Z");
+    assert_eq!(history.get(1).unwrap().balance, 390000);
+
+    assert_eq!(history.get(2).unwrap().date, "2023-03-01T00:00:00Z");
+    assert_eq!(history.get(2).unwrap().balance, 340000);
+}
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn balance_history_requires_from_before_to(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_bank_account_balance_history(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1",
+        BalanceHistoryQueryDto {
+            from: "2023-04-01T00:00:00Z".to_string(),
+            to: "2023-01-01T00:00:00Z".to_string(),
+            interval: SeriesInterval::Day,
+        },
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn balance_history_is_limited_in_length(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_bank_account_balance_history(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1",
+        BalanceHistoryQueryDto {
+            from: "2000-01-01T00:00:00Z".to_string(),
+            to: "2023-01-01T00:00:00Z".to_string(),
+            interval: SeriesInterval::Day,
+        },
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let history = get_bank_account_balance_history(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1",
+        BalanceHistoryQueryDto {
+            from: "2000-01-01T00:00:00Z".to_string(),
+            to: "2023-01-01T00:00:00Z".to_string(),
+            interval: SeriesInterval::Month,
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(history.len(), 276);
+}
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn transactions_include_running_balance(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let transactions = get_transactions_for_bank_account(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1".to_string(),
+        PaginationQueryDto { page: 1, limit: 50, cursor: None },
+        true,
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    let balances: Vec<(&str, Option<i64>)> = transactions
+        .iter()
+        .map(|transaction| (transaction.id.as_str(), transaction.running_balance))
+        .collect();
+
+    // Transactions on the same date follow the order indicator.
+    assert_eq!(
+        balances,
+        vec![
+            ("balance-transaction-3", Some(340000)),
+            ("balance-transaction-2", Some(390000)),
+            ("balance-transaction-1", Some(90000)),
+            ("balance-correction-1", Some(100000)),
+        ]
+    );
+}
+
+#[sqlx::test(fixtures("users", "balances"))]
+async fn running_balance_is_only_included_when_requested(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let transactions = get_transactions_for_bank_account(
+        app.pool_state(),
+        app.alice(),
+        "balance-bank-account-1".to_string(),
+        PaginationQueryDto { page: 1, limit: 50, cursor: None },
+        false,
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert!(transactions.iter().all(|transaction| transaction.running_balance.is_none()));
+}
@@ -0,0 +1,71 @@
+use std::env;
+
+use rocket::tokio::fs;
+use rocket::tokio::io::AsyncReadExt;
+use crate::init::blob_storage_options::S3Options;
+use crate::services::blob_storage::{BlobState, BlobStorage};
+use crate::services::blob_storage::fs_storage::FsStorage;
+use crate::services::blob_storage::s3_storage::S3Storage;
+use crate::utils::rand_string::rand_string;
+
+/// Runs a blob through the whole lifecycle of the storage, from the upload to the deletion.
+async fn exercise_storage(storage: &dyn BlobStorage) {
+    let token = rand_string(32);
+    let file = env::temp_dir().join(format!("ledgero-upload-{}", token));
+    fs::write(&file, b"some content").await.unwrap();
+
+    storage.put_unconfirmed(&token, &file).await.unwrap();
+
+    assert!(storage.exists(BlobState::Unconfirmed, &token).await.unwrap());
+    assert!(!storage.exists(BlobState::Confirmed, &token).await.unwrap());
+    assert!(storage.get(&token).await.unwrap().is_none());
+
+    let unconfirmed = storage.list(BlobState::Unconfirmed).await.unwrap();
+    let stored = unconfirmed.iter().find(|blob| blob.token == token).unwrap();
+    assert_eq!(stored.size, 12);
+
+    assert!(storage.confirm(&token).await.unwrap());
+    assert!(!storage.confirm(&token).await.unwrap());
+
+    assert!(!storage.exists(BlobState::Unconfirmed, &token).await.unwrap());
+    assert!(storage.exists(BlobState::Confirmed, &token).await.unwrap());
+
+    let mut content = vec![];
+    storage.get(&token).await.unwrap().unwrap().read_to_end(&mut content).await.unwrap();
+    assert_eq!(content, b"some content");
+
+    let confirmed = storage.list(BlobState::Confirmed).await.unwrap();
+    assert!(confirmed.iter().any(|blob| blob.token == token));
+
+    storage.delete(BlobState::Confirmed, &token).await.unwrap();
+    storage.delete(BlobState::Confirmed, &token).await.unwrap();
+
+    assert!(!storage.exists(BlobState::Confirmed, &token).await.unwrap());
+    assert!(storage.get(&token).await.unwrap().is_none());
+}
+
+#[rocket::async_test]
+async fn filesystem_storage() {
+    let root = env::temp_dir().join(format!("ledgero-storage-{}", rand_string(16)));
+    let storage = FsStorage::new(root.join("unconfirmed"), root.join("blobs")).unwrap();
+
+    exercise_storage(&storage).await;
+}
+
+/// Needs an S3 compatible server, for example the MinIO server from the docker-compose file,
+/// with an existing bucket.
+#[rocket::async_test]
+#[ignore]
+async fn s3_storage() {
+    let var = |name: &str, default: &str| env::var(name).unwrap_or_else(|_| default.to_string());
+
+    let storage = S3Storage::new(S3Options {
+        endpoint: var("S3_ENDPOINT", "http://localhost:9000"),
+        region: var("S3_REGION", "us-east-1"),
+        bucket: var("S3_BUCKET", "ledgero"),
+        access_key_id: var("S3_ACCESS_KEY_ID", "minioadmin"),
+        secret_access_key: var("S3_SECRET_ACCESS_KEY", "minioadmin"),
+    }).unwrap();
+
+    exercise_storage(&storage).await;
+}
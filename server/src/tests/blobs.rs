@@ -0,0 +1,163 @@
+use std::fs;
+use std::time::Duration;
+
+use rocket::serde::json::Json;
+use rocket::tokio::io::AsyncReadExt;
+use sqlx::PgPool;
+use crate::models::dto::transactions::new_attachment_dto::NewAttachmentDto;
+use crate::routes::blobs::get_blob;
+use crate::routes::transactions::attachments::create_attachment;
+use crate::services::blob_service::BlobUse;
+use crate::tests::common::TestApp;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+const PDF: &[u8] = b"%PDF-1.4\n%%EOF\n";
+
+async fn set_profile_image(app: &TestApp, pool: &PgPool, user_id: &str, token: &str) {
+    app.blob_service()
+        .read()
+        .await
+        .confirm_optional(user_id, pool, Some(token), BlobUse::ProfileImage)
+        .await
+        .unwrap();
+
+    sqlx::query!("UPDATE users SET profile_image = $2 WHERE id = $1", user_id, token)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn blob_tokens(pool: &PgPool) -> Vec<String> {
+    sqlx::query_scalar!("SELECT token FROM blobs ORDER BY uploaded_at")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn used_blob_can_be_downloaded_by_owner(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+    let token = app.upload("abc", PNG).await;
+
+    // Unconfirmed blobs cannot be downloaded yet.
+    let result = get_blob(app.pool_state(), app.alice(), app.blob_service(), &token).await;
+
+    assert!(result.is_err());
+
+    set_profile_image(&app, &pool, "abc", &token).await;
+
+    let mut content = get_blob(app.pool_state(), app.alice(), app.blob_service(), &token)
+        .await
+        .unwrap();
+
+    let mut bytes = vec![];
+    content.reader.read_to_end(&mut bytes).await.unwrap();
+
+    assert_eq!(content.content_type.to_string(), "image/png");
+    assert_eq!(bytes, PNG);
+
+    let result = get_blob(app.pool_state(), app.bob(), app.blob_service(), &token).await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn mimetypes_are_checked_per_use(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+    let token = app.upload("abc", PDF).await;
+
+    let result = app
+        .blob_service()
+        .read()
+        .await
+        .confirm_optional("abc", &pool, Some(&token), BlobUse::ProfileImage)
+        .await;
+
+    assert!(result.is_err());
+
+    // The same file can be confirmed where documents are allowed.
+    let result = app
+        .blob_service()
+        .read()
+        .await
+        .confirm_token_for_use("abc", &pool, &token, BlobUse::Attachment)
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn cleanup_removes_unused_blobs(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+
+    let expired = app.upload("abc", PDF).await;
+    let orphaned = app.upload("abc", PNG).await;
+    let profile_image = app.upload("abc", PNG).await;
+    let attachment = app.upload("abc", PDF).await;
+
+    app.blob_service()
+        .read()
+        .await
+        .confirm_token("abc", &pool, &orphaned)
+        .await
+        .unwrap();
+
+    set_profile_image(&app, &pool, "abc", &profile_image).await;
+
+    create_attachment(
+        app.pool_state(),
+        app.alice(),
+        app.blob_service(),
+        "transaction-1",
+        Json(NewAttachmentDto { token: &attachment, filename: "receipt.pdf" }),
+    )
+        .await
+        .unwrap();
+
+    let temp_file = app
+        .blob_service()
+        .read()
+        .await
+        .stream_to_root()
+        .join("0123456789abcdef0123456789abcdef");
+    fs::write(&temp_file, PDF).unwrap();
+
+    // Nothing is old enough to be removed yet.
+    let report = app.blob_service().read().await.cleanup(&pool).await.unwrap();
+
+    assert_eq!(report.records_deleted, 0);
+    assert_eq!(report.bytes_reclaimed, 0);
+
+    sqlx::query!(
+        r#"
+            UPDATE blobs
+            SET uploaded_at = uploaded_at - interval '2 hours',
+                confirmed_at = confirmed_at - interval '2 hours'
+        "#
+    )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    app.blob_service()
+        .read()
+        .await
+        .backdate_files(Duration::from_secs(7200))
+        .unwrap();
+
+    let report = app.blob_service().read().await.cleanup(&pool).await.unwrap();
+
+    assert_eq!(report.records_deleted, 2);
+    assert_eq!(report.files_deleted, 3);
+    assert_eq!(report.bytes_reclaimed, (PDF.len() * 2 + PNG.len()) as u64);
+    assert!(!temp_file.exists());
+
+    let mut tokens = blob_tokens(&pool).await;
+    tokens.sort();
+
+    let mut expected = vec![profile_image, attachment];
+    expected.sort();
+
+    assert_eq!(tokens, expected);
+    assert!(!tokens.contains(&expired));
+}
@@ -0,0 +1,247 @@
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::error::error_dto_trait::ToErrorDto;
+use crate::error::Error;
+use crate::models::dto::budgets::new_budget_dto::NewBudgetDto;
+use crate::models::entities::budget::budget_period::BudgetPeriod;
+use crate::routes::budgets::{create_new_budget, delete_budget, get_all_budgets, get_budget_by_id, get_budget_overview, get_budgets_overview, update_budget};
+use crate::tests::common::TestApp;
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn all_budgets_are_returned_correctly(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let budgets = get_all_budgets(
+        app.pool_state(),
+        app.alice(),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(budgets.len(), 2);
+
+    let first_budget = budgets.get(0).unwrap();
+    assert_eq!(first_budget.id, "budget-1");
+    assert_eq!(first_budget.category_id, "budget-category-1");
+    assert_eq!(first_budget.subcategory_id, None);
+    assert_eq!(first_budget.amount, 10000);
+    assert_eq!(first_budget.period, BudgetPeriod::Month);
+    assert!(first_budget.rollover);
+
+    let second_budget = budgets.get(1).unwrap();
+    assert_eq!(second_budget.id, "budget-2");
+    assert_eq!(second_budget.subcategory_id, Some("budget-subcategory-1".to_string()));
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn budget_that_is_not_owned_by_user_is_not_returned(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_budget_by_id(
+        app.pool_state(),
+        app.alice(),
+        "budget-3",
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn budget_can_be_created(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let budget = create_new_budget(
+        app.pool_state(),
+        app.alice(),
+        Json(NewBudgetDto {
+            category_id: "budget-category-2",
+            subcategory_id: None,
+            amount: 92000,
+            period: BudgetPeriod::Month,
+            rollover: false,
+            starts_at: Some("2023-03-15T12:00:00Z"),
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(budget.category_id, "budget-category-2");
+    assert_eq!(budget.amount, 92000);
+    assert_eq!(budget.period, BudgetPeriod::Month);
+    assert!(!budget.rollover);
+    assert_eq!(budget.starts_at, "2023-03-01T00:00:00+00:00");
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn budget_cannot_be_created_for_category_of_other_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = create_new_budget(
+        app.pool_state(),
+        app.alice(),
+        Json(NewBudgetDto {
+            category_id: "budget-category-3",
+            subcategory_id: None,
+            amount: 10000,
+            period: BudgetPeriod::Month,
+            rollover: false,
+            starts_at: None,
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn category_cannot_have_multiple_budgets(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = create_new_budget(
+        app.pool_state(),
+        app.alice(),
+        Json(NewBudgetDto {
+            category_id: "budget-category-1",
+            subcategory_id: None,
+            amount: 10000,
+            period: BudgetPeriod::Week,
+            rollover: false,
+            starts_at: None,
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn budget_can_be_updated(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let budget = update_budget(
+        app.pool_state(),
+        app.alice(),
+        "budget-2",
+        Json(NewBudgetDto {
+            category_id: "budget-category-1",
+            subcategory_id: Some("budget-subcategory-1"),
+            amount: 2500,
+            period: BudgetPeriod::Week,
+            rollover: true,
+            starts_at: Some("2023-02-11T11:00:00Z"),
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(budget.amount, 2500);
+    assert_eq!(budget.period, BudgetPeriod::Week);
+    assert!(budget.rollover);
+    assert_eq!(budget.starts_at, "2023-02-06T00:00:00+00:00");
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn budget_of_other_user_cannot_be_deleted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = delete_budget(
+        app.pool_state(),
+        app.alice(),
+        "budget-3",
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    delete_budget(
+        app.pool_state(),
+        app.alice(),
+        "budget-1",
+    )
+        .await
+        .unwrap();
+
+    let budgets = get_all_budgets(
+        app.pool_state(),
+        app.alice(),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(budgets.len(), 1);
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn unspent_money_is_rolled_over(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let overview = get_budget_overview(
+        app.pool_state(),
+        app.alice(),
+        "budget-1",
+        Some("2023-03-15T00:00:00Z"),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    // January leaves 75.00, February overspends which resets the rollover to 25.00
+    assert_eq!(overview.period_start, "2023-03-01T00:00:00+00:00");
+    assert_eq!(overview.period_end, "2023-04-01T00:00:00+00:00");
+    assert_eq!(overview.budgeted, 10000);
+    assert_eq!(overview.rolled_over, 2500);
+    assert_eq!(overview.actual, 3000);
+    assert_eq!(overview.remaining, 9500);
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn overview_is_returned_for_all_budgets(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let overviews = get_budgets_overview(
+        app.pool_state(),
+        app.alice(),
+        Some("2023-02-10T00:00:00Z"),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(overviews.len(), 2);
+
+    let category_overview = overviews.get(0).unwrap();
+    assert_eq!(category_overview.budget_id, "budget-1");
+    assert_eq!(category_overview.rolled_over, 7500);
+    assert_eq!(category_overview.actual, 15000);
+    assert_eq!(category_overview.remaining, 2500);
+
+    let subcategory_overview = overviews.get(1).unwrap();
+    assert_eq!(subcategory_overview.budget_id, "budget-2");
+    assert_eq!(subcategory_overview.rolled_over, 0);
+    assert_eq!(subcategory_overview.actual, 15000);
+    assert_eq!(subcategory_overview.remaining, -10000);
+}
+
+#[sqlx::test(fixtures("users", "budgets"))]
+async fn overview_with_invalid_date_is_a_bad_request(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_budgets_overview(
+        app.pool_state(),
+        app.alice(),
+        Some("10-02-2023"),
+    )
+        .await;
+
+    let Err(Error::HttpError(error)) = result else {
+        panic!("Expected an HTTP error");
+    };
+
+    assert_eq!(error.get_status_code(), Status::BadRequest);
+}
@@ -53,6 +53,7 @@ async fn money_can_be_moved_between_categories(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -66,6 +67,7 @@ async fn money_can_be_moved_between_categories(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -140,6 +142,7 @@ async fn money_can_be_moved_between_subcategories(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -154,6 +157,7 @@ async fn money_can_be_moved_between_subcategories(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -287,6 +291,7 @@ async fn move_transaction_can_be_deleted(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -312,6 +317,7 @@ async fn move_transaction_can_be_deleted(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -329,6 +335,7 @@ async fn move_transaction_can_be_deleted(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
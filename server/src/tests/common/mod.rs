@@ -8,14 +8,18 @@ use rocket::State;
 use rsa::{BigUint};
 use sqlx::{PgPool, Pool, Postgres};
 use jumpdrive_auth::services::JwtService;
+use crate::models::csv::amount_format::AmountFormat;
+use crate::models::csv::csv_mapping::{AmountMapping, CsvColumn, CsvImportOrdering, CsvMapping, DateMapping};
 use crate::models::entities::user::user_role::UserRole;
 use crate::models::jwt::jwt_user_payload::JwtUserPayload;
+use crate::services::blob_service::BlobService;
 use crate::services::rate_limiter::RateLimiter;
 
 pub struct TestApp {
     pool: Arc<RwLock<Pool<Postgres>>>,
     jwt_service: JwtService,
     rate_limiter: RateLimiter,
+    blob_service: Arc<RwLock<BlobService>>,
 }
 
 impl TestApp {
@@ -23,6 +27,7 @@ impl TestApp {
         Self {
             pool: Arc::new(RwLock::new(pool)),
             rate_limiter: RateLimiter::new_test(),
+            blob_service: Arc::new(RwLock::new(BlobService::new_test().unwrap())),
             jwt_service: JwtService::new(
                 RsaPrivateKey::from_components(
                     BigUint::from_str("74997830905646587139816226014144719862265627823949553374295905850158141318656719276313209175746760261055971134897398913479558563360202476134525738215443985213798786134947536321820103185111448036430087812065337288385932817127530120303914818733328961756008475729319280311987156480371871574865965853381575857139")
@@ -54,6 +59,22 @@ impl TestApp {
         State::from(&self.rate_limiter)
     }
 
+    pub fn blob_service(&self) -> &State<Arc<RwLock<BlobService>>> {
+        State::from(&self.blob_service)
+    }
+
+    /// Uploads the bytes as a pending blob of the user and returns its token.
+    pub async fn upload(&self, user_id: &str, bytes: &[u8]) -> String {
+        let pool = self.pool.read().await;
+
+        self.blob_service
+            .read()
+            .await
+            .upload_bytes(user_id, &pool, bytes)
+            .await
+            .unwrap()
+    }
+
     pub fn remote_ip(&self) -> SocketAddr {
         SocketAddr::new("127.0.0.0".parse().unwrap(), 600)
     }
@@ -78,3 +99,34 @@ impl TestApp {
         }
     }
 }
+
+pub fn column(name: &str) -> CsvColumn {
+    CsvColumn::Name(name.to_string())
+}
+
+/// Maps an export with the columns `Date`, `Account`, `Reference`, `Name`, `Description` and
+/// `Amount`, with dates like `2023-03-01` and decimal amounts. Tests only override the fields in
+/// which their export differs.
+pub fn csv_mapping() -> CsvMapping {
+    CsvMapping {
+        date_mapping: DateMapping {
+            template: Some("$ 00:00:00 +0000".to_string()),
+            format: "%Y-%m-%d %H:%M:%S %z".to_string(),
+        },
+        amount_mapping: AmountMapping::Decimal,
+        amount_format: AmountFormat::default(),
+        ordering: CsvImportOrdering::NewestLast,
+        account_iban: column("Account"),
+        date: column("Date"),
+        follow_number: column("Reference"),
+        description: column("Description"),
+        amount: column("Amount"),
+        external_account_name: column("Name"),
+        currency: None,
+        external_account_iban: None,
+        value_date: None,
+        default_currency: None,
+        debit_credit: None,
+        credit_amount: None,
+    }
+}
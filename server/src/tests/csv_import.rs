@@ -0,0 +1,344 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::csv::amount_format::{AmountFormat, NegativeStyle};
+use crate::models::csv::csv_mapping::{CsvColumn, CsvMapping, DateMapping, DebitCreditMapping};
+use crate::error::import_error::ImportError;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::importing::import_row_dto::ImportRowStatus;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::routes::importing::check_csv_mapping::check_csv_mapping;
+use crate::prelude::*;
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::importing::get_all_imports;
+use crate::routes::transactions::transaction_management::get_all_transactions;
+use crate::tests::common::{column, csv_mapping, TestApp};
+
+// Looks like an ING export, which has the amounts without a sign and a separate column for the
+// direction of the transaction.
+const CSV: &str = "\"Datum\",\"Naam / Omschrijving\",\"Rekening\",\"Tegenrekening\",\"Code\",\"Af Bij\",\"Bedrag (EUR)\",\"Mededelingen\",\"Volgnr\"
+\"20230211\",\"Jumbo\",\"NL12INGB0001234567\",\"NL99RABO0123456789\",\"BA\",\"Af\",\"93,00\",\"Groceries\",\"1\"
+\"20230225\",\"Work\",\"NL12INGB0001234567\",\"\",\"GT\",\"Bij\",\"3000,00\",\"Salary\",\"2\"";
+
+fn mapping() -> CsvMapping {
+    CsvMapping {
+        date_mapping: DateMapping {
+            template: Some("$ 00:00:00 +0000".to_string()),
+            format: "%Y%m%d %H:%M:%S %z".to_string(),
+        },
+        account_iban: column("Rekening"),
+        date: column("datum"),
+        follow_number: column("Volgnr"),
+        description: column("Mededelingen"),
+        amount: column("Bedrag (EUR)"),
+        external_account_name: CsvColumn::Index(1),
+        external_account_iban: Some(column("Tegenrekening")),
+        debit_credit: Some(DebitCreditMapping {
+            column: column("Af Bij"),
+            debit: "af".to_string(),
+        }),
+        ..csv_mapping()
+    }
+}
+
+fn import_body(mapping: CsvMapping) -> ImportCsvDto {
+    ImportCsvDto {
+        mappings: Some(mapping),
+        profile_id: None,
+        filename: "ing-export.csv".to_string(),
+        csv: CSV.to_string(),
+    }
+}
+
+/// The CSV with a row for a new bank account and two rows that cannot be read.
+fn csv_with_errors() -> String {
+    [
+        CSV,
+        "\"20230301\",\"Shop\",\"NL34INGB0007654321\",\"\",\"BA\",\"Af\",\"10,00\",\"Savings\",\"3\"",
+        "\"20230302\",\"Shop\",\"NL12INGB0001234567\",\"\",\"BA\",\"Af\",\"ten\",\"Broken amount\",\"4\"",
+        "\"2023-03-03\",\"Shop\",\"NL12INGB0001234567\",\"\",\"BA\",\"Af\",\"10,00\",\"Broken date\",\"5\"",
+    ]
+    .join("\n")
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn csv_can_be_imported_with_column_names(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(mapping())),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert_eq!(transactions.len(), 2);
+
+    let salary = transactions.get(0).unwrap();
+    assert_eq!(salary.follow_number, "2");
+    assert_eq!(salary.description, "Salary");
+    assert_eq!(salary.amount, 300000);
+    assert_eq!(salary.external_account_name, "Work");
+    assert_eq!(salary.bank_account.as_ref().unwrap().iban, "NL12INGB0001234567");
+
+    let groceries = transactions.get(1).unwrap();
+    assert_eq!(groceries.follow_number, "1");
+    assert_eq!(groceries.amount, -9300);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn optional_columns_are_mapped(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let groceries = check_csv_mapping(Json(import_body(mapping())))
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(groceries.amount, -9300);
+    assert_eq!(groceries.external_account_iban.as_deref(), Some("NL99RABO0123456789"));
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(mapping())),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    // Empty values of optional columns are left out.
+    let salary = sqlx::query!("SELECT external_account_iban FROM transactions WHERE follow_number = '2'")
+        .fetch_one(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    assert_eq!(salary.external_account_iban, None);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn unknown_column_names_are_reported(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut mapping = mapping();
+    mapping.amount = column("Amount");
+    mapping.currency = Some(column("Currency"));
+
+    let result = check_csv_mapping(Json(import_body(mapping.clone()))).await;
+
+    let Err(Error::ImportError(ImportError::MissingColumn(columns))) = result else {
+        panic!("Expected the unknown columns to be reported");
+    };
+
+    assert_eq!(columns, "Amount, Currency");
+
+    let result = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(mapping)),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn dry_run_reports_every_row_without_saving(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(mapping())),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let mut body = import_body(mapping());
+    body.csv = csv_with_errors();
+
+    let report = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+        ImportQueryDto { dry_run: true },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(report.dry_run);
+    assert_eq!(report.inserted, 1);
+    assert_eq!(report.duplicates, 2);
+    assert_eq!(report.errors, 2);
+    assert_eq!(report.new_bank_accounts, 1);
+
+    let statuses: Vec<(u64, ImportRowStatus)> = report
+        .rows
+        .iter()
+        .map(|row| (row.row, row.status))
+        .collect();
+
+    assert_eq!(
+        statuses,
+        vec![
+            (2, ImportRowStatus::Duplicate),
+            (3, ImportRowStatus::Duplicate),
+            (4, ImportRowStatus::Insert),
+            (5, ImportRowStatus::Error),
+            (6, ImportRowStatus::Error),
+        ]
+    );
+
+    let savings = report.rows.get(2).unwrap();
+    assert!(savings.new_bank_account);
+    assert_eq!(savings.follow_number.as_deref(), Some("3"));
+
+    assert_eq!(report.rows.get(3).unwrap().column.as_deref(), Some("amount"));
+    assert_eq!(report.rows.get(4).unwrap().column.as_deref(), Some("date"));
+
+    // Nothing of the dry run is saved.
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert_eq!(transactions.len(), 2);
+
+    let imports = get_all_imports(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(imports.len(), 1);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn invalid_row_is_reported_with_row_and_column(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = import_body(mapping());
+    body.csv = csv_with_errors();
+
+    let result = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    let Err(Error::ImportError(ImportError::InvalidValue { row, column, .. })) = result else {
+        panic!("Expected the import to fail on an invalid value");
+    };
+
+    assert_eq!(row, 5);
+    assert_eq!(column, "amount");
+
+    let imports = get_all_imports(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert!(imports.is_empty());
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn amounts_are_parsed_exactly_with_locale_format(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut mapping = mapping();
+    mapping.debit_credit = None;
+    mapping.external_account_iban = None;
+    mapping.amount_format = AmountFormat {
+        decimal_separator: Some(','),
+        thousands_separator: Some('.'),
+        negative_style: NegativeStyle::Parentheses,
+    };
+
+    let mut body = import_body(mapping);
+    body.csv = [
+        "\"Datum\",\"Naam / Omschrijving\",\"Rekening\",\"Bedrag (EUR)\",\"Mededelingen\",\"Volgnr\"",
+        "\"20230211\",\"Jumbo\",\"NL12INGB0001234567\",\"(0,29)\",\"Groceries\",\"1\"",
+        "\"20230225\",\"Work\",\"NL12INGB0001234567\",\"1.234,56\",\"Salary\",\"2\"",
+    ]
+    .join("\n");
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert_eq!(transactions.get(0).unwrap().amount, 123456);
+    assert_eq!(transactions.get(1).unwrap().amount, -29);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn debit_and_credit_columns_are_combined(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut mapping = mapping();
+    mapping.debit_credit = None;
+    mapping.external_account_iban = None;
+    mapping.amount = column("Debit");
+    mapping.credit_amount = Some(column("Credit"));
+
+    let mut body = import_body(mapping);
+    body.csv = [
+        "\"Datum\",\"Naam / Omschrijving\",\"Rekening\",\"Debit\",\"Credit\",\"Mededelingen\",\"Volgnr\"",
+        "\"20230211\",\"Jumbo\",\"NL12INGB0001234567\",\"93,00\",\"\",\"Groceries\",\"1\"",
+        "\"20230225\",\"Work\",\"NL12INGB0001234567\",\"\",\"3000,00\",\"Salary\",\"2\"",
+        "\"20230226\",\"Shop\",\"NL12INGB0001234567\",\"\",\"\",\"Nothing\",\"3\"",
+    ]
+    .join("\n");
+
+    let report = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+        ImportQueryDto { dry_run: true },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(report.inserted, 2);
+    assert_eq!(report.errors, 1);
+
+    let failed = report.rows.get(2).unwrap();
+    assert_eq!(failed.row, 4);
+    assert_eq!(failed.column.as_deref(), Some("amount"));
+}
@@ -0,0 +1,204 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::csv::csv_mapping::{AmountMapping, CsvColumn, CsvImportOrdering, CsvMapping, DateMapping};
+use crate::models::dto::importing::detect_csv_mapping_dto::DetectCsvMappingDto;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::importing::new_csv_mapping_profile_dto::NewCsvMappingProfileDto;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::routes::importing::check_csv_mapping::check_csv_mapping;
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::importing::mapping_profiles::{create_csv_mapping_profile, delete_csv_mapping_profile, detect_csv_mapping, get_all_csv_mapping_profiles, get_csv_mapping_profile_by_id};
+use crate::routes::transactions::transaction_management::get_all_transactions;
+use crate::tests::common::{csv_mapping, TestApp};
+
+const CSV: &str = "iban,date,number,description,amount,name
+NL12 RABO 12345678910,2023-02-11,1,Payment for JUMBO,-93.00,Jumbo
+NL12 RABO 12345678910,2023-02-12,2,RENT 2023,-920.00,Landlord";
+
+fn import_body(mappings: Option<CsvMapping>, profile_id: Option<&str>) -> ImportCsvDto {
+    ImportCsvDto {
+        mappings,
+        profile_id: profile_id.map(|id| id.to_string()),
+        filename: "bank-export.csv".to_string(),
+        csv: CSV.to_string(),
+    }
+}
+
+#[sqlx::test(fixtures("users", "csv-mapping-profiles"))]
+async fn profiles_are_listed_per_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let profiles = get_all_csv_mapping_profiles(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles.get(0).unwrap().name, "Rabobank");
+
+    let result = get_csv_mapping_profile_by_id(app.pool_state(), app.alice(), "profile-2").await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "csv-mapping-profiles"))]
+async fn profile_can_be_created_and_deleted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let profile = create_csv_mapping_profile(
+        app.pool_state(),
+        app.alice(),
+        Json(NewCsvMappingProfileDto {
+            name: "ING".to_string(),
+            header: Some("\"Datum\", \"Naam / Omschrijving\",Rekening".to_string()),
+            mapping: CsvMapping {
+                date_mapping: DateMapping {
+                    template: None,
+                    format: "%Y%m%d".to_string(),
+                },
+                amount_mapping: AmountMapping::Cents,
+                ordering: CsvImportOrdering::NewestFirst,
+                ..csv_mapping()
+            },
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(profile.header, Some("datum,naam / omschrijving,rekening".to_string()));
+    assert_eq!(profile.mapping.amount_mapping, AmountMapping::Cents);
+    assert_eq!(profile.mapping.ordering, CsvImportOrdering::NewestFirst);
+
+    delete_csv_mapping_profile(app.pool_state(), app.alice(), &profile.id)
+        .await
+        .unwrap();
+
+    let result = get_csv_mapping_profile_by_id(app.pool_state(), app.alice(), &profile.id).await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "csv-mapping-profiles"))]
+async fn csv_can_be_imported_with_profile(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(None, Some("profile-1"))),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert_eq!(transactions.len(), 2);
+}
+
+#[sqlx::test(fixtures("users", "csv-mapping-profiles"))]
+async fn csv_cannot_be_imported_without_mapping(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(None, None)),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // Profiles of other users cannot be used.
+    let result = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(None, Some("profile-2"))),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "csv-mapping-profiles"))]
+async fn profile_is_detected_by_header(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let suggestion = detect_csv_mapping(
+        app.pool_state(),
+        app.alice(),
+        Json(DetectCsvMappingDto {
+            header: "IBAN, Date,Number,Description,Amount,Name".to_string(),
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(suggestion.profile.unwrap().id, "profile-1");
+    assert_eq!(suggestion.mapping.unwrap().follow_number, CsvColumn::Index(2));
+}
+
+#[sqlx::test(fixtures("users", "csv-mapping-profiles"))]
+async fn mapping_is_guessed_without_profile(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let suggestion = detect_csv_mapping(
+        app.pool_state(),
+        app.bob(),
+        Json(DetectCsvMappingDto {
+            header: "Datum,Omschrijving,Rekening,Volgnr,Bedrag,Naam tegenpartij".to_string(),
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(suggestion.profile.is_none());
+
+    let mapping = suggestion.mapping.unwrap();
+    assert_eq!(mapping.date, CsvColumn::Name("Datum".to_string()));
+    assert_eq!(mapping.description, CsvColumn::Name("Omschrijving".to_string()));
+    assert_eq!(mapping.account_iban, CsvColumn::Name("Rekening".to_string()));
+    assert_eq!(mapping.follow_number, CsvColumn::Name("Volgnr".to_string()));
+    assert_eq!(mapping.amount, CsvColumn::Name("Bedrag".to_string()));
+    assert_eq!(mapping.external_account_name, CsvColumn::Name("Naam tegenpartij".to_string()));
+}
+
+#[rocket::async_test]
+async fn check_mapping_requires_the_mappings() {
+    let result = check_csv_mapping(Json(import_body(None, Some("profile-1")))).await;
+
+    assert!(result.is_err());
+
+    let mapping = CsvMapping {
+        account_iban: CsvColumn::Name("iban".to_string()),
+        date: CsvColumn::Name("date".to_string()),
+        follow_number: CsvColumn::Name("number".to_string()),
+        description: CsvColumn::Name("description".to_string()),
+        amount: CsvColumn::Name("amount".to_string()),
+        external_account_name: CsvColumn::Name("name".to_string()),
+        ..csv_mapping()
+    };
+
+    let record = check_csv_mapping(Json(import_body(Some(mapping), None)))
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(record.follow_number, "1");
+    assert_eq!(record.amount, -9300);
+    assert_eq!(record.external_account_name, "Jumbo");
+}
@@ -0,0 +1,293 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::csv::csv_mapping::CsvMapping;
+use crate::models::dto::aggregates::series_query_dto::{SeriesInterval, SeriesQueryDto};
+use crate::models::dto::exchange_rates::import_exchange_rates_dto::ImportExchangeRatesDto;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::models::dto::users::update_base_currency_dto::UpdateBaseCurrencyDto;
+use crate::routes::aggregates::{get_series, get_user_total_amount};
+use crate::routes::bank_accounts::get_bank_accounts;
+use crate::routes::budgets::get_budget_overview;
+use crate::routes::categories::get_category_by_id;
+use crate::routes::categories::subcategories::{get_subcategories, get_subcategory_by_id};
+use crate::routes::exchange_rates::{get_all_exchange_rates, import_exchange_rates};
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::transactions::transaction_management::get_all_transactions;
+use crate::routes::users::me::update_me_base_currency;
+use crate::tests::common::{column, csv_mapping, TestApp};
+
+const ECB_CSV: &str = "Date, USD, JPY, CYP, \n\
+    2023-01-10, 1.0870, 141.79, N/A, \n\
+    2023-01-09, 1.0741, 141.82, N/A, \n";
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn total_is_converted_to_base_currency(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let total = get_user_total_amount(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    // The dollars and yen are worth 100 euro each, but the pounds cannot be converted.
+    assert_eq!(total.currency, "EUR");
+    assert_eq!(total.total, -10000);
+    assert_eq!(total.missing_rates, vec!["GBP".to_string()]);
+}
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn series_is_converted_to_base_currency(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = get_series(
+        app.pool_state(),
+        app.alice(),
+        SeriesQueryDto {
+            from: None,
+            to: None,
+            interval: SeriesInterval::Month,
+            group_by: None,
+        },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    let january = series.get(0).unwrap();
+    assert_eq!(january.income, 10000);
+    assert_eq!(january.expense, 20000);
+    assert_eq!(january.missing_rates, vec!["GBP".to_string()]);
+}
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn total_follows_base_currency_of_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    update_me_base_currency(
+        app.pool_state(),
+        app.alice(),
+        Json(UpdateBaseCurrencyDto {
+            base_currency: "usd".to_string(),
+        }),
+    )
+        .await
+        .unwrap();
+
+    let total = get_user_total_amount(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(total.currency, "USD");
+    assert_eq!(total.total, -10870);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn invalid_base_currency_is_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = update_me_base_currency(
+        app.pool_state(),
+        app.alice(),
+        Json(UpdateBaseCurrencyDto {
+            base_currency: "euro".to_string(),
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn category_amounts_are_converted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let category = get_category_by_id(app.pool_state(), app.alice(), "currency-category-1")
+        .await
+        .unwrap()
+        .0;
+
+    // The pounds cannot be converted, so they are left out and reported instead.
+    assert_eq!(category.amount, -20000);
+    assert_eq!(category.missing_rates, vec!["GBP".to_string()]);
+
+    assert_eq!(category.subcategories[0].amount, -20000);
+    assert!(category.subcategories[0].missing_rates.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn subcategory_amounts_are_converted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let subcategory = get_subcategory_by_id(
+        app.pool_state(),
+        app.alice(),
+        "currency-category-1",
+        "currency-subcategory-1",
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(subcategory.amount, -20000);
+
+    let subcategories = get_subcategories(app.pool_state(), app.alice(), "currency-category-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(subcategories[0].amount, -20000);
+}
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn budget_spending_is_converted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let overview = get_budget_overview(
+        app.pool_state(),
+        app.alice(),
+        "currency-budget-1",
+        Some("2023-01-15T00:00:00Z"),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(overview.actual, 20000);
+    assert_eq!(overview.missing_rates, vec!["GBP".to_string()]);
+
+    let overview = get_budget_overview(
+        app.pool_state(),
+        app.alice(),
+        "currency-budget-2",
+        Some("2023-01-15T00:00:00Z"),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(overview.actual, 20000);
+    assert!(overview.missing_rates.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "currencies"))]
+async fn bank_accounts_have_currency(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let bank_accounts = get_bank_accounts(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    let dollar_account = bank_accounts
+        .iter()
+        .find(|bank_account| bank_account.id == "currency-bank-account-usd")
+        .unwrap();
+
+    assert_eq!(dollar_account.currency, "USD");
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn exchange_rates_can_be_imported(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let imported = import_exchange_rates(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportExchangeRatesDto {
+            csv: ECB_CSV.to_string(),
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(imported, 4);
+
+    // Importing the same rates again overwrites them instead of failing.
+    import_exchange_rates(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportExchangeRatesDto {
+            csv: "Date,USD\n2023-01-10,1.09\n".to_string(),
+        }),
+    )
+        .await
+        .unwrap();
+
+    let rates = get_all_exchange_rates(app.pool_state(), app.alice(), Some("usd"))
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(rates.len(), 2);
+    assert_eq!(rates[0].date, "2023-01-10");
+    assert_eq!(rates[0].rate, 1.09);
+
+    let rates = get_all_exchange_rates(app.pool_state(), app.bob(), None)
+        .await
+        .unwrap()
+        .0;
+
+    assert!(rates.is_empty());
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn csv_amounts_use_minor_unit_of_currency(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let csv = "Date,Account,Reference,Name,Description,Amount,Currency
+2023-01-10,JP12 3456,1,Hotel,Stay,-15000,JPY
+2023-01-11,NL12 RABO,2,Jumbo,Groceries,\"-12,50\",eur
+2023-01-12,NL12 RABO,3,Shop,Tea,-4.5,";
+
+    let mapping = CsvMapping {
+        currency: Some(column("Currency")),
+        default_currency: Some("GBP".to_string()),
+        ..csv_mapping()
+    };
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportCsvDto {
+            mappings: Some(mapping),
+            profile_id: None,
+            filename: "export.csv".to_string(),
+            csv: csv.to_string(),
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    let find = |follow_number: &str| {
+        transactions
+            .iter()
+            .find(|transaction| transaction.follow_number == follow_number)
+            .unwrap()
+    };
+
+    assert_eq!(find("1").amount, -15000);
+    assert_eq!(find("1").currency, "JPY");
+    assert_eq!(find("1").bank_account.as_ref().unwrap().currency, "JPY");
+
+    assert_eq!(find("2").amount, -1250);
+    assert_eq!(find("2").currency, "EUR");
+
+    // Rows without a currency fall back to the default currency of the mapping.
+    assert_eq!(find("3").amount, -450);
+    assert_eq!(find("3").currency, "GBP");
+}
@@ -0,0 +1,127 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+
+use crate::models::dto::external_accounts::accept_external_account_suggestion_dto::AcceptExternalAccountSuggestionDto;
+use crate::routes::external_accounts::{accept_external_account_suggestion, get_external_account_names, get_external_account_suggestions};
+use crate::routes::transactions::transaction_management::get_single_transaction;
+use crate::tests::common::TestApp;
+
+#[sqlx::test(fixtures("users", "external-accounts", "external-account-suggestions"))]
+async fn unlinked_names_are_clustered(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let suggestions = get_external_account_suggestions(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(suggestions.len(), 2);
+
+    let first = &suggestions[0];
+    assert_eq!(first.key, "albert heijn");
+    assert_eq!(first.suggested_name, "ALBERT HEIJN 1234");
+    assert_eq!(first.transaction_count, 4);
+    assert_eq!(first.names, vec!["ALBERT HEIJN 1234", "Albert Heijn 5678", "Albert Heijn Utrecht"]);
+
+    let second = &suggestions[1];
+    assert_eq!(second.key, "de bakker");
+    assert_eq!(second.transaction_count, 1);
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "external-account-suggestions"))]
+async fn a_suggestion_can_be_accepted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let external_account = accept_external_account_suggestion(
+        app.pool_state(),
+        app.alice(),
+        Json(AcceptExternalAccountSuggestionDto {
+            key: "albert heijn",
+            name: Some("Albert Heijn"),
+            description: "",
+            hex_color: None,
+            default_category_id: None,
+            default_subcategory_id: None,
+        }),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(external_account.name, "Albert Heijn");
+    assert_eq!(external_account.hex_color, "ffffff");
+
+    let names = get_external_account_names(
+        app.pool_state(),
+        app.alice(),
+        external_account.id.to_string(),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(names.len(), 3);
+
+    for transaction_id in ["suggestion-transaction-1", "suggestion-transaction-3", "suggestion-transaction-4"] {
+        let transaction = get_single_transaction(app.pool_state(), app.alice(), transaction_id)
+            .await
+            .unwrap()
+            .0;
+
+        assert_eq!(transaction.external_account.unwrap().id, external_account.id);
+    }
+
+    let transaction = get_single_transaction(app.pool_state(), app.bob(), "suggestion-transaction-6")
+        .await
+        .unwrap()
+        .0;
+
+    assert!(transaction.external_account.is_none());
+
+    let suggestions = get_external_account_suggestions(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].key, "de bakker");
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "external-account-suggestions"))]
+async fn names_matched_by_an_external_account_are_not_suggested(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    sqlx::query("INSERT INTO external_account_names VALUES ('suggestion-name-1', 'abc', 'De Bakker', 'external-account-1');")
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    let suggestions = get_external_account_suggestions(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].key, "albert heijn");
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "external-account-suggestions"))]
+async fn an_unknown_suggestion_cannot_be_accepted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = accept_external_account_suggestion(
+        app.pool_state(),
+        app.alice(),
+        Json(AcceptExternalAccountSuggestionDto {
+            key: "jumbo",
+            name: None,
+            description: "",
+            hex_color: None,
+            default_category_id: None,
+            default_subcategory_id: None,
+        }),
+    )
+    .await;
+
+    assert!(result.is_err());
+}
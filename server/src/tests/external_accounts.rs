@@ -1,11 +1,17 @@
 use rocket::serde::json::Json;
 use sqlx::PgPool;
+use crate::models::csv::csv_mapping::CsvMapping;
 use crate::models::dto::external_accounts::new_external_account_dto::NewExternalAccountDto;
+use crate::models::dto::external_accounts::new_external_account_iban_dto::NewExternalAccountIbanDto;
 use crate::models::dto::external_accounts::new_external_account_name_dto::NewExternalAccountNameDto;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
-use crate::routes::external_accounts::{add_external_account_name, apply_external_account_name, create_new_external_account, delete_external_account, delete_external_account_name, get_all_external_accounts, get_external_account_by_id, get_external_account_names, get_transactions_for_external_account, remove_external_account_name_associations, update_external_account};
+use crate::models::entities::external_account_names::name_match_kind::NameMatchKind;
+use crate::routes::external_accounts::{add_external_account_iban, add_external_account_name, apply_external_account_iban, apply_external_account_name, create_new_external_account, delete_external_account, delete_external_account_iban, delete_external_account_name, get_all_external_accounts, get_external_account_by_id, get_external_account_ibans, get_external_account_names, get_transactions_for_external_account, remove_external_account_name_associations, update_external_account};
+use crate::routes::importing::csv_import::import_csv;
 use crate::routes::transactions::transaction_management::{get_all_transactions, get_single_transaction};
-use crate::tests::common::TestApp;
+use crate::tests::common::{column, csv_mapping, TestApp};
 
 #[sqlx::test(fixtures("users", "external-accounts"))]
 async fn all_external_accounts_are_returned_correctly(pool: PgPool) {
@@ -200,7 +206,8 @@ async fn name_can_be_added_to_external_account(pool: PgPool) {
         app.alice(),
         "external-account-1".to_string(),
         Json(NewExternalAccountNameDto {
-            name: "jumbo"
+            name: "jumbo",
+            match_kind: NameMatchKind::Exact,
         }),
     )
         .await
@@ -341,6 +348,7 @@ async fn transactions_are_returned_correctly_for_external_account(pool: PgPool)
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         }
     )
         .await
@@ -350,3 +358,410 @@ async fn transactions_are_returned_correctly_for_external_account(pool: PgPool)
 
     assert_eq!(transactions.len(), 1);
 }
+
+#[sqlx::test(fixtures("users", "external-accounts"))]
+async fn iban_can_be_added_and_removed(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let iban = add_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-3".to_string(),
+        Json(NewExternalAccountIbanDto { iban: " nl66 ingb 0006 6666 66" }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(iban.iban, "NL66INGB0006666666");
+    assert_eq!(iban.parent_external_account, "external-account-3");
+
+    let ibans = get_external_account_ibans(
+        app.pool_state(),
+        app.alice(),
+        "external-account-3".to_string(),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(ibans.len(), 1);
+    assert_eq!(ibans[0].id, iban.id);
+
+    // An IBAN can only belong to one of the external accounts of the user.
+    let result = add_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        Json(NewExternalAccountIbanDto { iban: "NL66INGB0006666666" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = add_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        Json(NewExternalAccountIbanDto { iban: "NL66-INGB" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = add_external_account_iban(
+        app.pool_state(),
+        app.bob(),
+        "external-account-3".to_string(),
+        Json(NewExternalAccountIbanDto { iban: "NL77INGB0007777777" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = delete_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        iban.id.to_string(),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    delete_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-3".to_string(),
+        iban.id,
+    )
+        .await
+        .unwrap();
+
+    let ibans = get_external_account_ibans(
+        app.pool_state(),
+        app.alice(),
+        "external-account-3".to_string(),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(ibans.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "transactions", "external-account-ibans"))]
+async fn external_account_iban_can_be_applied_to_existing_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    apply_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-3".to_string(),
+        "external-account-iban-1".to_string()
+    )
+        .await
+        .unwrap();
+
+    let transaction = get_single_transaction(
+        app.pool_state(),
+        app.alice(),
+        "transaction-3"
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(transaction.external_account_iban.as_deref(), Some("NL66INGB0006666666"));
+    assert_eq!(transaction.external_account.unwrap().id, "external-account-3");
+
+    let result = apply_external_account_iban(
+        app.pool_state(),
+        app.bob(),
+        "external-account-3".to_string(),
+        "external-account-iban-1".to_string()
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "transactions", "external-account-ibans"))]
+async fn imported_transactions_are_matched_on_iban_before_name(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let csv = "Date,Account,Reference,Name,Counterparty,Description,Amount
+2023-03-01,NL12 RABO 12345678910,iban-1,AH to go 1234,nl66 ingb 0006 6666 66,Rent,\"-920,00\"
+2023-03-02,NL12 RABO 12345678910,iban-2,EVIL_LAND_LORD_INC,,Rent,\"-920,00\"
+2023-03-03,NL12 RABO 12345678910,iban-3,EVIL_LAND_LORD_INC,NL77INGB0007777777,Rent,\"-920,00\"
+2023-03-04,NL12 RABO 12345678910,iban-4,Someone,,Something,\"-1,00\"";
+
+    let body = ImportCsvDto {
+        mappings: Some(CsvMapping {
+            external_account_iban: Some(column("Counterparty")),
+            ..csv_mapping()
+        }),
+        profile_id: None,
+        filename: "export.csv".to_string(),
+        csv: csv.to_string(),
+    };
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let records = sqlx::query!(
+        r#"
+            SELECT external_account_id, external_account_iban, category_id
+            FROM transactions
+            WHERE follow_number LIKE 'iban-%'
+            ORDER BY follow_number;
+        "#
+    )
+        .fetch_all(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    let matched: Vec<Option<&str>> = records
+        .iter()
+        .map(|record| record.external_account_id.as_deref())
+        .collect();
+
+    assert_eq!(matched, vec![
+        Some("external-account-3"),
+        Some("external-account-3"),
+        Some("external-account-3"),
+        None,
+    ]);
+
+    assert_eq!(records[0].external_account_iban.as_deref(), Some("NL66INGB0006666666"));
+    assert_eq!(records[0].category_id.as_deref(), Some("category-external-account"));
+    assert_eq!(records[1].external_account_iban, None);
+    assert_eq!(records[2].external_account_iban.as_deref(), Some("NL77INGB0007777777"));
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "transactions"))]
+async fn overlapping_name_patterns_are_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let add = |id: &'static str, name: &'static str, match_kind: NameMatchKind| {
+        add_external_account_name(
+            app.pool_state(),
+            app.alice(),
+            id.to_string(),
+            Json(NewExternalAccountNameDto { name, match_kind }),
+        )
+    };
+
+    // The exact name of the landlord contains the pattern.
+    assert!(add("external-account-1", "land_lord", NameMatchKind::Contains).await.is_err());
+
+    // A transaction of the landlord matches the glob.
+    assert!(add("external-account-1", "*INC", NameMatchKind::Glob).await.is_err());
+
+    assert!(add("external-account-1", "(", NameMatchKind::Regex).await.is_err());
+    assert!(add("external-account-1", "", NameMatchKind::Prefix).await.is_err());
+
+    // Patterns of the same external account may overlap.
+    let name = add("external-account-3", "evil_*", NameMatchKind::Glob)
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(name.match_kind, NameMatchKind::Glob);
+
+    add("external-account-1", "jumbo", NameMatchKind::Prefix).await.unwrap();
+
+    let names = get_external_account_names(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(names[0].match_kind, NameMatchKind::Prefix);
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "transactions"))]
+async fn name_pattern_can_be_applied_to_existing_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let name = add_external_account_name(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        Json(NewExternalAccountNameDto { name: "JUM", match_kind: NameMatchKind::Prefix }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    apply_external_account_name(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        name.id,
+    )
+        .await
+        .unwrap();
+
+    let transaction = get_single_transaction(app.pool_state(), app.alice(), "transaction-2")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(transaction.external_account.unwrap().id, "external-account-1");
+
+    let transaction = get_single_transaction(app.pool_state(), app.alice(), "transaction-3")
+        .await
+        .unwrap()
+        .0;
+
+    assert!(transaction.external_account.is_none());
+
+    // The transactions of bob are left alone.
+    let transaction = get_single_transaction(app.pool_state(), app.bob(), "transaction-5")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(transaction.external_account.unwrap().id, "transaction-external-account-2");
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "transactions", "external-account-ibans"))]
+async fn applying_a_name_pattern_keeps_transactions_linked_by_iban(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    sqlx::query!("DELETE FROM external_account_names WHERE id = 'external-account-name-1'")
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    apply_external_account_iban(
+        app.pool_state(),
+        app.alice(),
+        "external-account-3".to_string(),
+        "external-account-iban-1".to_string()
+    )
+        .await
+        .unwrap();
+
+    let name = add_external_account_name(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        Json(NewExternalAccountNameDto { name: ".*", match_kind: NameMatchKind::Regex }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    apply_external_account_name(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        name.id,
+    )
+        .await
+        .unwrap();
+
+    let transaction = get_single_transaction(app.pool_state(), app.alice(), "transaction-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(transaction.external_account.unwrap().id, "external-account-1");
+
+    let transaction = get_single_transaction(app.pool_state(), app.alice(), "transaction-3")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(transaction.external_account.unwrap().id, "external-account-3");
+}
+
+#[sqlx::test(fixtures("users", "external-accounts", "transactions"))]
+async fn imported_transactions_are_matched_on_name_patterns(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let name = add_external_account_name(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        Json(NewExternalAccountNameDto { name: "AH to go", match_kind: NameMatchKind::Prefix }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    let csv = "Date,Account,Reference,Name,Description,Amount
+2023-03-01,NL12 RABO 12345678910,pattern-1,AH TO GO 1234,Lunch,\"-5,00\"
+2023-03-02,NL12 RABO 12345678910,pattern-2,AH to go 5678 Utrecht,Lunch,\"-6,00\"
+2023-03-03,NL12 RABO 12345678910,pattern-3,EVIL_LAND_LORD_INC,Rent,\"-920,00\"
+2023-03-04,NL12 RABO 12345678910,pattern-4,Bakery AH to go,Bread,\"-3,00\"";
+
+    let body = ImportCsvDto {
+        mappings: Some(csv_mapping()),
+        profile_id: None,
+        filename: "export.csv".to_string(),
+        csv: csv.to_string(),
+    };
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let matched: Vec<Option<String>> = sqlx::query_scalar!(
+        r#"
+            SELECT external_account_id
+            FROM transactions
+            WHERE follow_number LIKE 'pattern-%'
+            ORDER BY follow_number;
+        "#
+    )
+        .fetch_all(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    assert_eq!(matched, vec![
+        Some("external-account-1".to_string()),
+        Some("external-account-1".to_string()),
+        Some("external-account-3".to_string()),
+        None,
+    ]);
+
+    // The transactions keep their external account when the name is deleted.
+    delete_external_account_name(
+        app.pool_state(),
+        app.alice(),
+        "external-account-1".to_string(),
+        name.id,
+    )
+        .await
+        .unwrap();
+
+    let linked = sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*) AS "count!"
+            FROM transactions
+            WHERE follow_number LIKE 'pattern-%' AND external_account_id = 'external-account-1';
+        "#
+    )
+        .fetch_one(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    assert_eq!(linked, 2);
+}
@@ -0,0 +1,25 @@
+INSERT INTO imports
+VALUES
+    ('aggregate-import-1', 'abc', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('aggregate-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO categories
+VALUES
+    ('aggregate-category-1', 'abc', 'Groceries', 'For all the food', '303030', 1),
+    ('aggregate-category-2', 'abc', 'Rent', 'For all my rent', '303030', 2);
+
+INSERT INTO transactions
+VALUES
+    ('aggregate-transaction-1', 'abc', 'transaction', 'aggregate-00000001', 'Groceries', 'Groceries', -10000, -7000, '2023-01-10 11:00:00.000000 +00:00', 'aggregate-category-1', null, 'Jumbo', null, 'aggregate-bank-account-1', 'aggregate-import-1', null, 1),
+    ('aggregate-transaction-2', 'abc', 'transaction', 'aggregate-00000002', 'Salary', 'Salary', 300000, 300000, '2023-01-25 11:00:00.000000 +00:00', null, null, 'Work', null, 'aggregate-bank-account-1', 'aggregate-import-1', null, 2),
+    ('aggregate-transaction-3', 'abc', 'transaction', 'aggregate-00000003', 'Rent', 'Rent', -50000, -50000, '2023-02-01 11:00:00.000000 +00:00', 'aggregate-category-2', null, 'Landlord', null, 'aggregate-bank-account-1', 'aggregate-import-1', null, 3),
+    ('aggregate-split-1', 'abc', 'split', 'aggregate-00000004', 'Rent share', 'Rent share', -3000, -3000, '2023-01-10 11:00:00.000000 +00:00', 'aggregate-category-2', 'aggregate-transaction-1', 'Jumbo', null, 'aggregate-bank-account-1', null, null, 4),
+    ('aggregate-correction-1', 'abc', 'correction', 'aggregate-00000005', 'Correction', 'Correction', 1500, 1500, '2023-02-15 11:00:00.000000 +00:00', null, null, 'Correction', null, 'aggregate-bank-account-1', null, null, 5);
+
+INSERT INTO transactions
+VALUES
+    ('aggregate-move-1', 'abc', 'move', 'aggregate-00000006', '', '', -2000, -2000, '2023-02-20 11:00:00.000000 +00:00', 'aggregate-category-1', null, '', null, null, null, null, 6, 'aggregate-move-2'),
+    ('aggregate-move-2', 'abc', 'move', 'aggregate-00000007', '', '', 2000, 2000, '2023-02-20 11:00:00.000000 +00:00', 'aggregate-category-2', null, '', null, null, null, null, 7, 'aggregate-move-1');
@@ -0,0 +1,17 @@
+INSERT INTO imports
+VALUES
+    ('balance-import-1', 'abc', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('balance-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030'),
+    ('balance-bank-account-2', 'NL99 RABO 01987654321', 'def', 'Primary bank account', 'For all of the normal stuff', '3030ff');
+
+INSERT INTO transactions
+VALUES
+    ('balance-correction-1', 'abc', 'correction', 'balance-00000001', 'Starting balance', 'Starting balance', 100000, 100000, '2023-01-01 00:00:00.000000 +00:00', null, null, 'Correction', null, 'balance-bank-account-1', null, null, 1),
+    ('balance-transaction-1', 'abc', 'transaction', 'balance-00000002', 'Groceries', 'Groceries', -10000, -7000, '2023-01-10 11:00:00.000000 +00:00', null, null, 'Jumbo', null, 'balance-bank-account-1', 'balance-import-1', null, 2),
+    ('balance-split-1', 'abc', 'split', 'balance-00000003', 'Groceries share', 'Groceries share', -3000, -3000, '2023-01-10 11:00:00.000000 +00:00', null, 'balance-transaction-1', 'Jumbo', null, 'balance-bank-account-1', null, null, 3),
+    ('balance-transaction-2', 'abc', 'transaction', 'balance-00000004', 'Salary', 'Salary', 300000, 300000, '2023-01-10 11:00:00.000000 +00:00', null, null, 'Work', null, 'balance-bank-account-1', 'balance-import-1', null, 4),
+    ('balance-transaction-3', 'abc', 'transaction', 'balance-00000005', 'Rent', 'Rent', -50000, -50000, '2023-03-01 11:00:00.000000 +00:00', null, null, 'Landlord', null, 'balance-bank-account-1', 'balance-import-1', null, 5),
+    ('balance-transaction-4', 'def', 'transaction', 'balance-00000006', 'Salary', 'Salary', 200000, 200000, '2023-01-10 11:00:00.000000 +00:00', null, null, 'Work', null, 'balance-bank-account-2', null, null, 6);
@@ -0,0 +1,31 @@
+INSERT INTO imports
+VALUES
+    ('budget-import-1', 'abc', '2023-04-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('budget-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO categories
+VALUES
+    ('budget-category-1', 'abc', 'Groceries', 'For all the food', '303030', 1),
+    ('budget-category-2', 'abc', 'Rent', 'For all my rent', '303030', 2),
+    ('budget-category-3', 'def', 'Groceries', 'For all the food', '303030', 3);
+
+INSERT INTO subcategories
+VALUES
+    ('budget-subcategory-1', 'abc', 'budget-category-1', 'Supermarket', 'Test subcategory', '030303');
+
+INSERT INTO transactions
+VALUES
+    ('budget-transaction-1', 'abc', 'transaction', 'budget-00000001', 'Groceries', 'Groceries', -2500, -2500, '2023-01-10 11:00:00.000000 +00:00', 'budget-category-1', null, 'Jumbo', null, 'budget-bank-account-1', 'budget-import-1', null, 1),
+    ('budget-transaction-2', 'abc', 'transaction', 'budget-00000002', 'Groceries', 'Groceries', -15000, -15000, '2023-02-05 11:00:00.000000 +00:00', 'budget-category-1', null, 'Jumbo', null, 'budget-bank-account-1', 'budget-import-1', 'budget-subcategory-1', 2),
+    ('budget-transaction-3', 'abc', 'transaction', 'budget-00000003', 'Groceries', 'Groceries', -4000, -4000, '2023-03-03 11:00:00.000000 +00:00', 'budget-category-1', null, 'Jumbo', null, 'budget-bank-account-1', 'budget-import-1', null, 3),
+    ('budget-transaction-4', 'abc', 'transaction', 'budget-00000004', 'Refund', 'Refund', 1000, 1000, '2023-03-20 11:00:00.000000 +00:00', 'budget-category-1', null, 'Jumbo', null, 'budget-bank-account-1', 'budget-import-1', null, 4),
+    ('budget-transaction-5', 'abc', 'transaction', 'budget-00000005', 'Rent', 'Rent', -92000, -92000, '2023-03-01 11:00:00.000000 +00:00', 'budget-category-2', null, 'Landlord', null, 'budget-bank-account-1', 'budget-import-1', null, 5);
+
+INSERT INTO budgets
+VALUES
+    ('budget-1', 'abc', 'budget-category-1', null, 10000, 'month', true, '2023-01-01 00:00:00.000000 +00:00'),
+    ('budget-2', 'abc', 'budget-category-1', 'budget-subcategory-1', 5000, 'month', false, '2023-01-01 00:00:00.000000 +00:00'),
+    ('budget-3', 'def', 'budget-category-3', null, 10000, 'week', false, '2023-01-01 00:00:00.000000 +00:00');
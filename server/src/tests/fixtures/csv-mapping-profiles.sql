@@ -0,0 +1,4 @@
+INSERT INTO csv_mapping_profiles
+VALUES
+    ('profile-1', 'abc', 'Rabobank', 'iban,date,number,description,amount,name', '$ 00:00:00 +0000', '%Y-%m-%d %H:%M:%S %z', 'euro', 'newestLast', '{"accountIban": 0, "date": 1, "followNumber": 2, "description": 3, "amount": 4, "externalAccountName": 5}'),
+    ('profile-2', 'def', 'ING', null, null, '%Y%m%d', 'cents', 'newestFirst', '{"accountIban": 1, "date": 0, "followNumber": 2, "description": 3, "amount": 4, "externalAccountName": 5}');
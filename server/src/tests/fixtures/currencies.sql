@@ -0,0 +1,36 @@
+INSERT INTO imports
+VALUES
+    ('currency-import-1', 'abc', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('currency-bank-account-eur', 'NL12 RABO 12345678910', 'abc', 'Euro account', 'For all of the normal stuff', 'ff3030', 'EUR'),
+    ('currency-bank-account-usd', 'US12 3456 7891 0111 2131', 'abc', 'Dollar account', 'For the trips', 'ff3030', 'USD'),
+    ('currency-bank-account-jpy', 'JP12 3456 7891 0111 2131', 'abc', 'Yen account', 'For the trips', 'ff3030', 'JPY'),
+    ('currency-bank-account-gbp', 'GB12 3456 7891 0111 2131', 'abc', 'Pound account', 'For the trips', 'ff3030', 'GBP');
+
+INSERT INTO categories
+VALUES
+    ('currency-category-1', 'abc', 'Groceries', 'For all the food', '303030', 1);
+
+INSERT INTO subcategories
+VALUES
+    ('currency-subcategory-1', 'abc', 'currency-category-1', 'Supermarket', 'Test subcategory', '030303');
+
+INSERT INTO transactions
+VALUES
+    ('currency-transaction-1', 'abc', 'transaction', 'currency-00000001', 'Groceries', 'Groceries', -10000, -10000, '2023-01-10 11:00:00.000000 +00:00', 'currency-category-1', null, 'Jumbo', null, 'currency-bank-account-eur', 'currency-import-1', 'currency-subcategory-1', 1, null, null, 'EUR'),
+    ('currency-transaction-2', 'abc', 'transaction', 'currency-00000002', 'Groceries', 'Groceries', -10870, -10870, '2023-01-10 11:00:00.000000 +00:00', 'currency-category-1', null, 'Walmart', null, 'currency-bank-account-usd', 'currency-import-1', 'currency-subcategory-1', 2, null, null, 'USD'),
+    ('currency-transaction-3', 'abc', 'transaction', 'currency-00000003', 'Refund', 'Refund', 14179, 14179, '2023-01-12 11:00:00.000000 +00:00', null, null, 'Hotel', null, 'currency-bank-account-jpy', 'currency-import-1', null, 3, null, null, 'JPY'),
+    ('currency-transaction-4', 'abc', 'transaction', 'currency-00000004', 'Tea', 'Tea', -5000, -5000, '2023-01-12 11:00:00.000000 +00:00', 'currency-category-1', null, 'Shop', null, 'currency-bank-account-gbp', 'currency-import-1', null, 4, null, null, 'GBP');
+
+INSERT INTO exchange_rates
+VALUES
+    ('abc', 'USD', '2023-01-09', 1.0741),
+    ('abc', 'USD', '2023-01-10', 1.087),
+    ('abc', 'JPY', '2023-01-11', 141.79);
+
+INSERT INTO budgets
+VALUES
+    ('currency-budget-1', 'abc', 'currency-category-1', null, 30000, 'month', false, '2023-01-01 00:00:00.000000 +00:00'),
+    ('currency-budget-2', 'abc', 'currency-category-1', 'currency-subcategory-1', 25000, 'month', false, '2023-01-01 00:00:00.000000 +00:00');
@@ -0,0 +1,7 @@
+INSERT INTO external_account_ibans
+VALUES
+    ('external-account-iban-1', 'abc', 'NL66INGB0006666666', 'external-account-3');
+
+UPDATE transactions
+SET external_account_iban = 'NL66INGB0006666666'
+WHERE id = 'transaction-3';
@@ -0,0 +1,18 @@
+INSERT INTO imports
+VALUES
+    ('suggestion-import-1', 'abc', '2023-02-11 11:00:00.000000 +00:00', 'bank-export.csv'),
+    ('suggestion-import-2', 'def', '2023-02-11 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('suggestion-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030'),
+    ('suggestion-bank-account-2', 'NL99 RABO 01987654321', 'def', 'Primary bank account', 'For all of the normal stuff', '3030ff');
+
+INSERT INTO transactions
+VALUES
+    ('suggestion-transaction-1', 'abc', 'transaction', 'suggestion-00000001', 'Groceries', 'Albert Heijn', -1250, -1250, '2023-02-11 11:00:00.000000 +00:00', null, null, 'ALBERT HEIJN 1234', null, 'suggestion-bank-account-1', 'suggestion-import-1', null, 1),
+    ('suggestion-transaction-2', 'abc', 'transaction', 'suggestion-00000002', 'Groceries', 'Albert Heijn', -2500, -2500, '2023-02-12 11:00:00.000000 +00:00', null, null, 'ALBERT HEIJN 1234', null, 'suggestion-bank-account-1', 'suggestion-import-1', null, 2),
+    ('suggestion-transaction-3', 'abc', 'transaction', 'suggestion-00000003', 'Groceries', 'Albert Heijn', -750, -750, '2023-02-13 11:00:00.000000 +00:00', null, null, 'Albert Heijn 5678', null, 'suggestion-bank-account-1', 'suggestion-import-1', null, 3),
+    ('suggestion-transaction-4', 'abc', 'transaction', 'suggestion-00000004', 'Lunch', 'Albert Heijn', -500, -500, '2023-02-14 11:00:00.000000 +00:00', null, null, 'Albert Heijn Utrecht', null, 'suggestion-bank-account-1', 'suggestion-import-1', null, 4),
+    ('suggestion-transaction-5', 'abc', 'transaction', 'suggestion-00000005', 'Bread', 'Bakery', -300, -300, '2023-02-15 11:00:00.000000 +00:00', null, null, 'De Bakker', null, 'suggestion-bank-account-1', 'suggestion-import-1', null, 5),
+    ('suggestion-transaction-6', 'def', 'transaction', 'suggestion-00000006', 'Groceries', 'Albert Heijn', -1250, -1250, '2023-02-11 11:00:00.000000 +00:00', null, null, 'ALBERT HEIJN 1234', null, 'suggestion-bank-account-2', 'suggestion-import-2', null, 1);
@@ -0,0 +1,55 @@
+INSERT INTO imports
+VALUES
+    ('merge-import-1', 'abc', '2023-02-11 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('merge-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO categories
+VALUES
+    ('merge-category-1', 'abc', 'Food', 'For all the food', '303030', 1),
+    ('merge-category-2', 'abc', 'Groceries', 'For all the groceries', '303030', 2),
+    ('merge-category-3', 'def', 'Groceries', 'For all the groceries', '303030', 3);
+
+INSERT INTO subcategories
+VALUES
+    ('merge-subcategory-1', 'abc', 'merge-category-1', 'Supermarket', 'Test subcategory', '030303'),
+    ('merge-subcategory-2', 'abc', 'merge-category-2', 'Bakery', 'Test subcategory', '030303'),
+    ('merge-subcategory-3', 'abc', 'merge-category-2', 'Market', 'Test subcategory', '030303'),
+    ('merge-subcategory-4', 'def', 'merge-category-3', 'Market', 'Test subcategory', '030303');
+
+INSERT INTO external_accounts
+VALUES
+    ('merge-external-account-1', 'abc', 'AH', 'Supermarket', 'merge-category-1', 'merge-subcategory-1', null, '303030'),
+    ('merge-external-account-2', 'abc', 'Albert Heijn', 'Supermarket', null, null, null, '303030'),
+    ('merge-external-account-3', 'def', 'Albert Heijn', 'Supermarket', null, null, null, '303030');
+
+INSERT INTO external_account_names
+VALUES
+    ('merge-name-1', 'abc', 'AH 1234', 'merge-external-account-1'),
+    ('merge-name-2', 'abc', 'Albert Heijn', 'merge-external-account-2');
+
+INSERT INTO external_account_ibans
+VALUES
+    ('merge-iban-1', 'abc', 'NL11INGB0001111111', 'merge-external-account-1');
+
+INSERT INTO transactions
+VALUES
+    ('merge-transaction-1', 'abc', 'transaction', 'merge-00000001', 'Groceries', 'Groceries', -2500, -2500, '2023-02-11 11:00:00.000000 +00:00', 'merge-category-1', null, 'AH 1234', 'merge-external-account-1', 'merge-bank-account-1', 'merge-import-1', 'merge-subcategory-1', 1, 'merge-name-1'),
+    ('merge-transaction-2', 'abc', 'transaction', 'merge-00000002', 'Groceries', 'Groceries', -1500, -1500, '2023-02-12 11:00:00.000000 +00:00', 'merge-category-2', null, 'Albert Heijn', 'merge-external-account-2', 'merge-bank-account-1', 'merge-import-1', 'merge-subcategory-2', 2, 'merge-name-2'),
+    ('merge-transaction-3', 'abc', 'transaction', 'merge-00000003', 'Lunch', 'Lunch', -750, -750, '2023-02-13 11:00:00.000000 +00:00', 'merge-category-1', null, 'Bakery', null, 'merge-bank-account-1', 'merge-import-1', null, 3, null);
+
+INSERT INTO budgets
+VALUES
+    ('merge-budget-1', 'abc', 'merge-category-1', null, 10000, 'month', false, '2023-01-01 00:00:00.000000 +00:00'),
+    ('merge-budget-2', 'abc', 'merge-category-2', null, 20000, 'month', false, '2023-01-01 00:00:00.000000 +00:00'),
+    ('merge-budget-3', 'abc', 'merge-category-1', 'merge-subcategory-1', 5000, 'month', false, '2023-01-01 00:00:00.000000 +00:00');
+
+INSERT INTO rules
+VALUES
+    ('merge-rule-1', 'abc', 'Supermarket', 1, 'ah', null, null, null, null, null, null, 'merge-category-1', 'merge-subcategory-1', null, 'merge-external-account-1');
+
+INSERT INTO planned_transactions
+VALUES
+    ('merge-planned-1', 'abc', 'merge-bank-account-1', 'merge-category-1', 'merge-subcategory-1', 'merge-external-account-1', 'Groceries', -10000, '2023-03-01 00:00:00.000000 +00:00', 'month', null, false);
@@ -0,0 +1,32 @@
+INSERT INTO imports
+VALUES
+    ('planned-import-1', 'def', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('planned-bank-account-1', 'NL12INGB0001234567', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030'),
+    ('planned-bank-account-2', 'NL12INGB0001234567', 'def', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO categories
+VALUES
+    ('planned-category-1', 'abc', 'Rent', 'For all my rent', '303030', 1);
+
+INSERT INTO external_accounts
+VALUES
+    ('planned-external-account-1', 'abc', 'Landlord', 'For the rent', null, null);
+
+INSERT INTO external_account_names
+VALUES
+    ('planned-external-account-name-1', 'abc', 'Landlord BV', 'planned-external-account-1');
+
+INSERT INTO transactions
+VALUES
+    ('planned-transaction-1', 'def', 'transaction', 'planned-00000001', 'Savings', 'Savings', 200000, 200000, date_trunc('day', now()) - interval '10 days', null, null, 'Savings', null, 'planned-bank-account-2', 'planned-import-1', null, 1);
+
+-- The planned transactions of bob are relative to now, as the forecast always starts today.
+INSERT INTO planned_transactions
+VALUES
+    ('planned-1', 'abc', 'planned-bank-account-1', 'planned-category-1', null, 'planned-external-account-1', 'Rent', -95000, '2023-03-01 00:00:00.000000 +00:00', 'month', null, false),
+    ('planned-2', 'def', 'planned-bank-account-2', null, null, null, 'Insurance', -30000, date_trunc('day', now()) - interval '2 days', null, null, false),
+    ('planned-3', 'def', 'planned-bank-account-2', null, null, null, 'Salary', 250000, date_trunc('day', now()) + interval '5 days', 'month', date_trunc('day', now()) + interval '40 days', false),
+    ('planned-4', 'def', 'planned-bank-account-2', null, null, null, 'Car', -99999, date_trunc('day', now()) + interval '5 days', null, null, true);
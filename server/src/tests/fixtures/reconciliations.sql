@@ -0,0 +1,19 @@
+INSERT INTO imports
+VALUES
+    ('reconciliation-import-1', 'abc', '2023-02-01 11:00:00.000000 +00:00', 'bank-export.csv'),
+    ('reconciliation-import-2', 'abc', '2023-03-02 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('reconciliation-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO transactions
+VALUES
+    ('reconciliation-transaction-1', 'abc', 'transaction', 'reconciliation-00000001', 'Groceries', 'Groceries', -10000, -10000, '2023-01-10 11:00:00.000000 +00:00', null, null, 'Jumbo', null, 'reconciliation-bank-account-1', 'reconciliation-import-1', null, 1),
+    ('reconciliation-transaction-2', 'abc', 'transaction', 'reconciliation-00000002', 'Salary', 'Salary', 300000, 300000, '2023-01-20 11:00:00.000000 +00:00', null, null, 'Work', null, 'reconciliation-bank-account-1', 'reconciliation-import-1', null, 2),
+    ('reconciliation-correction-1', 'abc', 'correction', 'reconciliation-00000003', 'Correction', 'Correction', 5000, 5000, '2023-01-25 11:00:00.000000 +00:00', null, null, 'Correction', null, 'reconciliation-bank-account-1', null, null, 3),
+    ('reconciliation-transaction-3', 'abc', 'transaction', 'reconciliation-00000004', 'Rent', 'Rent', -50000, -50000, '2023-03-01 11:00:00.000000 +00:00', null, null, 'Landlord', null, 'reconciliation-bank-account-1', 'reconciliation-import-2', null, 4);
+
+INSERT INTO reconciliations
+VALUES
+    ('reconciliation-1', 'abc', 'reconciliation-bank-account-1', '2023-01-31 00:00:00.000000 +00:00', 300000);
@@ -0,0 +1,35 @@
+INSERT INTO imports
+VALUES
+    ('recurring-import-1', 'abc', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv'),
+    ('recurring-import-2', 'def', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('recurring-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030'),
+    ('recurring-bank-account-2', 'NL12 RABO 12345678910', 'def', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO external_accounts
+VALUES
+    ('recurring-external-account-1', 'abc', 'Landlord', 'For the rent', null, null);
+
+-- The dates are relative to now, as series that ended a while ago are not detected.
+INSERT INTO transactions
+VALUES
+    ('recurring-transaction-1', 'abc', 'transaction', 'recurring-00000001', 'Netflix', 'Netflix', -1299, -1299, date_trunc('day', now()) - interval '3 months', null, null, 'NETFLIX.COM', null, 'recurring-bank-account-1', 'recurring-import-1', null, 1),
+    ('recurring-transaction-2', 'abc', 'transaction', 'recurring-00000002', 'Netflix', 'Netflix', -1299, -1299, date_trunc('day', now()) - interval '2 months', null, null, 'Netflix.com ', null, 'recurring-bank-account-1', 'recurring-import-1', null, 2),
+    ('recurring-transaction-3', 'abc', 'transaction', 'recurring-00000003', 'Netflix', 'Netflix', -1499, -1499, date_trunc('day', now()) - interval '1 month', null, null, 'NETFLIX.COM', null, 'recurring-bank-account-1', 'recurring-import-1', null, 3),
+    ('recurring-transaction-4', 'abc', 'transaction', 'recurring-00000004', 'Rent', 'Rent', -95000, -95000, date_trunc('day', now()) - interval '3 months' + interval '1 day', null, null, 'LANDLORD BV', 'recurring-external-account-1', 'recurring-bank-account-1', 'recurring-import-1', null, 4),
+    ('recurring-transaction-5', 'abc', 'transaction', 'recurring-00000005', 'Rent', 'Rent', -95000, -95000, date_trunc('day', now()) - interval '2 months' + interval '1 day', null, null, 'Landlord', 'recurring-external-account-1', 'recurring-bank-account-1', 'recurring-import-1', null, 5),
+    ('recurring-transaction-6', 'abc', 'transaction', 'recurring-00000006', 'Rent', 'Rent', -95000, -95000, date_trunc('day', now()) - interval '1 month' + interval '1 day', null, null, 'Landlord', 'recurring-external-account-1', 'recurring-bank-account-1', 'recurring-import-1', null, 6),
+    ('recurring-transaction-7', 'abc', 'transaction', 'recurring-00000007', 'Groceries', 'Groceries', -2000, -2000, date_trunc('day', now()) - interval '40 days', null, null, 'Jumbo', null, 'recurring-bank-account-1', 'recurring-import-1', null, 7),
+    ('recurring-transaction-8', 'abc', 'transaction', 'recurring-00000008', 'Groceries', 'Groceries', -2100, -2100, date_trunc('day', now()) - interval '20 days', null, null, 'Jumbo', null, 'recurring-bank-account-1', 'recurring-import-1', null, 8),
+    ('recurring-transaction-9', 'abc', 'transaction', 'recurring-00000009', 'Groceries', 'Groceries', -1900, -1900, date_trunc('day', now()) - interval '3 days', null, null, 'Jumbo', null, 'recurring-bank-account-1', 'recurring-import-1', null, 9),
+    ('recurring-transaction-10', 'def', 'transaction', 'recurring-00000010', 'Netflix', 'Netflix', -1299, -1299, date_trunc('day', now()) - interval '3 months', null, null, 'NETFLIX.COM', null, 'recurring-bank-account-2', 'recurring-import-2', null, 10),
+    ('recurring-transaction-11', 'def', 'transaction', 'recurring-00000011', 'Netflix', 'Netflix', -1299, -1299, date_trunc('day', now()) - interval '2 months', null, null, 'NETFLIX.COM', null, 'recurring-bank-account-2', 'recurring-import-2', null, 11),
+    ('recurring-transaction-12', 'def', 'transaction', 'recurring-00000012', 'Netflix', 'Netflix', -1299, -1299, date_trunc('day', now()) - interval '1 month', null, null, 'NETFLIX.COM', null, 'recurring-bank-account-2', 'recurring-import-2', null, 12);
+
+-- Series that are not detected anymore. Only the one the user confirmed should be kept.
+INSERT INTO recurring_series
+VALUES
+    ('recurring-series-1', 'abc', 'gym', null, 'Gym', 'month', -2500, 'EUR', 6, '2022-06-01 00:00:00.000000 +00:00', '2022-07-01 00:00:00.000000 +00:00', 'detected'),
+    ('recurring-series-2', 'abc', 'insurance', null, 'Insurance', 'year', -30000, 'EUR', 2, '2022-06-01 00:00:00.000000 +00:00', '2023-06-01 00:00:00.000000 +00:00', 'confirmed');
@@ -0,0 +1,38 @@
+INSERT INTO bank_accounts
+VALUES
+    ('rule-bank-account-1', 'NL12 RABO 12345678910', 'abc', 'Primary bank account', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO categories
+VALUES
+    ('rule-category-1', 'abc', 'Groceries', 'For all the food', '303030', 1),
+    ('rule-category-2', 'abc', 'Rent', 'For all my rent', '303030', 2),
+    ('rule-category-3', 'def', 'Groceries', 'For all the food', '303030', 3);
+
+INSERT INTO subcategories
+VALUES
+    ('rule-subcategory-1', 'abc', 'rule-category-1', 'Supermarket', 'Test subcategory', '030303');
+
+INSERT INTO external_accounts
+VALUES
+    ('rule-external-account-1', 'abc', 'Jumbo', 'The price it quite high', null, null, null, '303030'),
+    ('rule-external-account-2', 'abc', 'Jumbo City', 'Another supermarket', null, null, null, '303030');
+
+INSERT INTO external_account_names
+VALUES
+    ('rule-external-account-name-1', 'abc', 'Jumbo', 'rule-external-account-2');
+
+INSERT INTO imports
+VALUES
+    ('rule-import-1', 'abc', '2023-02-11 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO transactions
+VALUES
+    ('rule-transaction-1', 'abc', 'transaction', 'rule-00000001', 'Payment for Jumbo', 'Payment for Jumbo', -9300, -9300, '2023-02-11 11:00:00.000000 +00:00', null, null, 'Jumbo', null, 'rule-bank-account-1', 'rule-import-1', null, 1),
+    ('rule-transaction-2', 'abc', 'transaction', 'rule-00000002', 'Rent', 'RENT 2023', -92000, -92000, '2023-03-01 11:00:00.000000 +00:00', 'rule-category-1', null, 'EVIL_LAND_LORD_INC', null, 'rule-bank-account-1', 'rule-import-1', null, 2),
+    ('rule-transaction-3', 'abc', 'transaction', 'rule-00000003', 'Salary', 'Salary', 256700, 256700, '2023-03-01 11:00:00.000000 +00:00', null, null, 'Name of work', null, 'rule-bank-account-1', 'rule-import-1', null, 3);
+
+INSERT INTO rules
+VALUES
+    ('rule-1', 'abc', 'Groceries', 1, 'jumbo', null, null, null, null, null, null, 'rule-category-1', 'rule-subcategory-1', 'Groceries', 'rule-external-account-1'),
+    ('rule-2', 'abc', 'Rent', 2, null, '^RENT \d{4}$', -100000, -50000, null, null, null, 'rule-category-2', null, null, null),
+    ('rule-3', 'def', 'Groceries', 1, 'jumbo', null, null, null, null, null, null, 'rule-category-3', null, null, null);
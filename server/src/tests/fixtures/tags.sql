@@ -0,0 +1,13 @@
+INSERT INTO tags
+VALUES
+    ('tag-1', 'abc', 'Shopping', 'ff3030'),
+    ('tag-2', 'abc', 'Work', '303030'),
+    ('tag-3', 'abc', 'Holiday', '3030ff'),
+    ('tag-4', 'def', 'Shopping', 'ff3030');
+
+INSERT INTO transaction_tags
+VALUES
+    ('aggregate-transaction-1', 'tag-1', 'abc'),
+    ('aggregate-split-1', 'tag-1', 'abc'),
+    ('aggregate-transaction-2', 'tag-2', 'abc'),
+    ('aggregate-move-1', 'tag-2', 'abc');
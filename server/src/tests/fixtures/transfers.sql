@@ -0,0 +1,19 @@
+INSERT INTO imports
+VALUES
+    ('transfer-import-1', 'abc', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv'),
+    ('transfer-import-2', 'def', '2023-03-01 11:00:00.000000 +00:00', 'bank-export.csv');
+
+INSERT INTO bank_accounts
+VALUES
+    ('transfer-bank-account-1', 'NL11INGB0001111111', 'abc', 'Checking', 'For all of the normal stuff', 'ff3030'),
+    ('transfer-bank-account-2', 'NL22INGB0002222222', 'abc', 'Savings', 'For later', 'ff3030'),
+    ('transfer-bank-account-3', 'NL33INGB0003333333', 'def', 'Checking', 'For all of the normal stuff', 'ff3030');
+
+INSERT INTO transactions
+VALUES
+    ('transfer-transaction-1', 'abc', 'transaction', 'transfer-00000001', 'From checking', 'From checking', 50000, 50000, '2023-03-02 11:00:00.000000 +00:00', null, null, 'Checking', null, 'transfer-bank-account-2', 'transfer-import-1', null, 1),
+    ('transfer-transaction-2', 'abc', 'transaction', 'transfer-00000002', 'From checking', 'From checking', 7500, 7500, '2023-03-30 11:00:00.000000 +00:00', null, null, 'Checking', null, 'transfer-bank-account-2', 'transfer-import-1', null, 2),
+    ('transfer-transaction-3', 'abc', 'transaction', 'transfer-00000003', 'To savings', 'To savings', -20000, -20000, '2023-04-01 11:00:00.000000 +00:00', null, null, 'Savings', null, 'transfer-bank-account-1', 'transfer-import-1', null, 3),
+    ('transfer-transaction-4', 'abc', 'transaction', 'transfer-00000004', 'From checking', 'From checking', 20000, 20000, '2023-04-01 11:00:00.000000 +00:00', null, null, 'Checking', null, 'transfer-bank-account-2', 'transfer-import-1', null, 4),
+    ('transfer-transaction-5', 'abc', 'transaction', 'transfer-00000005', 'Salary', 'Salary', 300000, 300000, '2023-04-25 11:00:00.000000 +00:00', null, null, 'Work', null, 'transfer-bank-account-1', 'transfer-import-1', null, 5),
+    ('transfer-transaction-6', 'def', 'transaction', 'transfer-00000006', 'To savings', 'To savings', -20000, -20000, '2023-04-01 11:00:00.000000 +00:00', null, null, 'Savings', null, 'transfer-bank-account-3', 'transfer-import-2', null, 6);
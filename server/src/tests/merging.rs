@@ -0,0 +1,279 @@
+use sqlx::PgPool;
+
+use crate::routes::categories::get_category_by_id;
+use crate::routes::categories::merging::{merge_category, merge_subcategory};
+use crate::routes::external_accounts::{get_external_account_by_id, get_external_account_ibans, get_external_account_names, merge_external_account};
+use crate::tests::common::TestApp;
+
+#[sqlx::test(fixtures("users", "merging"))]
+async fn external_accounts_can_be_merged(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    merge_external_account(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-1".to_string(),
+        "merge-external-account-2".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let result = get_external_account_by_id(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-1".to_string(),
+    )
+    .await;
+
+    assert!(result.is_err());
+
+    let target = get_external_account_by_id(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-2".to_string(),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(target.name, "Albert Heijn");
+    assert_eq!(target.default_category_id, Some("merge-category-1".to_string()));
+    assert_eq!(target.default_subcategory_id, Some("merge-subcategory-1".to_string()));
+
+    let names = get_external_account_names(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-2".to_string(),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(names.len(), 2);
+
+    let ibans = get_external_account_ibans(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-2".to_string(),
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert_eq!(ibans.len(), 1);
+
+    let pool = app.pool_state().inner().read().await;
+
+    let transaction = sqlx::query!(
+        "SELECT external_account_id, external_account_name_id FROM transactions WHERE id = 'merge-transaction-1'"
+    )
+    .fetch_one(&*pool)
+    .await
+    .unwrap();
+
+    assert_eq!(transaction.external_account_id, Some("merge-external-account-2".to_string()));
+    assert_eq!(transaction.external_account_name_id, Some("merge-name-1".to_string()));
+
+    let rule = sqlx::query_scalar!("SELECT set_external_account_id FROM rules WHERE id = 'merge-rule-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(rule, Some("merge-external-account-2".to_string()));
+
+    let planned = sqlx::query_scalar!("SELECT external_account_id FROM planned_transactions WHERE id = 'merge-planned-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(planned, Some("merge-external-account-2".to_string()));
+}
+
+#[sqlx::test(fixtures("users", "merging"))]
+async fn an_external_account_cannot_be_merged_into_itself_or_another_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = merge_external_account(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-1".to_string(),
+        "merge-external-account-1".to_string(),
+    )
+    .await;
+
+    assert!(result.is_err());
+
+    let result = merge_external_account(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-1".to_string(),
+        "merge-external-account-3".to_string(),
+    )
+    .await;
+
+    assert!(result.is_err());
+
+    get_external_account_by_id(
+        app.pool_state(),
+        app.alice(),
+        "merge-external-account-1".to_string(),
+    )
+    .await
+    .unwrap();
+}
+
+#[sqlx::test(fixtures("users", "merging"))]
+async fn categories_can_be_merged(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    merge_category(app.pool_state(), app.alice(), "merge-category-1", "merge-category-2")
+        .await
+        .unwrap();
+
+    let result = get_category_by_id(app.pool_state(), app.alice(), "merge-category-1").await;
+    assert!(result.is_err());
+
+    let pool = app.pool_state().inner().read().await;
+
+    let transactions = sqlx::query!(
+        "SELECT id, category_id, subcategory_id FROM transactions WHERE user_id = 'abc' ORDER BY id"
+    )
+    .fetch_all(&*pool)
+    .await
+    .unwrap();
+
+    assert_eq!(transactions.len(), 3);
+    assert!(transactions.iter().all(|t| t.category_id.as_deref() == Some("merge-category-2")));
+    assert_eq!(transactions[0].subcategory_id, Some("merge-subcategory-1".to_string()));
+
+    let subcategory = sqlx::query_scalar!("SELECT parent_category FROM subcategories WHERE id = 'merge-subcategory-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(subcategory, "merge-category-2");
+
+    // The budget for the whole source category is dropped, as the target already has one.
+    let budgets = sqlx::query!("SELECT id, category_id FROM budgets WHERE user_id = 'abc' ORDER BY id")
+        .fetch_all(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(budgets.len(), 2);
+    assert_eq!(budgets[0].id, "merge-budget-2");
+    assert_eq!(budgets[1].id, "merge-budget-3");
+    assert_eq!(budgets[1].category_id, "merge-category-2");
+
+    let external_account = sqlx::query_scalar!("SELECT default_category_id FROM external_accounts WHERE id = 'merge-external-account-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(external_account, Some("merge-category-2".to_string()));
+
+    let rule = sqlx::query_scalar!("SELECT set_category_id FROM rules WHERE id = 'merge-rule-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(rule, Some("merge-category-2".to_string()));
+
+    let planned = sqlx::query_scalar!("SELECT category_id FROM planned_transactions WHERE id = 'merge-planned-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(planned, Some("merge-category-2".to_string()));
+}
+
+#[sqlx::test(fixtures("users", "merging"))]
+async fn a_category_cannot_be_merged_into_a_category_of_another_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = merge_category(app.pool_state(), app.alice(), "merge-category-1", "merge-category-3").await;
+    assert!(result.is_err());
+
+    get_category_by_id(app.pool_state(), app.alice(), "merge-category-1")
+        .await
+        .unwrap();
+}
+
+#[sqlx::test(fixtures("users", "merging"))]
+async fn subcategories_of_different_categories_can_be_merged(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    merge_subcategory(
+        app.pool_state(),
+        app.alice(),
+        "merge-category-1",
+        "merge-subcategory-1",
+        "merge-subcategory-3",
+    )
+    .await
+    .unwrap();
+
+    let pool = app.pool_state().inner().read().await;
+
+    let count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM subcategories WHERE id = 'merge-subcategory-1'"#)
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(count, 0);
+
+    let transaction = sqlx::query!("SELECT category_id, subcategory_id FROM transactions WHERE id = 'merge-transaction-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(transaction.category_id, Some("merge-category-2".to_string()));
+    assert_eq!(transaction.subcategory_id, Some("merge-subcategory-3".to_string()));
+
+    let budget = sqlx::query!("SELECT category_id, subcategory_id FROM budgets WHERE id = 'merge-budget-3'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(budget.category_id, "merge-category-2");
+    assert_eq!(budget.subcategory_id, Some("merge-subcategory-3".to_string()));
+
+    let external_account = sqlx::query!("SELECT default_category_id, default_subcategory_id FROM external_accounts WHERE id = 'merge-external-account-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(external_account.default_category_id, Some("merge-category-2".to_string()));
+    assert_eq!(external_account.default_subcategory_id, Some("merge-subcategory-3".to_string()));
+
+    let rule = sqlx::query!("SELECT set_category_id, set_subcategory_id FROM rules WHERE id = 'merge-rule-1'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(rule.set_category_id, Some("merge-category-2".to_string()));
+    assert_eq!(rule.set_subcategory_id, Some("merge-subcategory-3".to_string()));
+
+    // The other category keeps its own transactions.
+    let category = sqlx::query_scalar!("SELECT category_id FROM transactions WHERE id = 'merge-transaction-3'")
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+
+    assert_eq!(category, Some("merge-category-1".to_string()));
+}
+
+#[sqlx::test(fixtures("users", "merging"))]
+async fn a_subcategory_cannot_be_merged_into_a_subcategory_of_another_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = merge_subcategory(
+        app.pool_state(),
+        app.alice(),
+        "merge-category-1",
+        "merge-subcategory-1",
+        "merge-subcategory-4",
+    )
+    .await;
+
+    assert!(result.is_err());
+}
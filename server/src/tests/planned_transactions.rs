@@ -0,0 +1,235 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::planned_transactions::new_planned_transaction_dto::NewPlannedTransactionDto;
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+use crate::routes::forecast::get_forecast;
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::planned_transactions::{create_new_planned_transaction, delete_planned_transaction, get_all_planned_transactions, get_planned_transaction_by_id, update_planned_transaction};
+use crate::tests::common::{csv_mapping, TestApp};
+
+const CSV: &str = "Date,Account,Reference,Name,Description,Amount
+2023-03-02,NL12INGB0001234567,1,Landlord BV,Rent March,\"-975,00\"
+2023-04-03,NL12INGB0001234567,2,Landlord BV,Rent April,\"-950,00\"
+2023-04-04,NL12INGB0001234567,3,Jumbo,Groceries,\"-950,00\"";
+
+fn import_body() -> ImportCsvDto {
+    ImportCsvDto {
+        mappings: Some(csv_mapping()),
+        profile_id: None,
+        filename: "export.csv".to_string(),
+        csv: CSV.to_string(),
+    }
+}
+
+fn new_planned_transaction<'a>() -> NewPlannedTransactionDto<'a> {
+    NewPlannedTransactionDto {
+        bank_account_id: "planned-bank-account-1",
+        category_id: Some("planned-category-1"),
+        subcategory_id: None,
+        external_account_id: Some("planned-external-account-1"),
+        description: "Parking",
+        amount: -4500,
+        date: "2023-05-01T00:00:00+00:00",
+        recurrence: Some(Recurrence::Quarter),
+        ends_at: Some("2023-12-31T00:00:00+00:00"),
+    }
+}
+
+#[sqlx::test(fixtures("users", "planned-transactions"))]
+async fn planned_transaction_can_be_created(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let planned = create_new_planned_transaction(
+        app.pool_state(),
+        app.alice(),
+        Json(new_planned_transaction()),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(planned.description, "Parking");
+    assert_eq!(planned.recurrence, Some(Recurrence::Quarter));
+    assert_eq!(planned.ends_at.as_deref(), Some("2023-12-31T00:00:00+00:00"));
+    assert!(!planned.completed);
+
+    let all = get_all_planned_transactions(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(all.len(), 2);
+
+    // Other users cannot see it.
+    let result = get_planned_transaction_by_id(app.pool_state(), app.bob(), &planned.id).await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "planned-transactions"))]
+async fn invalid_planned_transaction_is_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_planned_transaction();
+    body.ends_at = Some("2023-04-01T00:00:00+00:00");
+
+    let result = create_new_planned_transaction(app.pool_state(), app.alice(), Json(body)).await;
+
+    assert!(result.is_err());
+
+    let mut body = new_planned_transaction();
+    body.category_id = None;
+    body.subcategory_id = Some("planned-subcategory-1");
+
+    let result = create_new_planned_transaction(app.pool_state(), app.alice(), Json(body)).await;
+
+    assert!(result.is_err());
+
+    // The bank account belongs to alice.
+    let result = create_new_planned_transaction(
+        app.pool_state(),
+        app.bob(),
+        Json(new_planned_transaction()),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "planned-transactions"))]
+async fn planned_transaction_can_be_updated_and_deleted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let updated = update_planned_transaction(
+        app.pool_state(),
+        app.bob(),
+        "planned-4",
+        Json(NewPlannedTransactionDto {
+            bank_account_id: "planned-bank-account-2",
+            category_id: None,
+            subcategory_id: None,
+            external_account_id: None,
+            description: "New car",
+            amount: -1250000,
+            date: "2030-01-01T00:00:00+00:00",
+            recurrence: None,
+            ends_at: None,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    // Updating opens a completed planned transaction again.
+    assert_eq!(updated.amount, -1250000);
+    assert!(!updated.completed);
+
+    delete_planned_transaction(app.pool_state(), app.bob(), "planned-4")
+        .await
+        .unwrap();
+
+    let result = get_planned_transaction_by_id(app.pool_state(), app.bob(), "planned-4").await;
+
+    assert!(result.is_err());
+
+    let result = delete_planned_transaction(app.pool_state(), app.alice(), "planned-2").await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "planned-transactions"))]
+async fn forecast_adds_planned_transactions_to_balance(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let forecasts = get_forecast(app.pool_state(), app.bob(), Some(3))
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(forecasts.len(), 1);
+
+    let forecast = &forecasts[0];
+
+    // The overdue insurance comes first, the salary stops after two months and the completed
+    // planned transaction is left out.
+    let items: Vec<(&str, i64)> = forecast
+        .items
+        .iter()
+        .map(|item| (&*item.description, item.balance))
+        .collect();
+
+    assert_eq!(forecast.balance, 200000);
+    assert_eq!(items, vec![("Insurance", 170000), ("Salary", 420000), ("Salary", 670000)]);
+    assert_eq!(forecast.end_balance, 670000);
+    assert_eq!(forecast.lowest_balance, 170000);
+    assert_eq!(forecast.balances.len(), 3);
+    assert_eq!(forecast.balances[2].balance, 670000);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn forecast_length_is_limited(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    assert!(get_forecast(app.pool_state(), app.alice(), Some(0)).await.is_err());
+    assert!(get_forecast(app.pool_state(), app.alice(), Some(25)).await.is_err());
+}
+
+#[sqlx::test(fixtures("users", "planned-transactions"))]
+async fn imported_transactions_are_linked_to_planned_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let rows = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body()),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap()
+        .0
+        .rows;
+
+    let matched: Vec<Option<&str>> = rows
+        .iter()
+        .map(|row| row.planned_transaction_id.as_deref())
+        .collect();
+
+    // Both rent payments fulfil an occurrence, the groceries have no external account.
+    assert_eq!(matched, vec![Some("planned-1"), Some("planned-1"), None]);
+
+    let planned = get_planned_transaction_by_id(app.pool_state(), app.alice(), "planned-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(planned.date, "2023-05-01T00:00:00+00:00");
+    assert_eq!(planned.linked_transaction_ids.len(), 2);
+}
+
+#[sqlx::test(fixtures("users", "planned-transactions"))]
+async fn dry_run_does_not_link_planned_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let rows = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body()),
+        ImportQueryDto { dry_run: true },
+    )
+        .await
+        .unwrap()
+        .0
+        .rows;
+
+    assert_eq!(rows[0].planned_transaction_id.as_deref(), Some("planned-1"));
+
+    let planned = get_planned_transaction_by_id(app.pool_state(), app.alice(), "planned-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(planned.date, "2023-03-01T00:00:00+00:00");
+    assert!(planned.linked_transaction_ids.is_empty());
+}
@@ -0,0 +1,295 @@
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::error::error_dto_trait::ToErrorDto;
+use crate::error::Error;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::models::dto::reconciliations::new_reconciliation_dto::NewReconciliationDto;
+use crate::models::dto::transactions::new_correction_dto::NewCorrectionDto;
+use crate::routes::bank_accounts::get_transactions_for_bank_account;
+use crate::routes::corrections::{delete_correction, update_correction};
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::importing::delete_import;
+use crate::routes::reconciliations::{create_reconciliation, create_reconciliation_correction, delete_reconciliation, get_reconciliation_by_id};
+use crate::tests::common::{csv_mapping, TestApp};
+
+fn import_body(rows: &[&str]) -> ImportCsvDto {
+    let mut csv = vec!["Account,Date,Reference,Description,Amount,Name"];
+    csv.extend_from_slice(rows);
+
+    ImportCsvDto {
+        mappings: Some(csv_mapping()),
+        profile_id: None,
+        filename: "bank-export.csv".to_string(),
+        csv: csv.join("\n"),
+    }
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn reconciliation_reports_difference(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let reconciliation = get_reconciliation_by_id(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-1",
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(reconciliation.statement_balance, 300000);
+    assert_eq!(reconciliation.computed_balance, 295000);
+    assert_eq!(reconciliation.difference, 5000);
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn reconciliation_of_other_user_cannot_be_requested(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_reconciliation_by_id(
+        app.pool_state(),
+        app.bob(),
+        "reconciliation-1",
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn reconciliation_can_be_created(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let reconciliation = create_reconciliation(
+        app.pool_state(),
+        app.alice(),
+        Json(NewReconciliationDto {
+            bank_account_id: "reconciliation-bank-account-1",
+            date: "2023-03-31T00:00:00Z",
+            balance: 245000,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(reconciliation.date, "2023-03-31T00:00:00+00:00");
+    assert_eq!(reconciliation.computed_balance, 245000);
+    assert_eq!(reconciliation.difference, 0);
+
+    // There is nothing to correct when the balances already match.
+    let result = create_reconciliation_correction(
+        app.pool_state(),
+        app.alice(),
+        &reconciliation.id,
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn reconciliation_cannot_be_created_for_other_users_bank_account(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = create_reconciliation(
+        app.pool_state(),
+        app.bob(),
+        Json(NewReconciliationDto {
+            bank_account_id: "reconciliation-bank-account-1",
+            date: "2023-03-31T00:00:00Z",
+            balance: 245000,
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn reconciliation_with_invalid_date_is_a_bad_request(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = create_reconciliation(
+        app.pool_state(),
+        app.alice(),
+        Json(NewReconciliationDto {
+            bank_account_id: "reconciliation-bank-account-1",
+            date: "2023-03-31",
+            balance: 245000,
+        }),
+    )
+        .await;
+
+    let Err(Error::HttpError(error)) = result else {
+        panic!("Expected an HTTP error");
+    };
+
+    assert_eq!(error.get_status_code(), Status::BadRequest);
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn correction_closes_the_difference(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let correction = create_reconciliation_correction(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-1",
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(correction.amount, 5000);
+    assert_eq!(correction.date, "2023-01-31T00:00:00Z");
+    assert!(correction.locked);
+
+    let reconciliation = get_reconciliation_by_id(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-1",
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(reconciliation.difference, 0);
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn transactions_up_to_reconciliation_are_locked(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let transactions = get_transactions_for_bank_account(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-bank-account-1".to_string(),
+        PaginationQueryDto { page: 1, limit: 50, cursor: None },
+        false,
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    let locked: Vec<(&str, bool)> = transactions
+        .iter()
+        .map(|transaction| (transaction.id.as_str(), transaction.locked))
+        .collect();
+
+    assert_eq!(
+        locked,
+        vec![
+            ("reconciliation-transaction-3", false),
+            ("reconciliation-correction-1", true),
+            ("reconciliation-transaction-2", true),
+            ("reconciliation-transaction-1", true),
+        ]
+    );
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn locked_correction_cannot_be_changed(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = update_correction(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-correction-1".to_string(),
+        Json(NewCorrectionDto {
+            amount: 10000,
+            description: "Correction".to_string(),
+            bank_account_id: "reconciliation-bank-account-1".to_string(),
+            category_id: None,
+            subcategory_id: None,
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = delete_correction(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-correction-1".to_string(),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // Removing the reconciliation unlocks the correction again.
+    delete_reconciliation(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-1",
+    )
+        .await
+        .unwrap();
+
+    let result = delete_correction(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-correction-1".to_string(),
+    )
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn import_with_locked_transactions_cannot_be_deleted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = delete_import(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-import-1".to_string(),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = delete_import(
+        app.pool_state(),
+        app.alice(),
+        "reconciliation-import-2".to_string(),
+    )
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[sqlx::test(fixtures("users", "reconciliations"))]
+async fn import_into_reconciled_period_is_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(&[
+            "NL12 RABO 12345678910,2023-01-15,reconciliation-00000010,Forgotten,-10.00,Shop",
+        ])),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // Duplicates of locked transactions are skipped, so they don't change the reconciled balance.
+    let result = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body(&[
+            "NL12 RABO 12345678910,2023-01-10,reconciliation-00000001,Groceries,-100.00,Jumbo",
+            "NL12 RABO 12345678910,2023-04-01,reconciliation-00000011,Rent,-500.00,Landlord",
+        ])),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    assert!(result.is_ok());
+}
@@ -0,0 +1,117 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::dto::recurring::update_recurring_series_dto::UpdateRecurringSeriesDto;
+use crate::models::entities::recurring_series::recurrence::Recurrence;
+use crate::models::entities::recurring_series::RecurringStatus;
+use crate::routes::recurring::{detect_recurring_series, get_all_recurring_series, get_upcoming_transactions, update_recurring_series};
+use crate::tests::common::TestApp;
+
+#[sqlx::test(fixtures("users", "recurring"))]
+async fn recurring_series_are_detected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = detect_recurring_series(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    let names: Vec<&str> = series
+        .iter()
+        .map(|series| series.external_account_name.as_str())
+        .collect();
+
+    // The irregular groceries are not a series and the old gym series is removed.
+    assert_eq!(series.len(), 3);
+    assert!(names.contains(&"Insurance"));
+
+    let netflix = series
+        .iter()
+        .find(|series| series.external_account_name == "NETFLIX.COM")
+        .unwrap();
+
+    assert_eq!(netflix.recurrence, Recurrence::Month);
+    assert_eq!(netflix.amount, -1499);
+    assert_eq!(netflix.occurrences, 3);
+    assert_eq!(netflix.status, RecurringStatus::Detected);
+
+    // The names differ, but the transactions are grouped by their external account.
+    let rent = series
+        .iter()
+        .find(|series| series.external_account_id.as_deref() == Some("recurring-external-account-1"))
+        .unwrap();
+
+    assert_eq!(rent.amount, -95000);
+    assert_eq!(rent.occurrences, 3);
+
+    let series = get_all_recurring_series(app.pool_state(), app.bob())
+        .await
+        .unwrap()
+        .0;
+
+    assert!(series.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "recurring"))]
+async fn dismissed_series_stay_dismissed(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = detect_recurring_series(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    let netflix_id = series
+        .iter()
+        .find(|series| series.external_account_name == "NETFLIX.COM")
+        .unwrap()
+        .id
+        .to_string();
+
+    update_recurring_series(
+        app.pool_state(),
+        app.alice(),
+        &netflix_id,
+        Json(UpdateRecurringSeriesDto {
+            status: RecurringStatus::Dismissed,
+        }),
+    )
+        .await
+        .unwrap();
+
+    let series = detect_recurring_series(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    let netflix = series
+        .iter()
+        .find(|series| series.id == netflix_id)
+        .unwrap();
+
+    assert_eq!(netflix.status, RecurringStatus::Dismissed);
+
+    let upcoming = get_upcoming_transactions(app.pool_state(), app.alice(), None)
+        .await
+        .unwrap()
+        .0;
+
+    assert!(!upcoming.is_empty());
+    assert!(upcoming.iter().all(|upcoming| upcoming.series_id != netflix_id));
+}
+
+#[sqlx::test(fixtures("users", "recurring"))]
+async fn series_of_other_user_cannot_be_updated(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = update_recurring_series(
+        app.pool_state(),
+        app.bob(),
+        "recurring-series-2",
+        Json(UpdateRecurringSeriesDto {
+            status: RecurringStatus::Dismissed,
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
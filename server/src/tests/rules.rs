@@ -0,0 +1,479 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::models::dto::rules::apply_rules_dto::ApplyRulesDto;
+use crate::models::dto::rules::new_rule_dto::NewRuleDto;
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::rules::applying::{apply_rules, preview_rules};
+use crate::routes::rules::{create_new_rule, delete_rule, get_all_rules, get_rule_by_id, update_rule};
+use crate::routes::transactions::transaction_management::{get_all_transactions, get_single_transaction};
+use crate::tests::common::{csv_mapping, TestApp};
+
+fn new_rule() -> NewRuleDto {
+    NewRuleDto {
+        name: "Test".to_string(),
+        priority: 3,
+        description_contains: None,
+        description_regex: None,
+        min_amount: None,
+        max_amount: None,
+        iban: None,
+        bank_account_id: None,
+        weekday: None,
+        set_category_id: None,
+        set_subcategory_id: None,
+        set_description: Some("Renamed".to_string()),
+        set_external_account_id: None,
+    }
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn all_rules_are_returned_in_order_of_priority(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let rules = get_all_rules(
+        app.pool_state(),
+        app.alice(),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules.get(0).unwrap().id, "rule-1");
+    assert_eq!(rules.get(1).unwrap().id, "rule-2");
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_that_is_not_owned_by_user_is_not_returned(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_rule_by_id(
+        app.pool_state(),
+        app.alice(),
+        "rule-3",
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_can_be_created(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_rule();
+    body.description_regex = Some(r"^\d+$".to_string());
+    body.set_category_id = Some("rule-category-1".to_string());
+    body.set_subcategory_id = Some("rule-subcategory-1".to_string());
+
+    let rule = create_new_rule(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(rule.name, "Test");
+    assert_eq!(rule.priority, 3);
+    assert_eq!(rule.description_regex, Some(r"^\d+$".to_string()));
+    assert_eq!(rule.set_category_id, Some("rule-category-1".to_string()));
+    assert_eq!(rule.set_subcategory_id, Some("rule-subcategory-1".to_string()));
+    assert_eq!(rule.set_description, Some("Renamed".to_string()));
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_with_invalid_regex_cannot_be_created(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_rule();
+    body.description_regex = Some("(".to_string());
+
+    let result = create_new_rule(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_without_actions_cannot_be_created(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_rule();
+    body.set_description = None;
+
+    let result = create_new_rule(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_cannot_use_category_of_other_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_rule();
+    body.set_category_id = Some("rule-category-3".to_string());
+
+    let result = create_new_rule(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_cannot_use_subcategory_of_other_category(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_rule();
+    body.set_category_id = Some("rule-category-2".to_string());
+    body.set_subcategory_id = Some("rule-subcategory-1".to_string());
+
+    let result = create_new_rule(
+        app.pool_state(),
+        app.alice(),
+        Json(body),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_can_be_updated(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let mut body = new_rule();
+    body.weekday = Some(6);
+
+    let rule = update_rule(
+        app.pool_state(),
+        app.alice(),
+        "rule-2",
+        Json(body),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(rule.id, "rule-2");
+    assert_eq!(rule.name, "Test");
+    assert_eq!(rule.weekday, Some(6));
+    assert_eq!(rule.description_regex, None);
+    assert_eq!(rule.set_category_id, None);
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_of_other_user_cannot_be_deleted(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = delete_rule(
+        app.pool_state(),
+        app.alice(),
+        "rule-3",
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let rule = get_rule_by_id(
+        app.pool_state(),
+        app.bob(),
+        "rule-3",
+    )
+        .await;
+
+    assert!(rule.is_ok());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rules_are_applied_when_importing(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportCsvDto {
+            mappings: Some(csv_mapping()),
+            profile_id: None,
+            filename: "bank-export.csv".to_string(),
+            csv: [
+                "Account,Date,Reference,Description,Amount,Name",
+                "NL12 RABO 12345678910,2023-02-11,1,Payment for JUMBO,-93.00,Jumbo",
+                "NL12 RABO 12345678910,2023-02-12,2,RENT 2023,-920.00,EVIL_LAND_LORD_INC",
+                "NL12 RABO 12345678910,2023-02-13,3,RENT 2023,-1200.00,EVIL_LAND_LORD_INC",
+            ].join("\n"),
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto {
+            page: 1,
+            limit: 10,
+            cursor: None,
+        }
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    let groceries = transactions.iter()
+        .find(|transaction| transaction.follow_number == "1")
+        .unwrap();
+
+    assert_eq!(groceries.description, "Groceries");
+    assert_eq!(groceries.original_description, "Payment for JUMBO");
+    assert_eq!(groceries.category.as_ref().unwrap().id, "rule-category-1");
+    assert_eq!(groceries.subcategory.as_ref().unwrap().id, "rule-subcategory-1");
+    assert_eq!(groceries.external_account.as_ref().unwrap().id, "rule-external-account-1");
+
+    let rent = transactions.iter()
+        .find(|transaction| transaction.follow_number == "2")
+        .unwrap();
+
+    assert_eq!(rent.description, "RENT 2023");
+    assert_eq!(rent.category.as_ref().unwrap().id, "rule-category-2");
+    assert!(rent.subcategory.is_none());
+
+    let out_of_range = transactions.iter()
+        .find(|transaction| transaction.follow_number == "3")
+        .unwrap();
+
+    assert!(out_of_range.category.is_none());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_overrides_the_external_account_of_a_matched_name(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    // The name matches an entry of another external account than the one the rule sets.
+    import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportCsvDto {
+            mappings: Some(csv_mapping()),
+            profile_id: None,
+            filename: "bank-export.csv".to_string(),
+            csv: [
+                "Account,Date,Reference,Description,Amount,Name",
+                "NL12 RABO 12345678910,2023-02-11,1,Payment for JUMBO,-93.00,Jumbo",
+            ].join("\n"),
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let record = sqlx::query!(
+        "SELECT external_account_id, external_account_name_id FROM transactions WHERE follow_number = '1'"
+    )
+        .fetch_one(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    assert_eq!(record.external_account_id.as_deref(), Some("rule-external-account-1"));
+    assert_eq!(record.external_account_name_id, None);
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_preview_returns_changes_without_applying_them(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let changes = preview_rules(
+        app.pool_state(),
+        app.alice(),
+        Json(ApplyRulesDto {
+            from: None,
+            to: None,
+            bank_account_id: None,
+            only_uncategorized: false,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(changes.len(), 2);
+
+    let rent = changes.iter()
+        .find(|change| change.transaction_id == "rule-transaction-2")
+        .unwrap();
+
+    assert_eq!(rent.before.category_id, Some("rule-category-1".to_string()));
+    assert_eq!(rent.after.category_id, Some("rule-category-2".to_string()));
+    assert_eq!(rent.after.description, "Rent");
+
+    let transaction = get_single_transaction(
+        app.pool_state(),
+        app.alice(),
+        "rule-transaction-2",
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(transaction.category.unwrap().id, "rule-category-1");
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rule_preview_respects_filters(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let uncategorized = preview_rules(
+        app.pool_state(),
+        app.alice(),
+        Json(ApplyRulesDto {
+            from: None,
+            to: None,
+            bank_account_id: Some("rule-bank-account-1"),
+            only_uncategorized: true,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(uncategorized.len(), 1);
+    assert_eq!(uncategorized.get(0).unwrap().transaction_id, "rule-transaction-1");
+
+    let in_march = preview_rules(
+        app.pool_state(),
+        app.alice(),
+        Json(ApplyRulesDto {
+            from: Some("2023-03-01T00:00:00Z"),
+            to: Some("2023-03-31T23:59:59Z"),
+            bank_account_id: None,
+            only_uncategorized: false,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(in_march.len(), 1);
+    assert_eq!(in_march.get(0).unwrap().transaction_id, "rule-transaction-2");
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rules_can_be_applied_to_existing_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let changes = apply_rules(
+        app.pool_state(),
+        app.alice(),
+        Json(ApplyRulesDto {
+            from: None,
+            to: None,
+            bank_account_id: None,
+            only_uncategorized: false,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(changes.len(), 2);
+
+    let groceries = get_single_transaction(
+        app.pool_state(),
+        app.alice(),
+        "rule-transaction-1",
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(groceries.description, "Groceries");
+    assert_eq!(groceries.category.unwrap().id, "rule-category-1");
+    assert_eq!(groceries.subcategory.unwrap().id, "rule-subcategory-1");
+    assert_eq!(groceries.external_account.unwrap().id, "rule-external-account-1");
+
+    let rent = get_single_transaction(
+        app.pool_state(),
+        app.alice(),
+        "rule-transaction-2",
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(rent.category.unwrap().id, "rule-category-2");
+
+    let changes = preview_rules(
+        app.pool_state(),
+        app.alice(),
+        Json(ApplyRulesDto {
+            from: None,
+            to: None,
+            bank_account_id: None,
+            only_uncategorized: false,
+        }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(changes.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "rules"))]
+async fn rules_can_be_applied_to_transactions_with_a_matched_name(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    sqlx::query!(
+        r#"
+            UPDATE transactions
+            SET external_account_id = 'rule-external-account-2',
+                external_account_name_id = 'rule-external-account-name-1'
+            WHERE id = 'rule-transaction-1';
+        "#
+    )
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    apply_rules(
+        app.pool_state(),
+        app.alice(),
+        Json(ApplyRulesDto {
+            from: None,
+            to: None,
+            bank_account_id: None,
+            only_uncategorized: false,
+        }),
+    )
+        .await
+        .unwrap();
+
+    let record = sqlx::query!(
+        "SELECT external_account_id, external_account_name_id FROM transactions WHERE id = 'rule-transaction-1'"
+    )
+        .fetch_one(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    assert_eq!(record.external_account_id.as_deref(), Some("rule-external-account-1"));
+    assert_eq!(record.external_account_name_id, None);
+}
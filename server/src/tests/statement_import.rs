@@ -0,0 +1,194 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::models::dto::importing::import_statement_dto::{ImportStatementDto, StatementFormat};
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::routes::importing::statement_import::import_statement;
+use crate::routes::transactions::transaction_management::get_all_transactions;
+use crate::tests::common::TestApp;
+
+const MT940: &str = ":20:STATEMENT-1
+:25:NL12RABO0123456789EUR
+:28C:1
+:60F:C230101EUR1000,00
+:61:230120C3000,00NTRF2023012001
+:86:/NAME/Work/REMI/USTD//Salary/
+:61:230115D12,34NTRF2023011501
+:86:/NAME/Jumbo/REMI/USTD//Groceries/
+:62F:C230131EUR3987,66
+-";
+
+#[sqlx::test(fixtures("users"))]
+async fn statement_format_is_detected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    import_statement(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportStatementDto {
+            filename: "statement.sta".to_string(),
+            content: MT940.to_string(),
+            format: None,
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap();
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert_eq!(transactions.len(), 2);
+
+    // The records are sorted by date before importing, so the order of the statement is ignored.
+    let salary = transactions.get(0).unwrap();
+    assert_eq!(salary.follow_number, "NL12RABO0123456789-2023012001");
+    assert_eq!(salary.description, "Salary");
+    assert_eq!(salary.amount, 300000);
+    assert_eq!(salary.external_account_name, "Work");
+    assert_eq!(salary.bank_account.as_ref().unwrap().iban, "NL12RABO0123456789");
+
+    let groceries = transactions.get(1).unwrap();
+    assert_eq!(groceries.follow_number, "NL12RABO0123456789-2023011501");
+    assert_eq!(groceries.amount, -1234);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn statement_can_be_imported_twice(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    for _ in 0..2 {
+        import_statement(
+            app.pool_state(),
+            app.alice(),
+            Json(ImportStatementDto {
+                filename: "statement.sta".to_string(),
+                content: MT940.to_string(),
+                format: Some(StatementFormat::Mt940),
+            }),
+            ImportQueryDto { dry_run: false },
+        )
+            .await
+            .unwrap();
+    }
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert_eq!(transactions.len(), 2);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn statement_import_reports_rows(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let report = import_statement(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportStatementDto {
+            filename: "statement.sta".to_string(),
+            content: MT940.to_string(),
+            format: None,
+        }),
+        ImportQueryDto { dry_run: true },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(report.dry_run);
+    assert_eq!(report.inserted, 2);
+    assert_eq!(report.new_bank_accounts, 1);
+
+    let transactions = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
+    )
+        .await
+        .unwrap()
+        .0
+        .into_items();
+
+    assert!(transactions.is_empty());
+
+    let report = import_statement(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportStatementDto {
+            filename: "statement.sta".to_string(),
+            content: MT940.to_string(),
+            format: None,
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert!(!report.dry_run);
+    assert_eq!(report.inserted, 2);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn same_reference_on_different_accounts_is_imported(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let content = ":20:STATEMENT-1
+:25:NL12RABO0123456789
+:61:230115D12,34NTRF2023011501
+:20:STATEMENT-2
+:25:NL34INGB0123456789
+:61:230115D56,78NTRF2023011501
+-";
+
+    let report = import_statement(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportStatementDto {
+            filename: "statement.sta".to_string(),
+            content: content.to_string(),
+            format: Some(StatementFormat::Mt940),
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(report.inserted, 2);
+    assert_eq!(report.duplicates, 0);
+}
+
+#[sqlx::test(fixtures("users"))]
+async fn unknown_statement_format_is_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = import_statement(
+        app.pool_state(),
+        app.alice(),
+        Json(ImportStatementDto {
+            filename: "statement.txt".to_string(),
+            content: "Just some text".to_string(),
+            format: None,
+        }),
+        ImportQueryDto { dry_run: false },
+    )
+        .await;
+
+    assert!(result.is_err());
+}
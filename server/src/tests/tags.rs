@@ -0,0 +1,293 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
+use crate::models::dto::tags::new_tag_dto::NewTagDto;
+use crate::models::dto::transactions::bulk_update_transaction_tags_dto::BulkUpdateTransactionTagsDto;
+use crate::routes::aggregates::get_tag_totals;
+use crate::routes::tags::{create_new_tag, delete_tag, get_all_tags, get_tag_by_id, get_tag_transactions, update_tag};
+use crate::routes::transactions::transaction_management::{bulk_update_transaction_tags, get_single_transaction};
+use crate::tests::common::TestApp;
+
+fn pagination() -> PaginationQueryDto {
+    PaginationQueryDto { page: 1, limit: 10, cursor: None }
+}
+
+async fn tag_transaction_ids(app: &TestApp, tag_id: &str) -> Vec<String> {
+    get_tag_transactions(app.pool_state(), app.alice(), tag_id.to_string(), pagination())
+        .await
+        .unwrap()
+        .0
+        .into_items()
+        .into_iter()
+        .map(|transaction| transaction.id)
+        .collect()
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn tags_can_be_created_and_updated(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let tag = create_new_tag(
+        app.pool_state(),
+        app.alice(),
+        Json(NewTagDto { name: "  Birthday ", hex_color: "30ff30" }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(tag.name, "Birthday");
+
+    let tags = get_all_tags(app.pool_state(), app.alice())
+        .await
+        .unwrap()
+        .0;
+
+    let names: Vec<&str> = tags.iter().map(|tag| &*tag.name).collect();
+    assert_eq!(names, vec!["Birthday", "Holiday", "Shopping", "Work"]);
+
+    let updated = update_tag(
+        app.pool_state(),
+        app.alice(),
+        &tag.id,
+        Json(NewTagDto { name: "Party", hex_color: "30ff30" }),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(updated.name, "Party");
+
+    // Other users cannot see it.
+    let result = get_tag_by_id(app.pool_state(), app.bob(), &tag.id).await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn invalid_tag_names_are_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = create_new_tag(
+        app.pool_state(),
+        app.alice(),
+        Json(NewTagDto { name: "  ", hex_color: "30ff30" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = update_tag(
+        app.pool_state(),
+        app.alice(),
+        "tag-3",
+        Json(NewTagDto { name: "Work", hex_color: "30ff30" }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // Names only have to be unique per user.
+    let result = create_new_tag(
+        app.pool_state(),
+        app.bob(),
+        Json(NewTagDto { name: "Work", hex_color: "30ff30" }),
+    )
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn tagged_transactions_are_returned(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let ids = tag_transaction_ids(&app, "tag-1").await;
+
+    assert_eq!(ids, vec!["aggregate-split-1", "aggregate-transaction-1"]);
+
+    let transaction =
+        get_single_transaction(app.pool_state(), app.alice(), "aggregate-transaction-2")
+            .await
+            .unwrap()
+            .0;
+
+    assert_eq!(transaction.tag_ids, vec!["tag-2"]);
+
+    let result = get_tag_transactions(
+        app.pool_state(),
+        app.alice(),
+        "tag-4".to_string(),
+        pagination(),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn transactions_can_be_tagged_in_bulk(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    bulk_update_transaction_tags(
+        app.pool_state(),
+        app.alice(),
+        Json(BulkUpdateTransactionTagsDto {
+            transactions: vec![
+                "aggregate-transaction-1".to_string(),
+                "aggregate-transaction-3".to_string(),
+            ],
+            add: vec!["tag-1".to_string(), "tag-3".to_string()],
+            remove: vec!["tag-2".to_string()],
+        }),
+    )
+        .await
+        .unwrap();
+
+    let ids = tag_transaction_ids(&app, "tag-1").await;
+    assert_eq!(
+        ids,
+        vec!["aggregate-transaction-3", "aggregate-split-1", "aggregate-transaction-1"]
+    );
+
+    let ids = tag_transaction_ids(&app, "tag-3").await;
+    assert_eq!(ids, vec!["aggregate-transaction-3", "aggregate-transaction-1"]);
+
+    bulk_update_transaction_tags(
+        app.pool_state(),
+        app.alice(),
+        Json(BulkUpdateTransactionTagsDto {
+            transactions: vec!["aggregate-transaction-1".to_string()],
+            add: vec![],
+            remove: vec!["tag-1".to_string(), "tag-3".to_string()],
+        }),
+    )
+        .await
+        .unwrap();
+
+    let ids = tag_transaction_ids(&app, "tag-1").await;
+    assert_eq!(ids, vec!["aggregate-transaction-3", "aggregate-split-1"]);
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn bulk_tagging_checks_ownership(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    // The tag belongs to bob.
+    let result = bulk_update_transaction_tags(
+        app.pool_state(),
+        app.alice(),
+        Json(BulkUpdateTransactionTagsDto {
+            transactions: vec!["aggregate-transaction-1".to_string()],
+            add: vec!["tag-4".to_string()],
+            remove: vec![],
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    // The transaction belongs to alice.
+    let result = bulk_update_transaction_tags(
+        app.pool_state(),
+        app.bob(),
+        Json(BulkUpdateTransactionTagsDto {
+            transactions: vec!["aggregate-transaction-1".to_string()],
+            add: vec!["tag-4".to_string()],
+            remove: vec![],
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+
+    let result = bulk_update_transaction_tags(
+        app.pool_state(),
+        app.alice(),
+        Json(BulkUpdateTransactionTagsDto {
+            transactions: vec!["aggregate-transaction-1".to_string()],
+            add: vec![],
+            remove: vec![],
+        }),
+    )
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn deleted_tag_is_removed_from_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    delete_tag(app.pool_state(), app.alice(), "tag-1")
+        .await
+        .unwrap();
+
+    let transaction =
+        get_single_transaction(app.pool_state(), app.alice(), "aggregate-transaction-1")
+            .await
+            .unwrap()
+            .0;
+
+    assert!(transaction.tag_ids.is_empty());
+
+    let result = delete_tag(app.pool_state(), app.alice(), "tag-4").await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn tag_totals_are_summed(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let totals = get_tag_totals(app.pool_state(), app.alice(), None, None)
+        .await
+        .unwrap()
+        .0;
+
+    let totals: Vec<(&str, i64, i64, i64)> = totals
+        .iter()
+        .map(|total| (&*total.tag_id, total.income, total.expense, total.net))
+        .collect();
+
+    // The split and the rest of its parent add up to the full amount, moves are left out.
+    assert_eq!(
+        totals,
+        vec![("tag-3", 0, 0, 0), ("tag-1", 0, 10000, -10000), ("tag-2", 300000, 0, 300000)]
+    );
+
+    let totals = get_tag_totals(
+        app.pool_state(),
+        app.alice(),
+        Some("2023-01-20T00:00:00Z".to_string()),
+        None,
+    )
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(totals[1].expense, 0);
+    assert_eq!(totals[2].income, 300000);
+}
+
+#[sqlx::test(fixtures("users", "aggregates", "tags"))]
+async fn tag_totals_report_missing_rates(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    sqlx::query!("UPDATE transactions SET currency = 'GBP' WHERE id = 'aggregate-transaction-2'")
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    let totals = get_tag_totals(app.pool_state(), app.alice(), None, None)
+        .await
+        .unwrap()
+        .0;
+
+    let work = totals.iter().find(|total| total.tag_id == "tag-2").unwrap();
+    assert_eq!(work.income, 0);
+    assert_eq!(work.missing_rates, vec!["GBP".to_string()]);
+
+    let holiday = totals.iter().find(|total| total.tag_id == "tag-3").unwrap();
+    assert!(holiday.missing_rates.is_empty());
+}
@@ -3,9 +3,11 @@ use sqlx::PgPool;
 
 use crate::models::dto::pagination::pagination_query_dto::PaginationQueryDto;
 use crate::models::dto::transactions::bulk_update_transaction_categories_dto::BulkUpdateTransactionCategoriesDto;
+use crate::models::dto::transactions::transaction_search_query_dto::TransactionSearchQueryDto;
 use crate::models::dto::transactions::transaction_set_category_dto::TransactionSetCategoryDto;
 use crate::models::dto::transactions::update_transaction_dto::UpdateTransactionDto;
-use crate::routes::transactions::transaction_management::{bulk_update_transaction_categories, change_category_for_transaction, get_all_transactions, get_single_transaction, update_transaction};
+use crate::routes::transactions::transaction_management::{bulk_update_transaction_categories, change_category_for_transaction, get_all_transactions, get_single_transaction, search_transactions, update_transaction};
+use crate::models::entities::transaction::transaction_type::TransactionType;
 use crate::tests::common::TestApp;
 
 #[sqlx::test(fixtures("users", "transactions"))]
@@ -15,7 +17,7 @@ async fn transactions_are_returned_correctly(pool: PgPool) {
     let transactions = get_all_transactions(
         app.pool_state(),
         app.alice(),
-        PaginationQueryDto { page: 1, limit: 10 },
+        PaginationQueryDto { page: 1, limit: 10, cursor: None },
     )
     .await
     .unwrap()
@@ -35,7 +37,7 @@ async fn transactions_can_be_paginated(pool: PgPool) {
     let page = get_all_transactions(
         app.pool_state(),
         app.alice(),
-        PaginationQueryDto { page: 1, limit: 2 },
+        PaginationQueryDto { page: 1, limit: 2, cursor: None },
     )
     .await
     .unwrap()
@@ -52,7 +54,7 @@ async fn transactions_can_be_paginated(pool: PgPool) {
     let page = get_all_transactions(
         app.pool_state(),
         app.alice(),
-        PaginationQueryDto { page: 2, limit: 2 },
+        PaginationQueryDto { page: 2, limit: 2, cursor: None },
     )
     .await
     .unwrap()
@@ -66,6 +68,64 @@ async fn transactions_can_be_paginated(pool: PgPool) {
     assert_eq!(transactions.get(0).unwrap().id, "transaction-3");
 }
 
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn transactions_can_be_paginated_with_cursor(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+
+    let page = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 2, cursor: None },
+    )
+    .await
+    .unwrap()
+    .0;
+
+    let cursor = page.next_cursor().unwrap().to_string();
+
+    // A transaction that is imported between loading the pages doesn't shift the next page.
+    sqlx::query(
+        r#"
+            INSERT INTO transactions
+            VALUES ('transaction-7', 'abc', 'transaction', '00000007', 'Coffee', 'Coffee', -300, -300, '2023-03-01 08:00:00.000000 +00:00', null, null, 'Cafe', null, 'bank-account-1', 'import-1', null, 2);
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let page = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 2, cursor: Some(cursor) },
+    )
+    .await
+    .unwrap()
+    .0;
+
+    assert!(page.is_done());
+    assert_eq!(page.next_cursor(), None);
+
+    let transactions = page.into_items();
+
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions.get(0).unwrap().id, "transaction-3");
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn invalid_cursor_is_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = get_all_transactions(
+        app.pool_state(),
+        app.alice(),
+        PaginationQueryDto { page: 1, limit: 2, cursor: Some("abc".to_string()) },
+    )
+    .await;
+
+    assert!(result.is_err());
+}
+
 #[sqlx::test(fixtures("users", "transactions"))]
 async fn a_single_transaction_is_returned_correctly(pool: PgPool) {
     let app = TestApp::new(pool);
@@ -283,6 +343,7 @@ async fn category_of_multiple_transactions_can_be_set_at_once(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         },
     )
         .await
@@ -322,6 +383,7 @@ async fn subcategory_of_multiple_transactions_can_be_set_at_once(pool: PgPool) {
         PaginationQueryDto {
             page: 1,
             limit: 10,
+            cursor: None,
         },
     )
         .await
@@ -419,3 +481,133 @@ async fn cannot_bulk_update_no_transactions(pool: PgPool) {
 
     assert!(result.is_err());
 }
+
+fn search_query() -> TransactionSearchQueryDto {
+    TransactionSearchQueryDto {
+        q: None,
+        from: None,
+        to: None,
+        min_amount: None,
+        max_amount: None,
+        category_id: None,
+        subcategory_id: None,
+        uncategorized: false,
+        bank_account_id: None,
+        tag_id: None,
+        import_id: None,
+        transaction_type: None,
+        page: 1,
+        limit: 10,
+        cursor: None,
+    }
+}
+
+async fn search_ids(app: &TestApp, query: TransactionSearchQueryDto) -> Vec<String> {
+    search_transactions(
+        app.pool_state(),
+        app.alice(),
+        query,
+    )
+    .await
+    .unwrap()
+    .0
+    .into_items()
+    .into_iter()
+    .map(|transaction| transaction.id)
+    .collect()
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn transactions_can_be_searched_by_text(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let search = |text: &str| TransactionSearchQueryDto {
+        q: Some(text.to_string()),
+        ..search_query()
+    };
+
+    // Matches the original description, ignoring case.
+    assert_eq!(search_ids(&app, search("jumbo")).await, vec!["transaction-2"]);
+
+    // Every word has to occur, but not necessarily in the same column.
+    assert_eq!(search_ids(&app, search("work salary")).await, vec!["transaction-1"]);
+    assert!(search_ids(&app, search("work jumbo")).await.is_empty());
+
+    // Wildcards are matched literally.
+    assert_eq!(search_ids(&app, search("evil_land")).await, vec!["transaction-3"]);
+    assert!(search_ids(&app, search("evil%lord")).await.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn transactions_can_be_searched_with_filters(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let uncategorized = TransactionSearchQueryDto {
+        uncategorized: true,
+        ..search_query()
+    };
+
+    assert_eq!(search_ids(&app, uncategorized).await, vec!["transaction-1", "transaction-3"]);
+
+    let amount = TransactionSearchQueryDto {
+        min_amount: Some(-10000),
+        max_amount: Some(0),
+        ..search_query()
+    };
+
+    assert_eq!(search_ids(&app, amount).await, vec!["transaction-2"]);
+
+    let category = TransactionSearchQueryDto {
+        category_id: Some("transaction-category-1".to_string()),
+        from: Some("2023-02-01T00:00:00+00:00".to_string()),
+        to: Some("2023-02-28T00:00:00+00:00".to_string()),
+        ..search_query()
+    };
+
+    assert_eq!(search_ids(&app, category).await, vec!["transaction-2"]);
+
+    let invalid_date = search_transactions(
+        app.pool_state(),
+        app.alice(),
+        TransactionSearchQueryDto {
+            from: Some("yesterday".to_string()),
+            ..search_query()
+        },
+    )
+    .await;
+
+    assert!(invalid_date.is_err());
+
+    let other_import = TransactionSearchQueryDto {
+        import_id: Some("import-2".to_string()),
+        ..search_query()
+    };
+
+    assert!(search_ids(&app, other_import).await.is_empty());
+
+    let corrections = TransactionSearchQueryDto {
+        transaction_type: Some(TransactionType::Correction),
+        bank_account_id: Some("bank-account-1".to_string()),
+        ..search_query()
+    };
+
+    assert!(search_ids(&app, corrections).await.is_empty());
+}
+
+#[sqlx::test(fixtures("users", "transactions"))]
+async fn uncategorized_search_cannot_have_category(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let result = search_transactions(
+        app.pool_state(),
+        app.alice(),
+        TransactionSearchQueryDto {
+            uncategorized: true,
+            category_id: Some("transaction-category-1".to_string()),
+            ..search_query()
+        },
+    )
+    .await;
+
+    assert!(result.is_err());
+}
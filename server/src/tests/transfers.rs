@@ -0,0 +1,253 @@
+use rocket::serde::json::Json;
+use sqlx::PgPool;
+use crate::models::csv::csv_mapping::CsvMapping;
+use crate::models::dto::aggregates::series_query_dto::{SeriesGroupBy, SeriesInterval, SeriesQueryDto};
+use crate::models::dto::importing::import_csv_dto::ImportCsvDto;
+use crate::models::dto::importing::import_query_dto::ImportQueryDto;
+use crate::routes::aggregates::get_series;
+use crate::routes::categories::get_category_by_id;
+use crate::routes::categories::subcategories::{get_subcategories, get_subcategory_by_id};
+use crate::routes::importing::csv_import::import_csv;
+use crate::routes::transactions::transaction_management::get_single_transaction;
+use crate::routes::transactions::transfers::{link_transfer, unlink_transfer};
+use crate::tests::common::{column, csv_mapping, TestApp};
+
+const CSV: &str = "Date,Account,Reference,Name,Counterparty,Description,Amount
+2023-03-01,NL11INGB0001111111,1,Savings,NL22INGB0002222222,To savings,\"-500,00\"
+2023-03-05,NL11INGB0001111111,2,Jumbo,NL99INGB0009999999,Groceries,\"-25,00\"
+2023-03-10,NL11INGB0001111111,3,Savings,NL22INGB0002222222,To savings,\"-100,00\"
+2023-03-11,NL22INGB0002222222,4,Checking,NL11INGB0001111111,From checking,\"100,00\"
+2023-03-20,NL11INGB0001111111,5,Savings,NL22INGB0002222222,To savings,\"-75,00\"";
+
+fn import_body() -> ImportCsvDto {
+    ImportCsvDto {
+        mappings: Some(CsvMapping {
+            external_account_iban: Some(column("Counterparty")),
+            ..csv_mapping()
+        }),
+        profile_id: None,
+        filename: "export.csv".to_string(),
+        csv: CSV.to_string(),
+    }
+}
+
+fn april(group_by: Option<SeriesGroupBy>) -> SeriesQueryDto {
+    SeriesQueryDto {
+        from: Some("2023-04-01T00:00:00Z".to_string()),
+        to: Some("2023-05-01T00:00:00Z".to_string()),
+        interval: SeriesInterval::Month,
+        group_by,
+    }
+}
+
+async fn related_transfer(app: &TestApp, id: &str) -> Option<String> {
+    get_single_transaction(app.pool_state(), app.alice(), id)
+        .await
+        .unwrap()
+        .0
+        .related_transfer_transaction
+}
+
+#[sqlx::test(fixtures("users", "transfers"))]
+async fn imported_transfers_are_linked(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let rows = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body()),
+        ImportQueryDto { dry_run: false },
+    )
+        .await
+        .unwrap()
+        .0
+        .rows;
+
+    // The first row is linked with the leg that was imported before, the fourth row with the third
+    // row of the same import. The last row is booked too long before the other leg.
+    let linked: Vec<bool> = rows
+        .iter()
+        .map(|row| row.transfer_transaction_id.is_some())
+        .collect();
+
+    assert_eq!(linked, vec![true, false, false, true, false]);
+    assert_eq!(rows[0].transfer_transaction_id.as_deref(), Some("transfer-transaction-1"));
+
+    let first = rows[0].transfer_transaction_id.as_deref().unwrap();
+    let first_id = related_transfer(&app, first).await.unwrap();
+
+    assert_eq!(related_transfer(&app, &first_id).await.as_deref(), Some(first));
+
+    let fourth = rows[3].transfer_transaction_id.as_deref().unwrap();
+
+    assert!(related_transfer(&app, fourth).await.is_some());
+    assert_eq!(related_transfer(&app, "transfer-transaction-2").await, None);
+}
+
+#[sqlx::test(fixtures("users", "transfers"))]
+async fn dry_run_does_not_link_transfers(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let rows = import_csv(
+        app.pool_state(),
+        app.alice(),
+        Json(import_body()),
+        ImportQueryDto { dry_run: true },
+    )
+        .await
+        .unwrap()
+        .0
+        .rows;
+
+    assert_eq!(rows[0].transfer_transaction_id.as_deref(), Some("transfer-transaction-1"));
+    assert_eq!(related_transfer(&app, "transfer-transaction-1").await, None);
+}
+
+#[sqlx::test(fixtures("users", "transfers"))]
+async fn transfer_can_be_linked_and_unlinked(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let series = get_series(app.pool_state(), app.alice(), april(None)).await.unwrap().0;
+
+    assert_eq!(series[0].income, 320000);
+    assert_eq!(series[0].expense, 20000);
+
+    link_transfer(app.pool_state(), app.alice(), "transfer-transaction-3", "transfer-transaction-4")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        related_transfer(&app, "transfer-transaction-3").await.as_deref(),
+        Some("transfer-transaction-4")
+    );
+    assert_eq!(
+        related_transfer(&app, "transfer-transaction-4").await.as_deref(),
+        Some("transfer-transaction-3")
+    );
+
+    // The transfer is neither income nor expense, but it still moved money between the accounts.
+    let series = get_series(app.pool_state(), app.alice(), april(None)).await.unwrap().0;
+
+    assert_eq!(series[0].income, 300000);
+    assert_eq!(series[0].expense, 0);
+
+    let series = get_series(
+        app.pool_state(),
+        app.alice(),
+        april(Some(SeriesGroupBy::BankAccount)),
+    )
+        .await
+        .unwrap()
+        .0;
+
+    let savings = series
+        .iter()
+        .find(|bucket| bucket.group_id.as_deref() == Some("transfer-bank-account-2"))
+        .unwrap();
+
+    assert_eq!(savings.income, 20000);
+
+    // Unlinking either leg unlinks both.
+    unlink_transfer(app.pool_state(), app.alice(), "transfer-transaction-4")
+        .await
+        .unwrap();
+
+    assert_eq!(related_transfer(&app, "transfer-transaction-3").await, None);
+    assert_eq!(related_transfer(&app, "transfer-transaction-4").await, None);
+
+    let series = get_series(app.pool_state(), app.alice(), april(None)).await.unwrap().0;
+
+    assert_eq!(series[0].expense, 20000);
+}
+
+#[sqlx::test(fixtures("users", "categories", "subcategories", "transfers"))]
+async fn transfers_are_left_out_of_category_amounts(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    sqlx::query!("UPDATE transactions SET category_id = 'category-1' WHERE id = 'transfer-transaction-5'")
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    let category = get_category_by_id(app.pool_state(), app.alice(), "category-1").await.unwrap().0;
+
+    assert_eq!(category.amount, 300000);
+    assert_eq!(category.subcategories[0].amount, 0);
+
+    // Both legs end up in the category, but only the outgoing leg in the subcategory.
+    sqlx::query!(
+        r#"
+            UPDATE transactions
+            SET category_id = 'category-1', subcategory_id = CASE WHEN id = 'transfer-transaction-3' THEN 'subcategory-1' END
+            WHERE id IN ('transfer-transaction-3', 'transfer-transaction-4')
+        "#
+    )
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    link_transfer(app.pool_state(), app.alice(), "transfer-transaction-3", "transfer-transaction-4")
+        .await
+        .unwrap();
+
+    let category = get_category_by_id(app.pool_state(), app.alice(), "category-1").await.unwrap().0;
+
+    assert_eq!(category.amount, 300000);
+    assert_eq!(category.subcategories[0].amount, 0);
+
+    let subcategories = get_subcategories(app.pool_state(), app.alice(), "category-1").await.unwrap().0;
+    assert_eq!(subcategories[0].amount, 0);
+
+    let subcategory = get_subcategory_by_id(app.pool_state(), app.alice(), "category-1", "subcategory-1")
+        .await
+        .unwrap()
+        .0;
+
+    assert_eq!(subcategory.amount, 0);
+}
+
+#[sqlx::test(fixtures("users", "transfers"))]
+async fn invalid_transfers_are_rejected(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let link = |id: &'static str, other_id: &'static str| {
+        link_transfer(app.pool_state(), app.alice(), id, other_id)
+    };
+
+    // The same transaction, the same bank account and two incoming transactions.
+    assert!(link("transfer-transaction-3", "transfer-transaction-3").await.is_err());
+    assert!(link("transfer-transaction-3", "transfer-transaction-5").await.is_err());
+    assert!(link("transfer-transaction-4", "transfer-transaction-5").await.is_err());
+
+    // The transaction of bob cannot be used.
+    assert!(link("transfer-transaction-4", "transfer-transaction-6").await.is_err());
+
+    let result = unlink_transfer(app.pool_state(), app.alice(), "transfer-transaction-3").await;
+
+    assert!(result.is_err());
+
+    link("transfer-transaction-3", "transfer-transaction-4").await.unwrap();
+
+    // A transaction can only be part of a single transfer.
+    assert!(link("transfer-transaction-3", "transfer-transaction-1").await.is_err());
+
+    let result = unlink_transfer(app.pool_state(), app.bob(), "transfer-transaction-3").await;
+
+    assert!(result.is_err());
+}
+
+#[sqlx::test(fixtures("users", "transfers"))]
+async fn deleting_a_leg_unlinks_the_other(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    link_transfer(app.pool_state(), app.alice(), "transfer-transaction-3", "transfer-transaction-4")
+        .await
+        .unwrap();
+
+    sqlx::query!("DELETE FROM transactions WHERE id = 'transfer-transaction-3'")
+        .execute(&*app.pool_state().inner().read().await)
+        .await
+        .unwrap();
+
+    assert_eq!(related_transfer(&app, "transfer-transaction-4").await, None);
+}
@@ -0,0 +1,58 @@
+use crate::error::http_error::HttpError;
+use crate::prelude::*;
+
+/// The currency used when nothing else is known, as everything was in euro before currencies were
+/// supported.
+pub const DEFAULT_CURRENCY: &str = "EUR";
+
+/// Returns the number of digits after the decimal separator of the currency, which determines the
+/// minor unit amounts are stored in. Keep this in sync with the `currency_minor_units` database
+/// function.
+pub fn minor_units(currency: &str) -> u32 {
+    match currency {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+/// Returns whether the value looks like an ISO 4217 currency code.
+pub fn is_currency_code(value: &str) -> bool {
+    value.len() == 3 && value.chars().all(|char| char.is_ascii_uppercase())
+}
+
+/// Uppercases the currency code and checks it is valid.
+pub fn parse_currency(value: &str) -> Result<String> {
+    let currency = value.trim().to_uppercase();
+
+    if !is_currency_code(&currency) {
+        return HttpError::new(400)
+            .message(format!("'{}' is not a valid currency code", value))
+            .into();
+    }
+
+    Ok(currency)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::currency::{is_currency_code, minor_units, parse_currency};
+
+    #[test]
+    fn minor_units_depend_on_currency() {
+        assert_eq!(minor_units("EUR"), 2);
+        assert_eq!(minor_units("JPY"), 0);
+        assert_eq!(minor_units("KWD"), 3);
+    }
+
+    #[test]
+    fn currency_codes_are_validated() {
+        assert!(is_currency_code("USD"));
+        assert!(!is_currency_code("usd"));
+        assert!(!is_currency_code("EURO"));
+
+        assert_eq!(parse_currency(" gbp").unwrap(), "GBP");
+        assert!(parse_currency("12").is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, TimeZone, Utc};
+use sqlx::types::time::OffsetDateTime;
+
+use crate::error::http_error::HttpError;
+use crate::prelude::*;
+
+/// Converts a datetime returned by the database to a chrono datetime, which is easier to do
+/// calendar arithmetic with.
+pub fn to_utc(datetime: OffsetDateTime) -> DateTime<Utc> {
+    Utc.timestamp_opt(datetime.unix_timestamp(), 0)
+        .single()
+        .expect("Every unix timestamp should map to a single UTC datetime")
+}
+
+/// Parses an RFC 3339 datetime, like the ones used in query parameters, into a datetime that can
+/// be bound to a query. Invalid datetimes are rejected with a 400.
+pub fn parse_rfc3339(value: &str) -> Result<OffsetDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .and_then(|datetime| OffsetDateTime::from_unix_timestamp(datetime.timestamp()).ok())
+        .ok_or_else(|| {
+            HttpError::new(400)
+                .message(format!("'{}' is not a valid RFC 3339 datetime", value))
+                .into()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::http::Status;
+
+    use crate::error::error_dto_trait::ToErrorDto;
+    use crate::error::Error;
+    use crate::utils::datetime::parse_rfc3339;
+
+    #[test]
+    fn invalid_datetimes_are_a_bad_request() {
+        assert_eq!(parse_rfc3339("2023-02-01T12:00:00+01:00").unwrap().unix_timestamp(), 1675249200);
+
+        let Err(Error::HttpError(error)) = parse_rfc3339("2023-02-01") else {
+            panic!("Expected an HTTP error");
+        };
+
+        assert_eq!(error.get_status_code(), Status::BadRequest);
+    }
+}
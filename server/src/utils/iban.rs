@@ -0,0 +1,19 @@
+/// IBANs are often written with spaces between the groups, so those are removed before comparing
+/// or storing them.
+pub fn normalize_iban(iban: &str) -> String {
+    iban.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::iban::normalize_iban;
+
+    #[test]
+    fn spaces_are_removed_and_letters_uppercased() {
+        assert_eq!(normalize_iban(" nl12 rabo 0123 4567 89"), "NL12RABO0123456789");
+        assert_eq!(normalize_iban("NL12RABO0123456789"), "NL12RABO0123456789");
+    }
+}
@@ -1,5 +1,8 @@
+pub mod currency;
+pub mod datetime;
 pub mod diff;
 pub mod guard_role;
+pub mod iban;
 pub mod rand_string;
 pub mod try_collect;
 pub mod single_use_connection;